@@ -63,9 +63,7 @@ impl<Owner> Eq for Id<Owner> {}
 
 impl<Owner> PartialOrd for Id<Owner> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let Self { inner, .. } = self;
-        let Self { inner: other, .. } = other;
-        inner.partial_cmp(other)
+        Some(self.cmp(other))
     }
 }
 
@@ -5,3 +5,4 @@
 #![forbid(unsafe_code)]
 
 pub mod id;
+pub mod metrics;
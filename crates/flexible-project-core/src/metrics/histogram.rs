@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets in microseconds.
+const BOUNDS: [u64; 11] = [
+    1_000, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000, 1_000_000, 2_500_000,
+    5_000_000,
+];
+
+/// Histogram of operation latencies with fixed buckets from 1 ms to 5 s.
+///
+/// Latencies greater than the last bound are counted in the overflow bucket.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [u64; BOUNDS.len() + 1],
+    sum: Duration,
+}
+
+impl LatencyHistogram {
+    /// Records latency of the operation.
+    pub fn record(&mut self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let index = BOUNDS.partition_point(|&bound| bound < micros);
+        self.counts[index] += 1;
+        self.sum += latency;
+    }
+
+    /// Returns count of recorded latencies.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns sum of recorded latencies.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// Returns upper bounds of the buckets with count of latencies in each bucket.
+    ///
+    /// Upper bound of the overflow bucket is `None`.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        let bounds = BOUNDS
+            .iter()
+            .map(|&bound| Some(Duration::from_micros(bound)));
+        bounds.chain([None]).zip(self.counts.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::LatencyHistogram;
+
+    #[test]
+    fn record() {
+        let mut histogram = LatencyHistogram::default();
+        histogram.record(Duration::from_micros(500));
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_millis(7));
+        histogram.record(Duration::from_secs(10));
+
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum(), Duration::from_micros(10_008_500));
+        let buckets: Vec<_> = histogram.buckets().collect();
+        assert_eq!(buckets[0], (Some(Duration::from_millis(1)), 2));
+        assert_eq!(buckets[2], (Some(Duration::from_millis(10)), 1));
+        assert_eq!(buckets[11], (None, 1));
+    }
+}
@@ -0,0 +1,9 @@
+//! Metrics of repository operations of the backend.
+
+pub use self::{
+    histogram::LatencyHistogram,
+    operation::{OperationMetrics, RepositoryMetrics},
+};
+
+mod histogram;
+mod operation;
@@ -0,0 +1,54 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use super::LatencyHistogram;
+
+/// Metrics of one operation of the repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// Count of operation calls.
+    pub calls: u64,
+    /// Count of operation calls which failed.
+    pub errors: u64,
+    /// Latencies of operation calls.
+    pub latency: LatencyHistogram,
+}
+
+/// Metrics of all operations of the repository by operation names.
+///
+/// Clones of the metrics share the same recorded values.
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryMetrics {
+    operations: Arc<Mutex<BTreeMap<&'static str, OperationMetrics>>>,
+}
+
+impl RepositoryMetrics {
+    /// Creates new empty repository metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records call of the operation by its name.
+    pub fn record(&self, operation: &'static str, latency: Duration, is_error: bool) {
+        let mut operations = self
+            .operations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let metrics = operations.entry(operation).or_default();
+        metrics.calls += 1;
+        metrics.errors += u64::from(is_error);
+        metrics.latency.record(latency);
+    }
+
+    /// Returns snapshot of metrics of all operations which were called at least once.
+    pub fn snapshot(&self) -> BTreeMap<&'static str, OperationMetrics> {
+        let operations = self
+            .operations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        operations.clone()
+    }
+}
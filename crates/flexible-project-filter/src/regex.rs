@@ -16,6 +16,19 @@ impl<R> From<R> for Regex<R> {
     }
 }
 
+impl<S> Regex<S>
+where
+    S: Borrow<str>,
+{
+    /// Checks if the pattern could be compiled.
+    ///
+    /// Filter with invalid pattern is never satisfied.
+    pub fn is_valid(&self) -> bool {
+        let Self(regex) = self;
+        FancyRegex::new(regex.borrow()).is_ok()
+    }
+}
+
 impl<S, Input> Filter<Input> for Regex<S>
 where
     S: Borrow<str>,
@@ -44,4 +57,11 @@ mod tests {
         assert!(filter.satisfies("Catcat1"));
         assert!(filter.satisfies("smol").not());
     }
+
+    #[test]
+    fn invalid() {
+        let filter = Regex("[");
+        assert!(filter.is_valid().not());
+        assert!(filter.satisfies("[").not());
+    }
 }
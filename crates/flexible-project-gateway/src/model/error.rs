@@ -0,0 +1,62 @@
+//! Errors of the gateway service which are exposed to the clients.
+
+use async_graphql::{Error, ErrorExtensions, Value};
+use serde::Deserialize;
+
+/// Error which is returned by the services when request of the client cannot be fulfilled.
+///
+/// Exposed to the clients with the `code` of the error in GraphQL error extensions.
+/// If the error was caused by user input, it is exposed as [validation error](ValidationError).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ServiceError {
+    /// Machine readable code of the error.
+    pub code: String,
+    /// Human readable description of the error.
+    pub message: String,
+    /// Field of the input and machine readable reason why it was rejected, if any.
+    #[serde(default)]
+    pub validation: Option<ValidationError>,
+}
+
+impl ErrorExtensions for ServiceError {
+    fn extend(&self) -> Error {
+        let Self {
+            code,
+            message,
+            validation,
+        } = self;
+        if let Some(validation) = validation {
+            return validation.extend();
+        }
+        Error::new(message).extend_with(|_, extensions| extensions.set("code", code.as_str()))
+    }
+}
+
+/// Error which is returned by the services when user input does not meet requirements.
+///
+/// Exposed to the clients with `VALIDATION_ERROR` code in GraphQL error extensions,
+/// along with the field of the input and machine readable reason of the error.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ValidationError {
+    /// Field of the input which does not meet requirements.
+    pub field: String,
+    /// Human readable description of the error.
+    pub message: String,
+    /// Machine readable reason of the error with its `code` and details.
+    pub reason: Value,
+}
+
+impl ErrorExtensions for ValidationError {
+    fn extend(&self) -> Error {
+        let Self {
+            field,
+            message,
+            reason,
+        } = self;
+        Error::new(message).extend_with(|_, extensions| {
+            extensions.set("code", "VALIDATION_ERROR");
+            extensions.set("field", field.as_str());
+            extensions.set("reason", reason.clone());
+        })
+    }
+}
@@ -13,6 +13,7 @@ use super::{project::Project, user::User, workspace::Workspace};
 #[derive(Debug, Default)]
 pub struct MethodologyQuery;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl MethodologyQuery {
     /// Filters all methodologies of the system.
@@ -26,6 +27,7 @@ impl MethodologyQuery {
 #[derive(Debug, Default)]
 pub struct MethodologyMutation;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl MethodologyMutation {
     /// Creates new methodology in the system.
@@ -13,6 +13,7 @@ use self::{
     workspace::{WorkspaceMutation, WorkspaceQuery},
 };
 
+pub mod error;
 pub mod methodology;
 pub mod notification;
 pub mod project;
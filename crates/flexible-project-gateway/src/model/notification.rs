@@ -12,6 +12,7 @@ use super::{project::Project, user::User, workspace::Workspace};
 #[derive(Debug, Default)]
 pub struct NotificationQuery;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl NotificationQuery {
     /// Retrieve a list of all notifications of the user received earlier.
@@ -25,6 +26,7 @@ impl NotificationQuery {
 #[derive(Debug, Default)]
 pub struct NotificationMutation;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl NotificationMutation {
     /// Mark notification from the input notification stream as received by the user.
@@ -48,6 +50,7 @@ impl NotificationMutation {
 #[derive(Debug, Default)]
 pub struct NotificationSubscription;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Subscription]
 impl NotificationSubscription {
     /// Subscribe for all incoming notifications of the user by provided identifier.
@@ -12,6 +12,7 @@ use super::{
 #[derive(Debug, Default)]
 pub struct ProjectQuery;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl ProjectQuery {
     /// Filters all projects of the system.
@@ -25,6 +26,7 @@ impl ProjectQuery {
 #[derive(Debug, Default)]
 pub struct ProjectMutation;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl ProjectMutation {
     /// Creates new project in the system.
@@ -68,6 +70,7 @@ pub struct Project {
     pub tasks: Vec<ProjectTask>,
 }
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[ComplexObject]
 impl Project {
     /// Methodology used in the project.
@@ -1,6 +1,7 @@
 //! User data model of the gateway service.
 
-use async_graphql::{Enum, InputObject, Object, SimpleObject, ID};
+use async_graphql::{Enum, Error, InputObject, Object, Result, SimpleObject, Upload, ID};
+use chrono::{DateTime, Utc};
 
 /// Query object of users of the Flexible Project system.
 #[derive(Debug, Default)]
@@ -9,10 +10,66 @@ pub struct UserQuery;
 #[Object]
 impl UserQuery {
     /// Filters all users of the system.
+    #[allow(clippy::unnecessary_literal_unwrap)]
     pub async fn users(&self, filters: UserFilters) -> Vec<User> {
         let _ = filters;
         None.unwrap()
     }
+
+    /// Finds users by their identifiers.
+    ///
+    /// Result has the same length and order as provided identifiers,
+    /// so member resolution of workspaces could be batched.
+    /// Element of the result is `null` if no user was found by the identifier.
+    pub async fn users_by_ids(&self, ids: Vec<ID>) -> Result<Vec<Option<User>>> {
+        let _ = ids;
+        Err(unavailable())
+    }
+
+    /// Counts users of the system which satisfy provided filters.
+    pub async fn users_count(&self, filters: UserFilters) -> Result<u64> {
+        let _ = filters;
+        Err(unavailable())
+    }
+
+    /// Counts users of the system which satisfy provided filters grouped by their role.
+    ///
+    /// Every role is present in the result, even if no user satisfying the filters has it.
+    pub async fn users_count_by_role(&self, filters: UserFilters) -> Result<Vec<RoleCount>> {
+        let _ = filters;
+        Err(unavailable())
+    }
+
+    /// Finds user by its current or recent former name.
+    pub async fn user_by_name(&self, name: String) -> Result<Option<UserByName>> {
+        let _ = name;
+        Err(unavailable())
+    }
+}
+
+/// Error of the resolvers which are not connected to the user service yet.
+fn unavailable() -> Error {
+    Error::new("user service is not available")
+}
+
+/// Count of users with the same role.
+#[derive(Debug, SimpleObject, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RoleCount {
+    /// Role of the users.
+    pub role: UserRole,
+    /// Count of users with the role.
+    pub count: u64,
+}
+
+/// User which was found by its current or recent former name.
+#[derive(Debug, SimpleObject, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UserByName {
+    /// Found user.
+    pub user: User,
+    /// Former name of the user which was used to find it, if any.
+    ///
+    /// Clients should redirect to the current name of the user if present.
+    pub redirected_from: Option<String>,
 }
 
 /// Mutation object of users of the Flexible Project system.
@@ -21,23 +78,97 @@ pub struct UserMutation;
 
 #[Object]
 impl UserMutation {
-    /// Creates new user with provided name in the system.
-    pub async fn create_user(&self, name: String) -> User {
-        let _ = name;
-        None.unwrap()
+    /// Creates new user with provided name and optional email in the system.
+    ///
+    /// Returns validation error if provided name or email does not meet requirements
+    /// or if domain of provided email is not allowed.
+    pub async fn create_user(&self, name: String, email: Option<String>) -> Result<User> {
+        let _ = (name, email);
+        Err(unavailable())
     }
 
     /// Updates properties of the user by provided identifier with provided data.
-    pub async fn update_user(&self, id: ID, update: UpdateUser) -> User {
+    ///
+    /// Returns validation error if provided data does not meet requirements.
+    pub async fn update_user(&self, id: ID, update: UpdateUser) -> Result<User> {
         let _ = (id, update);
-        None.unwrap()
+        Err(unavailable())
     }
 
     /// Deletes user from the system by provided identifier.
+    #[allow(clippy::unnecessary_literal_unwrap)]
     pub async fn delete_user(&self, id: ID) -> User {
         let _ = id;
         None.unwrap()
     }
+
+    /// Erases all data of the user by provided identifier from the system.
+    ///
+    /// Unlike deletion, also erases credentials and name history of the user
+    /// and removes the user from all workspaces it was a member of.
+    pub async fn erase_user(&self, id: ID) -> Result<User> {
+        let _ = id;
+        Err(unavailable())
+    }
+
+    /// Exports all personal data of the user by provided identifier as JSON archive.
+    ///
+    /// Archive contains data held by all services of the system, but never the password of the user.
+    pub async fn export_personal_data(&self, id: ID) -> Result<String> {
+        let _ = id;
+        Err(unavailable())
+    }
+
+    /// Uploads PNG or JPEG image as an avatar of the user by provided identifier.
+    ///
+    /// Image is cropped to the square and resized by the user service,
+    /// so avatar of the user is set to the URL of the processed image.
+    pub async fn upload_avatar(&self, id: ID, file: Upload) -> Result<User> {
+        let _ = (id, file);
+        Err(unavailable())
+    }
+
+    /// Changes role of the user by provided identifier.
+    ///
+    /// Only administrators are allowed to change roles of the users.
+    pub async fn change_role(&self, id: ID, role: UserRole) -> Result<User> {
+        let _ = (id, role);
+        Err(unavailable())
+    }
+
+    /// Suspends the user by provided identifier until provided time.
+    pub async fn suspend_user(&self, id: ID, until: DateTime<Utc>, reason: String) -> Result<User> {
+        let _ = (id, until, reason);
+        Err(unavailable())
+    }
+
+    /// Lifts suspension of the user by provided identifier.
+    pub async fn unsuspend_user(&self, id: ID) -> Result<User> {
+        let _ = id;
+        Err(unavailable())
+    }
+
+    /// Bans the user by provided identifier permanently.
+    pub async fn ban_user(&self, id: ID, reason: String) -> Result<User> {
+        let _ = (id, reason);
+        Err(unavailable())
+    }
+
+    /// Requests password reset of the user by provided email.
+    ///
+    /// Always succeeds to not reveal if the user with provided email exists.
+    pub async fn request_password_reset(&self, email: String) -> Result<bool> {
+        let _ = email;
+        Err(unavailable())
+    }
+
+    /// Sets new password of the user with the token from password reset request.
+    ///
+    /// Returns validation error if provided password does not meet requirements.
+    pub async fn reset_password(&self, token: String, password: String) -> Result<User> {
+        let _ = (token, password);
+        Err(unavailable())
+    }
 }
 
 /// User properties of the Flexible Project system.
@@ -55,6 +186,38 @@ pub struct User {
     pub email: Option<String>,
     /// Optional avatar of the user.
     pub avatar_url: Option<String>,
+    /// Status of the user account.
+    pub status: UserStatus,
+    /// Optional short Markdown biography of the user.
+    pub bio: Option<String>,
+    /// Optional preferred locale of the user as BCP 47 language tag.
+    pub locale: Option<String>,
+    /// Optional IANA time zone of the user.
+    pub time_zone: Option<String>,
+    /// Optional pronouns of the user.
+    pub pronouns: Option<String>,
+}
+
+/// Account status of the user of the Flexible Project system.
+#[derive(Debug, SimpleObject, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UserStatus {
+    /// Kind of the account status.
+    pub kind: UserStatusKind,
+    /// Time when suspension ends, if the user is suspended.
+    pub until: Option<DateTime<Utc>>,
+    /// Reason of the suspension or the ban, if present.
+    pub reason: Option<String>,
+}
+
+/// Kind of the account status of the user of the Flexible Project system.
+#[derive(Debug, Enum, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UserStatusKind {
+    /// Account is active and has no restrictions.
+    Active,
+    /// Account is suspended until some point in time.
+    Suspended,
+    /// Account is banned permanently.
+    Banned,
 }
 
 /// Filters of users of the Flexible Project system.
@@ -62,6 +225,8 @@ pub struct User {
 pub struct UserFilters {
     /// Identifier filter of the user.
     pub id: Option<ID>,
+    /// Account status filter of the user.
+    pub status: Option<UserStatusKind>,
 }
 
 /// Data of the user to update.
@@ -75,6 +240,14 @@ pub struct UpdateUser {
     pub email: Option<Option<String>>,
     /// Avatar of the user to update, if present.
     pub avatar_url: Option<Option<String>>,
+    /// Bio of the user to update, if present.
+    pub bio: Option<Option<String>>,
+    /// Preferred locale of the user to update, if present.
+    pub locale: Option<Option<String>>,
+    /// Time zone of the user to update, if present.
+    pub time_zone: Option<Option<String>>,
+    /// Pronouns of the user to update, if present.
+    pub pronouns: Option<Option<String>>,
 }
 
 /// Role of the user in the Flexible Project system.
@@ -8,6 +8,7 @@ use super::{project::Project, user::User};
 #[derive(Debug, Default)]
 pub struct WorkspaceQuery;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl WorkspaceQuery {
     /// Filters all workspaces of the system.
@@ -21,6 +22,7 @@ impl WorkspaceQuery {
 #[derive(Debug, Default)]
 pub struct WorkspaceMutation;
 
+#[allow(clippy::unnecessary_literal_unwrap)]
 #[Object]
 impl WorkspaceMutation {
     /// Creates new workspace with provided name in the system.
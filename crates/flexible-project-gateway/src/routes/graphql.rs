@@ -3,6 +3,7 @@
 use async_graphql::http::GraphiQLSource;
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
+    extract::DefaultBodyLimit,
     response::{Html, IntoResponse},
     routing::{get, post},
     Extension, Router,
@@ -10,15 +11,22 @@ use axum::{
 
 use crate::model::Schema;
 
+/// Maximal size of the body of GraphQL request, including uploaded files.
+const MAX_BODY_SIZE: usize = 8 * 1024 * 1024;
+
 /// The main GraphQL endpoint which handles all input requests
 /// with path of `/graphql`.
+///
+/// Multipart requests are supported, so files could be uploaded by the clients.
 pub fn graphql() -> Router {
     async fn handler(schema: Extension<Schema>, request: GraphQLRequest) -> GraphQLResponse {
         let request = request.into_inner();
         schema.execute(request).await.into()
     }
 
-    Router::new().route("/graphql", post(handler))
+    Router::new()
+        .route("/graphql", post(handler))
+        .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
 }
 
 /// Presents GraphiQL IDE to user with path of `/graphiql`.
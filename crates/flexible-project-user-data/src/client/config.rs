@@ -0,0 +1,361 @@
+//! Configuration of the local repository client.
+
+use std::{
+    env::{self, VarError},
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use derive_more::{Display, Error, From};
+use mongodb::options::{
+    Acknowledgment, ClientOptions, ReadConcernLevel, Tls, TlsOptions, WriteConcern,
+};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Configuration of the [local repository client](super::Client)
+/// which is applied on top of options of the connection string.
+///
+/// Options which are not set are left as specified by the connection string
+/// (or as the driver defaults if the connection string does not specify them either).
+#[derive(Debug, Clone, PartialEq, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into)))]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// Name of the application which is reported to the server.
+    #[builder(default = "flexible-project".to_owned())]
+    pub app_name: String,
+    /// Name of the database where all collections are stored.
+    #[builder(default = "flexible-project-user".to_owned())]
+    pub database: String,
+    /// Name of the collection of users.
+    #[builder(default = "user".to_owned())]
+    pub user_collection: String,
+    /// Name of the collection of user name changes.
+    #[builder(default = "name_history".to_owned())]
+    pub name_history_collection: String,
+    /// Name of the collection of user credentials.
+    #[builder(default = "credentials".to_owned())]
+    pub credentials_collection: String,
+    /// Name of the collection of password reset requests.
+    #[builder(default = "password_reset".to_owned())]
+    pub password_reset_collection: String,
+    /// Name of the collection of applied [migrations](crate::migration).
+    #[builder(default = "migration".to_owned())]
+    pub migration_collection: String,
+    /// Minimal count of connections kept open to each server.
+    #[builder(setter(strip_option))]
+    pub min_pool_size: Option<u32>,
+    /// Maximal count of connections open to each server.
+    #[builder(setter(strip_option))]
+    pub max_pool_size: Option<u32>,
+    /// Timeout of establishing connection to the server, in milliseconds.
+    #[builder(setter(strip_option))]
+    pub connect_timeout_ms: Option<u64>,
+    /// Timeout of selecting the server for an operation, in milliseconds.
+    #[builder(setter(strip_option))]
+    pub server_selection_timeout_ms: Option<u64>,
+    /// Read concern level of all operations.
+    #[builder(setter(strip_option))]
+    pub read_concern: Option<ReadConcernLevel>,
+    /// Write concern of all operations.
+    #[builder(setter(strip_option))]
+    pub write_concern: Option<WriteConcern>,
+    /// Path of the certificate authority file which is used to verify the server,
+    /// enables TLS if set.
+    #[builder(setter(strip_option))]
+    pub tls_ca_file: Option<PathBuf>,
+    /// Path of the client certificate and private key file, enables TLS if set.
+    #[builder(setter(strip_option))]
+    pub tls_cert_key_file: Option<PathBuf>,
+    /// Whether reads which failed because of transient failure are retried once.
+    #[builder(setter(strip_option))]
+    pub retry_reads: Option<bool>,
+    /// Whether writes which failed because of transient failure are retried once.
+    #[builder(setter(strip_option))]
+    pub retry_writes: Option<bool>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl DatabaseConfig {
+    /// Loads configuration from the environment variables on top of the default configuration.
+    ///
+    /// See [`with_env`](DatabaseConfig::with_env) for the list of variables.
+    pub fn from_env() -> Result<Self, DatabaseConfigError> {
+        Self::default().with_env()
+    }
+
+    /// Loads configuration from the JSON file by provided path.
+    ///
+    /// Fields which are absent in the file are set to their default values.
+    pub async fn from_file(path: impl AsRef<Path>) -> Result<Self, DatabaseConfigError> {
+        let content = tokio::fs::read(path).await?;
+        let config = serde_json::from_slice(&content)?;
+        Ok(config)
+    }
+
+    /// Overrides configuration with the environment variables which are set:
+    /// - `DATABASE_APP_NAME`, `DATABASE_NAME`;
+    /// - `DATABASE_USER_COLLECTION`, `DATABASE_NAME_HISTORY_COLLECTION`,
+    ///   `DATABASE_CREDENTIALS_COLLECTION`, `DATABASE_PASSWORD_RESET_COLLECTION`,
+    ///   `DATABASE_MIGRATION_COLLECTION`;
+    /// - `DATABASE_MIN_POOL_SIZE`, `DATABASE_MAX_POOL_SIZE`;
+    /// - `DATABASE_CONNECT_TIMEOUT_MS`, `DATABASE_SERVER_SELECTION_TIMEOUT_MS`;
+    /// - `DATABASE_READ_CONCERN`: read concern level, such as `majority`;
+    /// - `DATABASE_WRITE_CONCERN`: count of nodes, `majority` or custom write concern tag,
+    ///   `DATABASE_WRITE_CONCERN_JOURNAL`, `DATABASE_WRITE_CONCERN_TIMEOUT_MS`;
+    /// - `DATABASE_TLS_CA_FILE`, `DATABASE_TLS_CERT_KEY_FILE`;
+    /// - `DATABASE_RETRY_READS`, `DATABASE_RETRY_WRITES`.
+    pub fn with_env(self) -> Result<Self, DatabaseConfigError> {
+        self.with_vars(Vars(|key: &str| env::var(key)))
+    }
+
+    fn with_vars<F>(self, vars: Vars<F>) -> Result<Self, DatabaseConfigError>
+    where
+        F: Fn(&str) -> Result<String, VarError>,
+    {
+        let Self {
+            app_name,
+            database,
+            user_collection,
+            name_history_collection,
+            credentials_collection,
+            password_reset_collection,
+            migration_collection,
+            min_pool_size,
+            max_pool_size,
+            connect_timeout_ms,
+            server_selection_timeout_ms,
+            read_concern,
+            mut write_concern,
+            tls_ca_file,
+            tls_cert_key_file,
+            retry_reads,
+            retry_writes,
+        } = self;
+
+        let read_concern = match vars.string("DATABASE_READ_CONCERN")? {
+            Some(level) => {
+                let level: Result<_, serde::de::value::Error> =
+                    ReadConcernLevel::deserialize(level.into_deserializer());
+                Some(level.map_err(|_| DatabaseConfigError::var("DATABASE_READ_CONCERN"))?)
+            }
+            None => read_concern,
+        };
+
+        let w = vars
+            .string("DATABASE_WRITE_CONCERN")?
+            .map(|w| match w.parse::<u32>() {
+                Ok(nodes) => Acknowledgment::from(nodes),
+                Err(_) => Acknowledgment::from(w),
+            });
+        let journal = vars.parse("DATABASE_WRITE_CONCERN_JOURNAL")?;
+        let w_timeout = vars
+            .parse("DATABASE_WRITE_CONCERN_TIMEOUT_MS")?
+            .map(Duration::from_millis);
+        if w.is_some() || journal.is_some() || w_timeout.is_some() {
+            let mut concern = write_concern.take().unwrap_or_default();
+            concern.w = w.or(concern.w);
+            concern.journal = journal.or(concern.journal);
+            concern.w_timeout = w_timeout.or(concern.w_timeout);
+            write_concern = Some(concern);
+        }
+
+        let config = Self {
+            app_name: vars.string("DATABASE_APP_NAME")?.unwrap_or(app_name),
+            database: vars.string("DATABASE_NAME")?.unwrap_or(database),
+            user_collection: vars
+                .string("DATABASE_USER_COLLECTION")?
+                .unwrap_or(user_collection),
+            name_history_collection: vars
+                .string("DATABASE_NAME_HISTORY_COLLECTION")?
+                .unwrap_or(name_history_collection),
+            credentials_collection: vars
+                .string("DATABASE_CREDENTIALS_COLLECTION")?
+                .unwrap_or(credentials_collection),
+            password_reset_collection: vars
+                .string("DATABASE_PASSWORD_RESET_COLLECTION")?
+                .unwrap_or(password_reset_collection),
+            migration_collection: vars
+                .string("DATABASE_MIGRATION_COLLECTION")?
+                .unwrap_or(migration_collection),
+            min_pool_size: vars.parse("DATABASE_MIN_POOL_SIZE")?.or(min_pool_size),
+            max_pool_size: vars.parse("DATABASE_MAX_POOL_SIZE")?.or(max_pool_size),
+            connect_timeout_ms: vars
+                .parse("DATABASE_CONNECT_TIMEOUT_MS")?
+                .or(connect_timeout_ms),
+            server_selection_timeout_ms: vars
+                .parse("DATABASE_SERVER_SELECTION_TIMEOUT_MS")?
+                .or(server_selection_timeout_ms),
+            read_concern,
+            write_concern,
+            tls_ca_file: vars.parse("DATABASE_TLS_CA_FILE")?.or(tls_ca_file),
+            tls_cert_key_file: vars
+                .parse("DATABASE_TLS_CERT_KEY_FILE")?
+                .or(tls_cert_key_file),
+            retry_reads: vars.parse("DATABASE_RETRY_READS")?.or(retry_reads),
+            retry_writes: vars.parse("DATABASE_RETRY_WRITES")?.or(retry_writes),
+        };
+        Ok(config)
+    }
+
+    /// Applies configuration on top of options of the connection string.
+    pub(crate) fn apply(&self, options: &mut ClientOptions) {
+        let Self {
+            app_name,
+            min_pool_size,
+            max_pool_size,
+            connect_timeout_ms,
+            server_selection_timeout_ms,
+            read_concern,
+            write_concern,
+            tls_ca_file,
+            tls_cert_key_file,
+            retry_reads,
+            retry_writes,
+            ..
+        } = self;
+
+        options.app_name = Some(app_name.clone());
+        options.min_pool_size = min_pool_size.or(options.min_pool_size);
+        options.max_pool_size = max_pool_size.or(options.max_pool_size);
+        options.connect_timeout = connect_timeout_ms
+            .map(Duration::from_millis)
+            .or(options.connect_timeout);
+        options.server_selection_timeout = server_selection_timeout_ms
+            .map(Duration::from_millis)
+            .or(options.server_selection_timeout);
+        if let Some(level) = read_concern {
+            options.read_concern = Some(level.clone().into());
+        }
+        if let Some(concern) = write_concern {
+            options.write_concern = Some(concern.clone());
+        }
+        if tls_ca_file.is_some() || tls_cert_key_file.is_some() {
+            let mut tls = match options.tls.take() {
+                Some(Tls::Enabled(tls)) => tls,
+                _ => TlsOptions::default(),
+            };
+            tls.ca_file_path = tls_ca_file.clone().or(tls.ca_file_path);
+            tls.cert_key_file_path = tls_cert_key_file.clone().or(tls.cert_key_file_path);
+            options.tls = Some(Tls::Enabled(tls));
+        }
+        options.retry_reads = retry_reads.or(options.retry_reads);
+        options.retry_writes = retry_writes.or(options.retry_writes);
+    }
+}
+
+struct Vars<F>(F);
+
+impl<F> Vars<F>
+where
+    F: Fn(&str) -> Result<String, VarError>,
+{
+    fn string(&self, key: &'static str) -> Result<Option<String>, DatabaseConfigError> {
+        match (self.0)(key) {
+            Ok(value) => Ok(Some(value)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(DatabaseConfigError::var(key)),
+        }
+    }
+
+    fn parse<T>(&self, key: &'static str) -> Result<Option<T>, DatabaseConfigError>
+    where
+        T: FromStr,
+    {
+        let value = self.string(key)?;
+        let value = value.map(|value| value.parse());
+        value.transpose().map_err(|_| DatabaseConfigError::var(key))
+    }
+}
+
+/// Error which is returned when configuration of the local repository client cannot be loaded.
+#[derive(Debug, Display, From, Error)]
+#[from(forward)]
+pub struct DatabaseConfigError {
+    kind: DatabaseConfigErrorKind,
+}
+
+impl DatabaseConfigError {
+    fn var(key: &'static str) -> Self {
+        let kind = DatabaseConfigErrorKind::Var(key);
+        Self { kind }
+    }
+}
+
+#[derive(Debug, Display, From, Error)]
+enum DatabaseConfigErrorKind {
+    Io(io::Error),
+    Json(serde_json::Error),
+    #[display(fmt = r#"environment variable "{}" is invalid"#, _0)]
+    #[from(ignore)]
+    Var(#[error(not(source))] &'static str),
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, env::VarError, time::Duration};
+
+    use mongodb::options::{Acknowledgment, ClientOptions, ReadConcernLevel, ServerAddress};
+
+    use super::{DatabaseConfig, Vars};
+
+    fn with_vars(config: DatabaseConfig, vars: &[(&str, &str)]) -> Option<DatabaseConfig> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        let vars = Vars(|key: &str| {
+            vars.get(key)
+                .map(|&value| value.to_owned())
+                .ok_or(VarError::NotPresent)
+        });
+        config.with_vars(vars).ok()
+    }
+
+    #[test]
+    fn override_with_env() {
+        let config = DatabaseConfig::builder()
+            .database("staging")
+            .max_pool_size(8u32)
+            .build();
+        let config = with_vars(
+            config,
+            &[
+                ("DATABASE_USER_COLLECTION", "users"),
+                ("DATABASE_MIN_POOL_SIZE", "2"),
+                ("DATABASE_READ_CONCERN", "majority"),
+                ("DATABASE_WRITE_CONCERN", "2"),
+                ("DATABASE_WRITE_CONCERN_JOURNAL", "true"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.database, "staging");
+        assert_eq!(config.user_collection, "users");
+        assert_eq!(config.name_history_collection, "name_history");
+        assert_eq!(config.min_pool_size, Some(2));
+        assert_eq!(config.max_pool_size, Some(8));
+        assert_eq!(config.read_concern, Some(ReadConcernLevel::Majority));
+        let write_concern = config.write_concern.clone().unwrap();
+        assert_eq!(write_concern.w, Some(Acknowledgment::Nodes(2)));
+        assert_eq!(write_concern.journal, Some(true));
+
+        let invalid = [("DATABASE_MAX_POOL_SIZE", "many")];
+        assert!(with_vars(config.clone(), &invalid).is_none());
+
+        let mut options = ClientOptions::builder()
+            .hosts([ServerAddress::parse("localhost").unwrap()])
+            .max_pool_size(16)
+            .connect_timeout(Duration::from_secs(1))
+            .build();
+        config.apply(&mut options);
+        assert_eq!(options.app_name.as_deref(), Some("flexible-project"));
+        assert_eq!(options.max_pool_size, Some(8));
+        assert_eq!(options.connect_timeout, Some(Duration::from_secs(1)));
+        assert_eq!(options.tls, None);
+    }
+}
@@ -3,22 +3,40 @@
 use derive_more::{Display, Error, From};
 use mongodb::error::Error;
 use mongodb::options::ClientOptions;
-use mongodb::Client as DatabaseClient;
+use mongodb::{Client as DatabaseClient, Database};
+
+pub use self::config::{DatabaseConfig, DatabaseConfigError};
+
+mod config;
 
 /// Local repository client.
 #[derive(Debug, Clone)]
 pub struct Client {
     pub(crate) inner: DatabaseClient,
+    pub(crate) config: DatabaseConfig,
 }
 
 impl Client {
-    /// Creates new client instance.
-    pub async fn new(conn_str: impl AsRef<str>) -> Result<Self, ClientError> {
+    /// Creates new client instance, applying provided configuration
+    /// on top of options of the connection string.
+    pub async fn new(
+        conn_str: impl AsRef<str>,
+        config: DatabaseConfig,
+    ) -> Result<Self, ClientError> {
         let mut client_options = ClientOptions::parse(conn_str).await?;
-        client_options.app_name = Some("flexible-project".into());
+        config.apply(&mut client_options);
 
         let inner = DatabaseClient::with_options(client_options)?;
-        Ok(Self { inner })
+        Ok(Self { inner, config })
+    }
+
+    /// Returns configuration of the client.
+    pub fn config(&self) -> &DatabaseConfig {
+        &self.config
+    }
+
+    pub(crate) fn database(&self) -> Database {
+        self.inner.database(&self.config.database)
     }
 }
 
@@ -0,0 +1,218 @@
+//! Versioned migrations of the local database schema.
+//!
+//! Migrations are applied in order of their declaration. Each applied migration
+//! is recorded in the `migration` collection, so it is never applied again.
+//!
+//! Every migration is idempotent, so it is safe to apply migrations again after a failure
+//! or from several instances of the service started at the same time.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use derive_more::{Display, Error, From};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{de, doc, ser},
+    error::{Error, ErrorKind},
+    options::ReplaceOptions,
+    Collection,
+};
+
+use crate::{client::Client, model::LocalMigration};
+
+use self::{
+    name_history::BackfillNameHistoryCanonical,
+    password_reset::DropPasswordResetExpirationIndex,
+    user::{
+        BackfillAccountStatus, BackfillUserCanonical, CreateUserIndexes, DropLegacyUserIndexes,
+    },
+};
+
+pub(crate) use self::user::{EMAIL_INDEX, NAME_INDEX};
+
+mod name_history;
+mod password_reset;
+mod user;
+
+/// All migrations of the local database in order of their application.
+///
+/// Migrations must never be removed or reordered, new migrations must be added to the end.
+const MIGRATIONS: [&dyn Migration; 6] = [
+    &BackfillUserCanonical,
+    &CreateUserIndexes,
+    &DropLegacyUserIndexes,
+    &BackfillAccountStatus,
+    &BackfillNameHistoryCanonical,
+    &DropPasswordResetExpirationIndex,
+];
+
+const NAMESPACE_NOT_FOUND_CODE: i32 = 26;
+
+/// Single step of the local database schema evolution.
+#[async_trait(?Send)]
+trait Migration {
+    /// Unique name of the migration which is recorded when the migration is applied.
+    fn name(&self) -> &'static str;
+
+    /// Counts documents and indexes which would be changed by the migration.
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError>;
+
+    /// Reports conflicts of stored documents which prevent the migration from being applied,
+    /// such as users which differ only in case of their names.
+    async fn conflicts(&self, _client: &Client) -> Result<Vec<String>, MigrationError> {
+        Ok(Vec::new())
+    }
+
+    /// Applies the migration, returning count of changed documents and indexes.
+    ///
+    /// Must have no effect if the migration was already applied.
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError>;
+}
+
+/// Report of the applied (or pending on dry run) migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Name of the migration.
+    pub name: &'static str,
+    /// Count of documents and indexes which were (or would be on dry run) changed.
+    pub changes: u64,
+    /// Conflicts of stored documents which must be resolved before the migration is applied.
+    ///
+    /// Always empty for applied migrations.
+    pub conflicts: Vec<String>,
+}
+
+/// Applies pending migrations of the local database.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    client: Client,
+    state: Collection<LocalMigration>,
+}
+
+impl Migrator {
+    /// Creates new migrator of the local database.
+    pub fn new(client: Client) -> Self {
+        let database = client.database();
+        let state = database.collection(&client.config.migration_collection);
+        Self { client, state }
+    }
+
+    /// Returns names of migrations which were not applied yet.
+    pub async fn pending(&self) -> Result<Vec<&'static str>, MigrationError> {
+        let applied = self.applied().await?;
+        let pending = MIGRATIONS
+            .iter()
+            .map(|migration| migration.name())
+            .filter(|name| !applied.contains(*name))
+            .collect();
+        Ok(pending)
+    }
+
+    /// Reports changes which pending migrations would make, without applying them.
+    ///
+    /// Changes of migrations which depend on previous pending migrations
+    /// are counted against the current state of the database.
+    pub async fn dry_run(&self) -> Result<Vec<MigrationReport>, MigrationError> {
+        let Self { client, .. } = self;
+        let applied = self.applied().await?;
+        let mut reports = Vec::new();
+        for migration in MIGRATIONS {
+            let name = migration.name();
+            if applied.contains(name) {
+                continue;
+            }
+            let changes = migration.pending(client).await?;
+            let conflicts = migration.conflicts(client).await?;
+            let report = MigrationReport {
+                name,
+                changes,
+                conflicts,
+            };
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Applies pending migrations in order, recording each of them as applied.
+    ///
+    /// Returns reports of the applied migrations, or an error if any pending migration
+    /// has conflicts, leaving it and all following migrations pending.
+    pub async fn migrate(&self) -> Result<Vec<MigrationReport>, MigrationError> {
+        let Self { client, state } = self;
+        let applied = self.applied().await?;
+        let mut reports = Vec::new();
+        for migration in MIGRATIONS {
+            let name = migration.name();
+            if applied.contains(name) {
+                continue;
+            }
+            let conflicts = migration.conflicts(client).await?;
+            if !conflicts.is_empty() {
+                let kind = MigrationErrorKind::Conflict { name, conflicts };
+                return Err(kind.into());
+            }
+            let changes = migration.up(client).await?;
+            let record = LocalMigration {
+                name: name.to_owned(),
+                applied_at: Utc::now(),
+                changes,
+            };
+            let options = ReplaceOptions::builder().upsert(true).build();
+            state
+                .replace_one(doc! { "_id": name }, record, options)
+                .await?;
+            let report = MigrationReport {
+                name,
+                changes,
+                conflicts: Vec::new(),
+            };
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    async fn applied(&self) -> Result<HashSet<String>, MigrationError> {
+        let Self { state, .. } = self;
+        let applied: Vec<_> = state.find(None, None).await?.try_collect().await?;
+        let applied = applied.into_iter().map(|migration| migration.name);
+        Ok(applied.collect())
+    }
+}
+
+/// Returns names of indexes of the collection, or nothing if the collection does not exist.
+async fn index_names<T>(collection: &Collection<T>) -> Result<Vec<String>, MigrationError> {
+    match collection.list_index_names().await {
+        Ok(names) => Ok(names),
+        Err(error) => match error.kind.as_ref() {
+            ErrorKind::Command(command) if command.code == NAMESPACE_NOT_FOUND_CODE => {
+                Ok(Vec::new())
+            }
+            _ => Err(error.into()),
+        },
+    }
+}
+
+/// Type of error which is returned when migration of the local database fails.
+#[derive(Debug, Display, Clone, From, Error)]
+#[from(forward)]
+pub struct MigrationError {
+    kind: MigrationErrorKind,
+}
+
+#[derive(Debug, Display, Clone, From, Error)]
+enum MigrationErrorKind {
+    #[display(
+        fmt = r#"migration "{}" has conflicts: {}"#,
+        name,
+        r#"conflicts.join("; ")"#
+    )]
+    #[from(ignore)]
+    Conflict {
+        name: &'static str,
+        conflicts: Vec<String>,
+    },
+    ToBson(ser::Error),
+    FromBson(de::Error),
+    Database(Error),
+}
@@ -0,0 +1,63 @@
+//! Migrations of the name history collection.
+
+use async_trait::async_trait;
+use fp_user_domain::model::Name;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, Document},
+    results::UpdateResult,
+    Collection,
+};
+
+use crate::client::Client;
+
+use super::{Migration, MigrationError};
+
+fn collection(client: &Client) -> Collection<Document> {
+    client
+        .database()
+        .collection(&client.config.name_history_collection)
+}
+
+/// Fills canonical names of name changes recorded before they were stored,
+/// so that such changes could be found by name.
+///
+/// Canonical forms are computed from raw stored strings without validation.
+pub(super) struct BackfillNameHistoryCanonical;
+
+impl BackfillNameHistoryCanonical {
+    fn filter() -> Document {
+        doc! { "name_canonical": { "$exists": false } }
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for BackfillNameHistoryCanonical {
+    fn name(&self) -> &'static str {
+        "0005_backfill_name_history_canonical"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        let count = collection(client)
+            .count_documents(Self::filter(), None)
+            .await?;
+        Ok(count)
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection(client);
+        let mut changes = collection.find(Self::filter(), None).await?;
+        let mut count = 0;
+        while let Some(change) = changes.try_next().await? {
+            let Ok(name) = change.get_str("name") else {
+                continue;
+            };
+            let filter = doc! { "_id": change.get("_id").cloned() };
+            let update = doc! { "$set": { "name_canonical": Name::canonical_form(name) } };
+            let UpdateResult { modified_count, .. } =
+                collection.update_one(filter, update, None).await?;
+            count += modified_count;
+        }
+        Ok(count)
+    }
+}
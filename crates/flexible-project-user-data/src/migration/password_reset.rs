@@ -0,0 +1,58 @@
+//! Migrations of the password reset collection.
+
+use async_trait::async_trait;
+use mongodb::{bson::Document, error::ErrorKind, Collection};
+
+use crate::client::Client;
+
+use super::{index_names, Migration, MigrationError};
+
+/// Name of the index which expired password resets at their expiration time.
+const EXPIRATION_INDEX: &str = "expires_at_1";
+const INDEX_NOT_FOUND_CODE: i32 = 27;
+
+fn collection(client: &Client) -> Collection<Document> {
+    client
+        .database()
+        .collection(&client.config.password_reset_collection)
+}
+
+/// Drops the index which deleted password resets as soon as they expired,
+/// so requests of the user were not counted during the whole rate limit window.
+///
+/// Password resets are deleted by the index of their creation time instead,
+/// which is created by the [password reset database](crate::repository::LocalPasswordResetDatabase).
+pub(super) struct DropPasswordResetExpirationIndex;
+
+impl DropPasswordResetExpirationIndex {
+    async fn has_index(client: &Client) -> Result<bool, MigrationError> {
+        let names = index_names(&collection(client)).await?;
+        Ok(names.iter().any(|name| name == EXPIRATION_INDEX))
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for DropPasswordResetExpirationIndex {
+    fn name(&self) -> &'static str {
+        "0006_drop_password_reset_expiration_index"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        let has_index = Self::has_index(client).await?;
+        Ok(has_index.into())
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        if !Self::has_index(client).await? {
+            return Ok(0);
+        }
+        match collection(client).drop_index(EXPIRATION_INDEX, None).await {
+            Ok(()) => Ok(1),
+            Err(error) => match error.kind.as_ref() {
+                // index was dropped concurrently
+                ErrorKind::Command(command) if command.code == INDEX_NOT_FOUND_CODE => Ok(0),
+                _ => Err(error.into()),
+            },
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Migrations of the user collection.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use fp_user_domain::model::{Email, Name};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, to_bson, Document},
+    error::ErrorKind,
+    options::{FindOptions, IndexOptions},
+    results::UpdateResult,
+    Collection, IndexModel,
+};
+
+use crate::{client::Client, model::LocalAccountStatus};
+
+use super::{index_names, Migration, MigrationError};
+
+/// Name of the unique index of canonical user names.
+pub(crate) const NAME_INDEX: &str = "unique_name_canonical";
+/// Name of the unique index of canonical user emails.
+pub(crate) const EMAIL_INDEX: &str = "unique_email_canonical";
+/// Unique indexes of user names and emails which were replaced by canonical ones.
+const LEGACY_INDEXES: [&str; 4] = ["name_1", "email_1", "unique_name", "unique_email"];
+const INDEX_NOT_FOUND_CODE: i32 = 27;
+
+fn collection<T>(client: &Client) -> Collection<T> {
+    client.database().collection(&client.config.user_collection)
+}
+
+/// Drops unique indexes of raw user names and emails,
+/// which would reject users differing only in case of their names or emails.
+///
+/// Applied only after unique indexes of canonical names and emails are created,
+/// so the collection is never left without uniqueness of user names and emails.
+pub(super) struct DropLegacyUserIndexes;
+
+impl DropLegacyUserIndexes {
+    async fn legacy_indexes(client: &Client) -> Result<Vec<String>, MigrationError> {
+        let names = index_names(&collection::<Document>(client)).await?;
+        let names = names
+            .into_iter()
+            .filter(|name| LEGACY_INDEXES.contains(&name.as_str()));
+        Ok(names.collect())
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for DropLegacyUserIndexes {
+    fn name(&self) -> &'static str {
+        "0003_drop_legacy_user_indexes"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        let names = Self::legacy_indexes(client).await?;
+        Ok(names.len() as u64)
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection::<Document>(client);
+        let mut changes = 0;
+        for name in Self::legacy_indexes(client).await? {
+            match collection.drop_index(name, None).await {
+                Ok(()) => changes += 1,
+                Err(error) => match error.kind.as_ref() {
+                    // index was dropped concurrently
+                    ErrorKind::Command(command) if command.code == INDEX_NOT_FOUND_CODE => {}
+                    _ => return Err(error.into()),
+                },
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// Fills canonical names and emails of users created before they were stored.
+///
+/// Canonical forms are computed from raw stored strings without validation,
+/// so users which do not meet current requirements are backfilled too.
+pub(super) struct BackfillUserCanonical;
+
+impl BackfillUserCanonical {
+    fn filter() -> Document {
+        doc! {
+            "$or": [
+                { "data.name_canonical": { "$exists": false } },
+                {
+                    "data.email": { "$type": "string" },
+                    "data.email_canonical": { "$exists": false },
+                },
+            ]
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for BackfillUserCanonical {
+    fn name(&self) -> &'static str {
+        "0001_backfill_user_canonical"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection::<Document>(client);
+        let count = collection.count_documents(Self::filter(), None).await?;
+        Ok(count)
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection::<Document>(client);
+        let mut users = collection.find(Self::filter(), None).await?;
+        let mut changes = 0;
+        while let Some(user) = users.try_next().await? {
+            let Ok(data) = user.get_document("data") else {
+                continue;
+            };
+            let name_canonical = data.get_str("name").ok().map(Name::canonical_form);
+            let email_canonical = data.get_str("email").ok().map(Email::canonical_form);
+
+            let filter = doc! { "_id": user.get("_id").cloned() };
+            let update = doc! {
+                "$set": {
+                    "data.name_canonical": name_canonical,
+                    "data.email_canonical": email_canonical,
+                }
+            };
+            let UpdateResult { modified_count, .. } =
+                collection.update_one(filter, update, None).await?;
+            changes += modified_count;
+        }
+        Ok(changes)
+    }
+}
+
+/// Marks users created before account status was introduced as active.
+pub(super) struct BackfillAccountStatus;
+
+impl BackfillAccountStatus {
+    fn filter() -> Document {
+        doc! { "data.status": { "$exists": false } }
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for BackfillAccountStatus {
+    fn name(&self) -> &'static str {
+        "0004_backfill_account_status"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection::<Document>(client);
+        let count = collection.count_documents(Self::filter(), None).await?;
+        Ok(count)
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        let collection = collection::<Document>(client);
+        let update = doc! { "$set": { "data.status": to_bson(&LocalAccountStatus::Active)? } };
+        let UpdateResult { modified_count, .. } =
+            collection.update_many(Self::filter(), update, None).await?;
+        Ok(modified_count)
+    }
+}
+
+/// Creates unique indexes of canonical user names and emails.
+///
+/// Users which differ only in case of their names or emails are reported as conflicts,
+/// so they could be resolved before the indexes are created.
+pub(super) struct CreateUserIndexes;
+
+impl CreateUserIndexes {
+    fn indexes() -> [IndexModel; 2] {
+        let name_index = {
+            let options = IndexOptions::builder()
+                .name(NAME_INDEX.to_owned())
+                .unique(true)
+                .build();
+            IndexModel::builder()
+                .keys(doc! { "data.name_canonical": 1 })
+                .options(options)
+                .build()
+        };
+        let email_index = {
+            let options = IndexOptions::builder()
+                .name(EMAIL_INDEX.to_owned())
+                .unique(true)
+                .partial_filter_expression(
+                    doc! { "data.email_canonical": { "$exists": true, "$type": "string" } },
+                )
+                .build();
+            IndexModel::builder()
+                .keys(doc! { "data.email_canonical": 1 })
+                .options(options)
+                .build()
+        };
+        [name_index, email_index]
+    }
+
+    async fn missing_indexes(client: &Client) -> Result<u64, MigrationError> {
+        let names = index_names(&collection::<Document>(client)).await?;
+        let missing = [NAME_INDEX, EMAIL_INDEX]
+            .into_iter()
+            .filter(|index| !names.iter().any(|name| name == index));
+        Ok(missing.count() as u64)
+    }
+
+    /// Finds users which share canonical forms of their names or emails,
+    /// computing canonical forms from raw stored strings.
+    async fn duplicates(client: &Client) -> Result<Vec<String>, MigrationError> {
+        let collection = collection::<Document>(client);
+        let options = FindOptions::builder()
+            .projection(doc! { "data.name": 1, "data.email": 1 })
+            .build();
+        let mut users = collection.find(None, options).await?;
+        let mut names = BTreeMap::<_, Vec<_>>::new();
+        let mut emails = BTreeMap::<_, Vec<_>>::new();
+        while let Some(user) = users.try_next().await? {
+            let id = user.get("_id").map(ToString::to_string).unwrap_or_default();
+            let Ok(data) = user.get_document("data") else {
+                continue;
+            };
+            if let Ok(name) = data.get_str("name") {
+                let ids = names.entry(Name::canonical_form(name)).or_default();
+                ids.push(id.clone());
+            }
+            if let Ok(email) = data.get_str("email") {
+                let ids = emails.entry(Email::canonical_form(email)).or_default();
+                ids.push(id);
+            }
+        }
+
+        let duplicates =
+            [("name", names), ("email", emails)]
+                .into_iter()
+                .flat_map(|(field, values)| {
+                    let values = values.into_iter().filter(|(_, ids)| ids.len() > 1);
+                    values.map(move |(value, ids)| {
+                        let ids = ids.join(", ");
+                        format!(r#"users {ids} share canonical {field} "{value}""#)
+                    })
+                });
+        Ok(duplicates.collect())
+    }
+}
+
+#[async_trait(?Send)]
+impl Migration for CreateUserIndexes {
+    fn name(&self) -> &'static str {
+        "0002_create_user_indexes"
+    }
+
+    async fn pending(&self, client: &Client) -> Result<u64, MigrationError> {
+        Self::missing_indexes(client).await
+    }
+
+    async fn conflicts(&self, client: &Client) -> Result<Vec<String>, MigrationError> {
+        if Self::missing_indexes(client).await? == 0 {
+            return Ok(Vec::new());
+        }
+        Self::duplicates(client).await
+    }
+
+    async fn up(&self, client: &Client) -> Result<u64, MigrationError> {
+        let changes = Self::missing_indexes(client).await?;
+        if changes > 0 {
+            let collection = collection::<Document>(client);
+            collection.create_indexes(Self::indexes(), None).await?;
+        }
+        Ok(changes)
+    }
+}
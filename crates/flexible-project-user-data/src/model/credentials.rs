@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::model::{PasswordHash, UserCredentials};
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
+use serde::{Deserialize, Serialize};
+
+use super::id::LocalUserId;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalCredentials {
+    #[serde(rename = "_id")]
+    pub user_id: LocalUserId,
+    pub password_hash: String,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LocalCredentials {
+    pub fn new(user_id: LocalUserId, credentials: UserCredentials) -> Self {
+        let UserCredentials {
+            password_hash,
+            updated_at,
+        } = credentials;
+        Self {
+            user_id,
+            password_hash: password_hash.into_inner(),
+            updated_at,
+        }
+    }
+}
+
+impl From<LocalCredentials> for UserCredentials {
+    fn from(value: LocalCredentials) -> Self {
+        let LocalCredentials {
+            password_hash,
+            updated_at,
+            ..
+        } = value;
+        Self {
+            password_hash: PasswordHash::new(password_hash),
+            updated_at,
+        }
+    }
+}
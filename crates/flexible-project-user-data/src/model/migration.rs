@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalMigration {
+    #[serde(rename = "_id")]
+    pub name: String,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub applied_at: DateTime<Utc>,
+    pub changes: u64,
+}
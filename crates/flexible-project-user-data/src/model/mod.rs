@@ -1,9 +1,21 @@
 pub use self::{
+    credentials::LocalCredentials,
     id::{LocalUserId, LocalUserIdError},
-    role::LocalRole,
+    migration::LocalMigration,
+    name_history::LocalNameChange,
+    password_reset::LocalPasswordReset,
+    record::LocalUserRecord,
+    role::{LocalRole, LocalRoleCount},
+    status::{LocalAccountStatus, LocalAccountStatusKind},
     user::{LocalUser, LocalUserData, LocalUserDataError},
 };
 
+mod credentials;
 mod id;
+mod migration;
+mod name_history;
+mod password_reset;
+mod record;
 mod role;
+mod status;
 mod user;
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::model::{Name, NameChange};
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    id::{LocalUserId, LocalUserIdError},
+    user::LocalUserDataError,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalNameChange {
+    pub user_id: LocalUserId,
+    pub name: String,
+    #[serde(default)]
+    pub name_canonical: String,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TryFrom<NameChange> for LocalNameChange {
+    type Error = LocalUserIdError;
+
+    fn try_from(value: NameChange) -> Result<Self, Self::Error> {
+        let NameChange {
+            user_id,
+            name,
+            changed_at,
+        } = value;
+        let change = Self {
+            user_id: user_id.try_into()?,
+            name_canonical: name.canonical(),
+            name: name.into_inner(),
+            changed_at,
+        };
+        Ok(change)
+    }
+}
+
+impl TryFrom<LocalNameChange> for NameChange {
+    type Error = LocalUserDataError;
+
+    fn try_from(value: LocalNameChange) -> Result<Self, Self::Error> {
+        let LocalNameChange {
+            user_id,
+            name,
+            name_canonical: _,
+            changed_at,
+        } = value;
+        let change = Self {
+            user_id: user_id.into(),
+            name: Name::new(name)?,
+            changed_at,
+        };
+        Ok(change)
+    }
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::model::{PasswordReset, PasswordResetTokenHash};
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
+use serde::{Deserialize, Serialize};
+
+use super::id::{LocalUserId, LocalUserIdError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalPasswordReset {
+    #[serde(rename = "_id")]
+    pub token_hash: String,
+    pub user_id: LocalUserId,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub taken: bool,
+}
+
+impl TryFrom<PasswordReset> for LocalPasswordReset {
+    type Error = LocalUserIdError;
+
+    fn try_from(value: PasswordReset) -> Result<Self, Self::Error> {
+        let PasswordReset {
+            token_hash,
+            user_id,
+            created_at,
+            expires_at,
+        } = value;
+        let reset = Self {
+            token_hash: token_hash.into_inner(),
+            user_id: user_id.try_into()?,
+            created_at,
+            expires_at,
+            taken: false,
+        };
+        Ok(reset)
+    }
+}
+
+impl From<LocalPasswordReset> for PasswordReset {
+    fn from(value: LocalPasswordReset) -> Self {
+        let LocalPasswordReset {
+            token_hash,
+            user_id,
+            created_at,
+            expires_at,
+            ..
+        } = value;
+        Self {
+            token_hash: PasswordResetTokenHash::new(token_hash),
+            user_id: user_id.into(),
+            created_at,
+            expires_at,
+        }
+    }
+}
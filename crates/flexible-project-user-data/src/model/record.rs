@@ -0,0 +1,71 @@
+use fp_user_domain::model::UserRecord;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalUserRecord {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub pronouns: Option<String>,
+}
+
+impl From<UserRecord> for LocalUserRecord {
+    fn from(value: UserRecord) -> Self {
+        let UserRecord {
+            name,
+            display_name,
+            email,
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = value;
+        Self {
+            name,
+            display_name,
+            email,
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        }
+    }
+}
+
+impl From<LocalUserRecord> for UserRecord {
+    fn from(value: LocalUserRecord) -> Self {
+        let LocalUserRecord {
+            name,
+            display_name,
+            email,
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = value;
+        Self {
+            name,
+            display_name,
+            email,
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        }
+    }
+}
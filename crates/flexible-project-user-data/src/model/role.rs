@@ -42,3 +42,10 @@ impl From<LocalRole> for Role {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalRoleCount {
+    #[serde(rename = "_id")]
+    pub role: LocalRole,
+    pub count: u64,
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::model::{AccountStatus, AccountStatusKind};
+use mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum LocalAccountStatus {
+    #[default]
+    Active,
+    Suspended {
+        #[serde(with = "chrono_datetime_as_bson_datetime")]
+        until: DateTime<Utc>,
+        reason: String,
+    },
+    Banned {
+        reason: String,
+    },
+}
+
+impl From<AccountStatus> for LocalAccountStatus {
+    fn from(value: AccountStatus) -> Self {
+        match value {
+            AccountStatus::Active => Self::Active,
+            AccountStatus::Suspended { until, reason } => Self::Suspended { until, reason },
+            AccountStatus::Banned { reason } => Self::Banned { reason },
+        }
+    }
+}
+
+impl From<LocalAccountStatus> for AccountStatus {
+    fn from(value: LocalAccountStatus) -> Self {
+        match value {
+            LocalAccountStatus::Active => Self::Active,
+            LocalAccountStatus::Suspended { until, reason } => Self::Suspended { until, reason },
+            LocalAccountStatus::Banned { reason } => Self::Banned { reason },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LocalAccountStatusKind {
+    Active,
+    Suspended,
+    Banned,
+}
+
+impl From<AccountStatusKind> for LocalAccountStatusKind {
+    fn from(value: AccountStatusKind) -> Self {
+        match value {
+            AccountStatusKind::Active => Self::Active,
+            AccountStatusKind::Suspended => Self::Suspended,
+            AccountStatusKind::Banned => Self::Banned,
+        }
+    }
+}
@@ -2,14 +2,15 @@ use std::hash::{Hash, Hasher};
 
 use derive_more::{Display, Error, From};
 use fp_user_domain::model::{
-    Avatar, AvatarError, DisplayName, DisplayNameError, Email, EmailError, Name, NameError, User,
-    UserData,
+    Avatar, AvatarError, Bio, BioError, DisplayName, DisplayNameError, Email, EmailError, Locale,
+    LocaleError, Name, NameError, Pronouns, PronounsError, TimeZone, TimeZoneError, User, UserData,
 };
 use serde::{Deserialize, Serialize};
 
 use super::{
     id::{LocalUserId, LocalUserIdError},
     role::LocalRole,
+    status::LocalAccountStatus,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,10 +61,24 @@ impl TryFrom<LocalUser> for User {
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LocalUserData {
     pub name: String,
+    #[serde(default)]
+    pub name_canonical: String,
     pub display_name: String,
     pub role: LocalRole,
     pub email: Option<String>,
+    #[serde(default)]
+    pub email_canonical: Option<String>,
     pub avatar: Option<String>,
+    #[serde(default)]
+    pub status: LocalAccountStatus,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub pronouns: Option<String>,
 }
 
 impl From<UserData> for LocalUserData {
@@ -74,13 +89,25 @@ impl From<UserData> for LocalUserData {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = value;
         Self {
+            name_canonical: name.canonical(),
             name: name.into_inner(),
             display_name: display_name.into_inner(),
             role: role.into(),
+            email_canonical: email.as_ref().map(Email::canonical),
             email: email.map(Email::into_inner),
             avatar: avatar.map(Avatar::into_inner),
+            status: status.into(),
+            bio: bio.map(Bio::into_inner),
+            locale: locale.map(Locale::into_inner),
+            time_zone: time_zone.map(TimeZone::into_inner),
+            pronouns: pronouns.map(Pronouns::into_inner),
         }
     }
 }
@@ -91,10 +118,17 @@ impl TryFrom<LocalUserData> for UserData {
     fn try_from(value: LocalUserData) -> Result<Self, Self::Error> {
         let LocalUserData {
             name,
+            name_canonical: _,
             display_name,
             role,
             email,
+            email_canonical: _,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = value;
         let user_data = Self {
             name: Name::new(name)?,
@@ -102,6 +136,11 @@ impl TryFrom<LocalUserData> for UserData {
             role: role.into(),
             email: email.map(Email::new).transpose()?,
             avatar: avatar.map(Avatar::new).transpose()?,
+            status: status.into(),
+            bio: bio.map(Bio::new).transpose()?,
+            locale: locale.map(Locale::new).transpose()?,
+            time_zone: time_zone.map(TimeZone::new).transpose()?,
+            pronouns: pronouns.map(Pronouns::new).transpose()?,
         };
         Ok(user_data)
     }
@@ -113,4 +152,8 @@ pub enum LocalUserDataError {
     DisplayName(DisplayNameError),
     Email(EmailError),
     Avatar(AvatarError),
+    Bio(BioError),
+    Locale(LocaleError),
+    TimeZone(TimeZoneError),
+    Pronouns(PronounsError),
 }
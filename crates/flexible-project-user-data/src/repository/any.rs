@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{
+    model::{Role, User, UserData, UserFilters, UserId, UserPatch},
+    repository::{UserConflict, UserDatabase, UserDatabaseError},
+};
+use futures::{future::Either, stream::MapErr, TryStreamExt};
+
+use super::{
+    LocalError, LocalJsonError, LocalJsonUserDatabase, LocalJsonUsers, LocalUserDatabase,
+    LocalUsers,
+};
+
+/// Database of user data which is selected on startup of the user service:
+/// either [MongoDB](LocalUserDatabase) or [JSON file](LocalJsonUserDatabase) database.
+#[derive(Debug, Clone, From)]
+pub enum LocalAnyUserDatabase {
+    /// Database of user data stored in MongoDB.
+    Mongo(LocalUserDatabase),
+    /// Database of user data persisted into a JSON file.
+    Json(LocalJsonUserDatabase),
+}
+
+#[async_trait(?Send)]
+impl UserDatabase for LocalAnyUserDatabase {
+    type Error = LocalAnyError;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let user = match self {
+            Self::Mongo(database) => database.create(id, data).await?,
+            Self::Json(database) => database.create(id, data).await?,
+        };
+        Ok(user)
+    }
+
+    type Users = LocalAnyUsers;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let users = match self {
+            Self::Mongo(database) => {
+                let users = database.read(filter).await?;
+                Either::Left(users.map_err(LocalAnyError::Mongo as fn(_) -> _))
+            }
+            Self::Json(database) => {
+                let users = database.read(filter).await?;
+                Either::Right(users.map_err(LocalAnyError::Json as fn(_) -> _))
+            }
+        };
+        Ok(users)
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        let count = match self {
+            Self::Mongo(database) => database.count(filter).await?,
+            Self::Json(database) => database.count(filter).await?,
+        };
+        Ok(count)
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        let counts = match self {
+            Self::Mongo(database) => database.count_by_role(filter).await?,
+            Self::Json(database) => database.count_by_role(filter).await?,
+        };
+        Ok(counts)
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let user = match self {
+            Self::Mongo(database) => database.update(id, data).await?,
+            Self::Json(database) => database.update(id, data).await?,
+        };
+        Ok(user)
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let user = match self {
+            Self::Mongo(database) => database.patch(id, patch).await?,
+            Self::Json(database) => database.patch(id, patch).await?,
+        };
+        Ok(user)
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        let user = match self {
+            Self::Mongo(database) => database.delete(id).await?,
+            Self::Json(database) => database.delete(id).await?,
+        };
+        Ok(user)
+    }
+}
+
+/// Stream of filtered user data from the selected user database.
+pub type LocalAnyUsers = Either<
+    MapErr<LocalUsers, fn(LocalError) -> LocalAnyError>,
+    MapErr<LocalJsonUsers, fn(LocalJsonError) -> LocalAnyError>,
+>;
+
+/// Type of error which is returned on the selected user database failure.
+#[derive(Debug, Display, From, Error)]
+pub enum LocalAnyError {
+    /// MongoDB user database error.
+    Mongo(LocalError),
+    /// JSON file user database error.
+    Json(LocalJsonError),
+}
+
+impl UserDatabaseError for LocalAnyError {
+    fn conflict(&self) -> Option<UserConflict> {
+        match self {
+            Self::Mongo(error) => error.conflict(),
+            Self::Json(error) => error.conflict(),
+        }
+    }
+
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Mongo(error) => error.is_transient(),
+            Self::Json(error) => error.is_transient(),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::repository::Clock;
+
+/// Implementation of clock which returns current system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalClock;
+
+impl Clock for LocalClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
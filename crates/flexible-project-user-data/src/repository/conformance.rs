@@ -0,0 +1,469 @@
+//! Conformance of the local user database filters to the filters of the domain layer.
+//!
+//! The same generated users are filtered by the in-memory database of the domain layer
+//! and by the translated MongoDB query. By default, query is evaluated by a stand-in
+//! which approximates MongoDB semantics of the operators produced by the translation
+//! against documents in the layout of the local user model. The stand-in matches regexes
+//! with the same engine as the in-memory database, so only the real server could reveal
+//! differences of regex dialects: run ignored tests with `MONGODB_TEST_URL` set
+//! to the connection string of the disposable server to check the translation against it.
+
+use std::{borrow::Cow, cmp::Ordering, collections::BTreeSet, env, pin::pin};
+
+use chrono::{DateTime, TimeZone as _, Utc};
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
+use fp_user_domain::{
+    model::{
+        AccountStatus, AccountStatusFilters, AccountStatusKind, Avatar, Bio, DisplayName,
+        DisplayNameFilters, Email, Locale, Name, NameFilters, OptionAvatarFilters,
+        OptionBioFilters, OptionEmailFilters, OptionLocaleFilters, OptionPronounsFilters,
+        OptionTimeZoneFilters, Pronouns, Role, RoleFilters, TimeZone, User, UserData,
+        UserDataFilters, UserFilters, UserId, UserIdFilters,
+    },
+    repository::{memory::MemoryUserDatabase, UserDatabase},
+};
+use futures::{executor::block_on, TryStreamExt};
+use mongodb::bson::{from_document, to_document, uuid::Uuid, Bson, Document};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    client::{Client, DatabaseConfig},
+    model::LocalUser,
+};
+
+use super::{filter::IntoDocument, LocalUserDatabase};
+
+/// Name of the environment variable with the connection string of MongoDB server for tests.
+const MONGODB_TEST_URL: &str = "MONGODB_TEST_URL";
+
+const REGEXES: [&str; 10] = [
+    "^tanabe",
+    "(?i)^KOTLIN",
+    r"\d$",
+    r"example\.com$",
+    "^(?=.*e).*o",
+    "^$",
+    r"\w+/\w+",
+    "Кот",
+    "avatars/1",
+    "[",
+];
+
+/// Checks if the document satisfies the query the same way as MongoDB does.
+///
+/// Only operators which are produced by the translation are supported.
+fn matches(query: &Document, document: &Document) -> bool {
+    query.iter().all(|(path, condition)| {
+        let value = lookup(document, path);
+        match condition {
+            Bson::Document(operators) if is_operators(operators) => satisfies(value, operators),
+            // anything else is compared with the field by equality
+            condition => equals(value, condition),
+        }
+    })
+}
+
+fn is_operators(document: &Document) -> bool {
+    !document.is_empty() && document.keys().all(|key| key.starts_with('$'))
+}
+
+fn satisfies(value: Option<&Bson>, operators: &Document) -> bool {
+    operators
+        .iter()
+        .all(|(operator, operand)| match (operator.as_str(), operand) {
+            ("$eq", operand) => equals(value, operand),
+            ("$ne", operand) => !equals(value, operand),
+            ("$in", Bson::Array(operands)) => operands.iter().any(|o| equals(value, o)),
+            ("$nin", Bson::Array(operands)) => !operands.iter().any(|o| equals(value, o)),
+            ("$gt", operand) => compare(value, operand) == Some(Ordering::Greater),
+            ("$lte", operand) => compare(value, operand).is_some_and(Ordering::is_le),
+            // only string fields are matched by the regex
+            ("$regex", Bson::String(pattern)) => match value {
+                Some(Bson::String(value)) => Regex(pattern.as_str()).satisfies(value.as_str()),
+                _ => false,
+            },
+            ("$not", Bson::Document(operators)) if is_operators(operators) => {
+                !satisfies(value, operators)
+            }
+            (operator, _) => panic!("operator {operator} is not supported"),
+        })
+}
+
+/// Only values of the same type are compared, missing field is never compared.
+fn compare(value: Option<&Bson>, operand: &Bson) -> Option<Ordering> {
+    match (value?, operand) {
+        (Bson::DateTime(value), Bson::DateTime(operand)) => Some(value.cmp(operand)),
+        _ => None,
+    }
+}
+
+/// Resolves dot notation path of the field, returning `None` if the field is missing.
+fn lookup<'a>(document: &'a Document, path: &str) -> Option<&'a Bson> {
+    match path.split_once('.') {
+        Some((key, rest)) => match document.get(key)? {
+            Bson::Document(document) => lookup(document, rest),
+            _ => None,
+        },
+        None => document.get(path),
+    }
+}
+
+/// Missing field is equal to `null`.
+fn equals(value: Option<&Bson>, operand: &Bson) -> bool {
+    match (value, operand) {
+        (None | Some(Bson::Null), Bson::Null) => true,
+        (Some(value), operand) => value == operand,
+        (None, _) => false,
+    }
+}
+
+fn maybe<T>(rng: &mut StdRng, generate: impl FnOnce(&mut StdRng) -> T) -> Option<T> {
+    rng.gen_bool(0.5).then(|| generate(rng))
+}
+
+fn choose<T: Clone>(rng: &mut StdRng, values: &[T]) -> T {
+    values
+        .choose(rng)
+        .expect("values should not be empty")
+        .clone()
+}
+
+fn time(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2023, 6, day, 0, 0, 0).unwrap()
+}
+
+fn generate_users(rng: &mut StdRng, count: usize) -> Vec<User> {
+    let statuses = [
+        AccountStatus::Active,
+        AccountStatus::Suspended {
+            until: time(1),
+            reason: "spam".to_owned(),
+        },
+        AccountStatus::Suspended {
+            until: time(3),
+            reason: "spam".to_owned(),
+        },
+        AccountStatus::Banned {
+            reason: "abuse".to_owned(),
+        },
+    ];
+    (0..count)
+        .map(|index| {
+            let id = Uuid::from_bytes(rng.gen()).to_string();
+            let name = choose(rng, &["tanabe", "Kotlinist", "flexible_user", "MODERATOR"]);
+            let data = UserData {
+                name: Name::new(format!("{name}{index}")).unwrap(),
+                display_name: DisplayName::new(choose(rng, &["Tanabe", "Kotlinist", "Кот"]))
+                    .unwrap(),
+                role: choose(rng, &[Role::User, Role::Moderator, Role::Administrator]),
+                email: maybe(rng, |rng| {
+                    let local = choose(rng, &["tanabe", "Kotlin.ist"]);
+                    let domain = choose(rng, &["example.com", "Example.org"]);
+                    Email::new(format!("{local}{index}@{domain}")).unwrap()
+                }),
+                avatar: maybe(rng, |rng| {
+                    let avatar = rng.gen_range(0..3);
+                    Avatar::new(format!("https://example.com/avatars/{avatar}.png")).unwrap()
+                }),
+                status: choose(rng, &statuses),
+                bio: maybe(rng, |rng| {
+                    Bio::new(choose(rng, &["Rust developer", "Кот", "hello world"])).unwrap()
+                }),
+                locale: maybe(rng, |rng| {
+                    Locale::new(choose(rng, &["en-US", "ru", "ja"])).unwrap()
+                }),
+                time_zone: maybe(rng, |rng| {
+                    TimeZone::new(choose(rng, &["Europe/Moscow", "UTC", "Asia/Tokyo"])).unwrap()
+                }),
+                pronouns: maybe(rng, |rng| {
+                    Pronouns::new(choose(rng, &["they/them", "she/her", "he/him"])).unwrap()
+                }),
+            };
+            User {
+                id: UserId::new(id),
+                data,
+            }
+        })
+        .collect()
+}
+
+type Operators<T> = (
+    Option<Equal<Cow<'static, T>>>,
+    Option<NotEqual<Cow<'static, T>>>,
+    Option<In<Cow<'static, [T]>>>,
+    Option<NotIn<Cow<'static, [T]>>>,
+);
+
+fn operators<T: Clone>(rng: &mut StdRng, values: &[T]) -> Operators<T> {
+    let some = |rng: &mut StdRng| {
+        let count = rng.gen_range(0..=3);
+        (0..count).map(|_| choose(rng, values)).collect::<Vec<_>>()
+    };
+    (
+        maybe(rng, |rng| Equal(Cow::Owned(choose(rng, values)))),
+        maybe(rng, |rng| NotEqual(Cow::Owned(choose(rng, values)))),
+        maybe(rng, |rng| In(Cow::Owned(some(rng)))),
+        maybe(rng, |rng| NotIn(Cow::Owned(some(rng)))),
+    )
+}
+
+fn regex(rng: &mut StdRng) -> Option<Regex<Cow<'static, str>>> {
+    maybe(rng, |rng| Regex(Cow::Borrowed(choose(rng, &REGEXES))))
+}
+
+fn generate_filter(rng: &mut StdRng, users: &[User]) -> UserFilters<'static> {
+    fn values<T: Clone>(users: &[User], field: impl Fn(&UserData) -> T) -> Vec<T> {
+        users.iter().map(|user| field(&user.data)).collect()
+    }
+
+    let mut ids: Vec<_> = users.iter().map(|user| user.id.clone()).collect();
+    ids.extend([
+        UserId::new("not-a-uuid"),
+        UserId::new(Uuid::from_bytes(rng.gen()).to_string()),
+    ]);
+    let mut names = values(users, |data| data.name.clone());
+    names.extend(names.clone().iter().map(|name| {
+        let name = name.as_str().to_uppercase();
+        Name::new(name).unwrap()
+    }));
+    let mut emails = values(users, |data| data.email.clone());
+    emails.extend(emails.clone().iter().flatten().map(|email| {
+        let email = email.as_str().to_uppercase();
+        Some(Email::new(email).unwrap())
+    }));
+    emails.push(None);
+
+    let id = maybe(rng, |rng| {
+        let (eq, ne, r#in, nin) = operators(rng, &ids);
+        UserIdFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            ..Default::default()
+        }
+    });
+    let data = maybe(rng, |rng| UserDataFilters {
+        name: maybe(rng, |rng| {
+            let (eq, ne, r#in, nin) = operators(rng, &names);
+            let regex = regex(rng);
+            NameFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+        display_name: maybe(rng, |rng| {
+            let display_names = values(users, |data| data.display_name.clone());
+            let (eq, ne, r#in, nin) = operators(rng, &display_names);
+            let regex = regex(rng);
+            DisplayNameFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+        role: maybe(rng, |rng| {
+            let roles = [Role::User, Role::Moderator, Role::Administrator];
+            let (eq, ne, r#in, nin) = operators(rng, &roles);
+            RoleFilters { eq, ne, r#in, nin }
+        }),
+        email: maybe(rng, |rng| {
+            let (eq, ne, r#in, nin) = operators(rng, &emails);
+            let regex = regex(rng);
+            OptionEmailFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+        avatar: maybe(rng, |rng| {
+            let mut avatars = values(users, |data| data.avatar.clone());
+            avatars.push(None);
+            let (eq, ne, r#in, nin) = operators(rng, &avatars);
+            let regex = regex(rng);
+            OptionAvatarFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+        status: maybe(rng, |rng| {
+            let kinds = [
+                AccountStatusKind::Active,
+                AccountStatusKind::Suspended,
+                AccountStatusKind::Banned,
+            ];
+            let (eq, ne, r#in, nin) = operators(rng, &kinds);
+            let at = maybe(rng, |rng| time(rng.gen_range(1..=4)));
+            AccountStatusFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                at,
+            }
+        }),
+        bio: maybe(rng, |rng| {
+            let mut bios = values(users, |data| data.bio.clone());
+            bios.push(None);
+            let (eq, ne, r#in, nin) = operators(rng, &bios);
+            let regex = regex(rng);
+            OptionBioFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+        locale: maybe(rng, |rng| {
+            let mut locales = values(users, |data| data.locale.clone());
+            locales.push(None);
+            let (eq, ne, r#in, nin) = operators(rng, &locales);
+            OptionLocaleFilters { eq, ne, r#in, nin }
+        }),
+        time_zone: maybe(rng, |rng| {
+            let mut time_zones = values(users, |data| data.time_zone);
+            time_zones.push(None);
+            let (eq, ne, r#in, nin) = operators(rng, &time_zones);
+            OptionTimeZoneFilters { eq, ne, r#in, nin }
+        }),
+        pronouns: maybe(rng, |rng| {
+            let mut pronouns_list = values(users, |data| data.pronouns.clone());
+            pronouns_list.push(None);
+            let (eq, ne, r#in, nin) = operators(rng, &pronouns_list);
+            let regex = regex(rng);
+            OptionPronounsFilters {
+                eq,
+                ne,
+                r#in,
+                nin,
+                regex,
+            }
+        }),
+    });
+    UserFilters { id, data }
+}
+
+/// Filters users the same way as the local user database does.
+fn filter_local(documents: &[Document], filter: &UserFilters<'_>) -> BTreeSet<UserId> {
+    let query = filter.clone().into_document().unwrap();
+    documents
+        .iter()
+        .filter(|document| matches(&query, document))
+        .map(|document| {
+            let user: LocalUser = from_document(document.clone()).unwrap();
+            User::try_from(user).unwrap().id
+        })
+        .collect()
+}
+
+fn filter_memory(database: &MemoryUserDatabase, filter: &UserFilters<'_>) -> BTreeSet<UserId> {
+    block_on(filter_ids(database, filter)).unwrap()
+}
+
+async fn filter_ids<Database>(
+    database: &Database,
+    filter: &UserFilters<'_>,
+) -> Result<BTreeSet<UserId>, Database::Error>
+where
+    Database: UserDatabase,
+{
+    let users = database.read(filter.clone()).await?;
+    let users = pin!(users);
+    users.map_ok(|user| user.id).try_collect().await
+}
+
+#[test]
+fn memory_and_local_agree() {
+    for seed in 0..16 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let users = generate_users(&mut rng, 24);
+        let database = MemoryUserDatabase::with_users(users.clone()).unwrap();
+        let documents: Vec<_> = users
+            .iter()
+            .map(|user| {
+                let user = LocalUser::try_from(user.clone()).unwrap();
+                to_document(&user).unwrap()
+            })
+            .collect();
+
+        for _ in 0..64 {
+            let filter = generate_filter(&mut rng, &users);
+            assert_eq!(
+                filter_local(&documents, &filter),
+                filter_memory(&database, &filter),
+                "backends disagree on filter {filter:#?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn user_without_email_does_not_match_regex() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let users = generate_users(&mut rng, 24);
+    let database = MemoryUserDatabase::with_users(users.clone()).unwrap();
+    let filter = {
+        let email = OptionEmailFilters::builder()
+            .regex(Regex(Cow::Borrowed(".*")))
+            .build();
+        let data = UserDataFilters::builder().email(email).build();
+        UserFilters::builder().data(data).build()
+    };
+    let expected: BTreeSet<_> = users
+        .into_iter()
+        .filter(|user| user.data.email.is_some())
+        .map(|user| user.id)
+        .collect();
+    assert_eq!(filter_memory(&database, &filter), expected);
+}
+
+#[test]
+#[ignore = "requires MongoDB server by the connection string from `MONGODB_TEST_URL`"]
+fn memory_and_mongodb_agree() {
+    let url = env::var(MONGODB_TEST_URL).expect("connection string of MongoDB server for tests");
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let config = DatabaseConfig::builder()
+            .database(format!(
+                "fp-user-conformance-{:016x}",
+                rand::random::<u64>()
+            ))
+            .build();
+        let client = Client::new(url, config).await.unwrap();
+        let local = LocalUserDatabase::new(client.clone()).await.unwrap();
+
+        for seed in 0..4 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let users = generate_users(&mut rng, 24);
+            let memory = MemoryUserDatabase::with_users(users.clone()).unwrap();
+            client.database().drop(None).await.unwrap();
+            for User { id, data } in users.iter().cloned() {
+                local.create(id, data).await.unwrap();
+            }
+
+            for _ in 0..64 {
+                let filter = generate_filter(&mut rng, &users);
+                assert_eq!(
+                    filter_ids(&local, &filter).await.unwrap(),
+                    filter_ids(&memory, &filter).await.unwrap(),
+                    "MongoDB and memory disagree on filter {filter:#?}",
+                );
+            }
+        }
+        client.database().drop(None).await.unwrap();
+    });
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use fp_user_domain::{
+    model::{UserCredentials, UserId},
+    repository::CredentialsDatabase,
+};
+use mongodb::{
+    bson::{doc, to_bson},
+    options::ReplaceOptions,
+    Collection,
+};
+
+use crate::{
+    client::Client,
+    model::{LocalCredentials, LocalUserId},
+};
+
+use super::user::LocalError;
+
+/// Local database of user credentials.
+#[derive(Debug, Clone)]
+pub struct LocalCredentialsDatabase {
+    collection: Collection<LocalCredentials>,
+}
+
+impl LocalCredentialsDatabase {
+    /// Creates new local user credentials repository instance.
+    pub async fn new(client: Client) -> Result<Self, LocalError> {
+        let database = client.database();
+        let collection = database.collection(&client.config.credentials_collection);
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait(?Send)]
+impl CredentialsDatabase for LocalCredentialsDatabase {
+    type Error = LocalError;
+
+    async fn read(&self, id: UserId) -> Result<Option<UserCredentials>, Self::Error> {
+        let Self { collection } = self;
+        let id = LocalUserId::try_from(id)?;
+
+        let filter = doc! { "_id": to_bson(&id)? };
+        let credentials = collection.find_one(filter, None).await?;
+        Ok(credentials.map(Into::into))
+    }
+
+    async fn upsert(
+        &self,
+        id: UserId,
+        credentials: UserCredentials,
+    ) -> Result<UserCredentials, Self::Error> {
+        let Self { collection } = self;
+        let id = LocalUserId::try_from(id)?;
+
+        let filter = doc! { "_id": to_bson(&id)? };
+        let credentials = LocalCredentials::new(id, credentials);
+        let options = ReplaceOptions::builder().upsert(true).build();
+        collection
+            .replace_one(filter, &credentials, options)
+            .await?;
+        Ok(credentials.into())
+    }
+
+    async fn delete(&self, id: UserId) -> Result<Option<UserCredentials>, Self::Error> {
+        let Self { collection } = self;
+        let id = LocalUserId::try_from(id)?;
+
+        let filter = doc! { "_id": to_bson(&id)? };
+        let credentials = collection.find_one_and_delete(filter, None).await?;
+        Ok(credentials.map(Into::into))
+    }
+}
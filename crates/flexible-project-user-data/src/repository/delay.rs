@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use fp_user_domain::repository::Delay;
+
+/// Implementation of delay which sleeps on the Tokio timer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalDelay;
+
+#[async_trait(?Send)]
+impl Delay for LocalDelay {
+    async fn delay(&self, duration: Duration) {
+        let duration = duration.to_std().unwrap_or_default();
+        tokio::time::sleep(duration).await
+    }
+}
@@ -1,30 +1,61 @@
-use std::{borrow::Borrow, convert::identity};
+use std::borrow::{Borrow, Cow};
 
 use fp_filter::{Equal, In, NotEqual, NotIn, Regex};
 use fp_user_domain::model::{
-    Avatar, DisplayName, DisplayNameFilters, Email, Name, NameFilters, OptionAvatarFilters,
-    OptionEmailFilters, Role, RoleFilters, UserDataFilters, UserFilters, UserId, UserIdFilters,
+    AccountStatusFilters, AccountStatusKind, Avatar, Bio, DisplayName, DisplayNameFilters, Email,
+    Locale, Name, NameFilters, OptionAvatarFilters, OptionBioFilters, OptionEmailFilters,
+    OptionLocaleFilters, OptionPronounsFilters, OptionTimeZoneFilters, Pronouns, Role, RoleFilters,
+    TimeZone, UserDataFilters, UserFilters, UserId, UserIdFilters,
 };
-use mongodb::bson::{to_bson, Bson, Document};
+use mongodb::bson::{doc, to_bson, Bson, DateTime as BsonDateTime, Document};
 
-use crate::model::{LocalRole, LocalUserId};
+use crate::model::{LocalAccountStatusKind, LocalRole, LocalUserId};
 
 use super::user::LocalError;
 
+/// Translates filters of the domain layer into MongoDB query documents.
+///
+/// Every translated filter must select the same users as [`fp_filter::Filter::satisfies`] does.
+/// Regex filters are evaluated by MongoDB in its own dialect (PCRE),
+/// which agrees with the one of the domain layer on anchors, classes, lookarounds and inline flags.
 pub trait IntoDocument {
     fn into_document(self) -> Result<Document, LocalError>;
 }
 
+/// Translates regex filter of the field into `$regex` operator.
+///
+/// Invalid pattern never satisfies the filter of the domain layer,
+/// so it is translated into the operator which never matches instead of failing the query.
+fn regex_operators(regex: Regex<Cow<'_, str>>) -> Document {
+    if !regex.is_valid() {
+        return doc! { "$in": [] };
+    }
+    let Regex(regex) = regex;
+    doc! { "$regex": regex.into_owned() }
+}
+
+/// Inserts operator document of the field only if it filters anything,
+/// because an empty document would be compared with the field by equality.
+fn insert_operators(document: &mut Document, key: impl Into<String>, operators: Document) {
+    if !operators.is_empty() {
+        document.insert(key, operators);
+    }
+}
+
 impl IntoDocument for UserFilters<'_> {
     fn into_document(self) -> Result<Document, LocalError> {
         let Self { id, data } = self;
 
         let mut document = Document::new();
         if let Some(id) = id {
-            document.insert("_id", id.into_document()?);
+            insert_operators(&mut document, "_id", id.into_document()?);
         }
         if let Some(data) = data {
-            document.insert("data", data.into_document()?);
+            // fields of embedded document are filtered by dot notation,
+            // because embedded document filter matches the whole document by equality
+            for (key, operators) in data.into_document()? {
+                document.insert(format!("data.{key}"), operators);
+            }
         }
         Ok(document)
     }
@@ -38,28 +69,62 @@ impl IntoDocument for UserDataFilters<'_> {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = self;
 
         let mut document = Document::new();
-        if let Some(name) = name {
-            document.insert("name", name.into_document()?);
+        if let Some(mut name) = name {
+            // regex matches the name as is, not its canonical form
+            let regex = name.regex.take();
+            insert_operators(&mut document, "name_canonical", name.into_document()?);
+            if let Some(regex) = regex {
+                document.insert("name", regex_operators(regex));
+            }
         }
         if let Some(display_name) = display_name {
-            document.insert("display_name", display_name.into_document()?);
+            insert_operators(&mut document, "display_name", display_name.into_document()?);
         }
         if let Some(role) = role {
-            document.insert("role", role.into_document()?);
+            insert_operators(&mut document, "role", role.into_document()?);
         }
-        if let Some(email) = email {
-            document.insert("email", email.into_document()?);
+        if let Some(mut email) = email {
+            // regex matches the email as is, not its canonical form
+            let regex = email.regex.take();
+            insert_operators(&mut document, "email_canonical", email.into_document()?);
+            if let Some(regex) = regex {
+                document.insert("email", regex_operators(regex));
+            }
         }
         if let Some(avatar) = avatar {
-            document.insert("avatar", avatar.into_document()?);
+            insert_operators(&mut document, "avatar", avatar.into_document()?);
+        }
+        if let Some(status) = status {
+            for (key, operators) in status.into_document()? {
+                document.insert(format!("status.{key}"), operators);
+            }
+        }
+        if let Some(bio) = bio {
+            insert_operators(&mut document, "bio", bio.into_document()?);
+        }
+        if let Some(locale) = locale {
+            insert_operators(&mut document, "locale", locale.into_document()?);
+        }
+        if let Some(time_zone) = time_zone {
+            insert_operators(&mut document, "time_zone", time_zone.into_document()?);
+        }
+        if let Some(pronouns) = pronouns {
+            insert_operators(&mut document, "pronouns", pronouns.into_document()?);
         }
         Ok(document)
     }
 }
 
+/// Identifiers which could not be stored in the local database are never equal
+/// to identifiers of existing users.
 impl IntoDocument for UserIdFilters<'_> {
     fn into_document(self) -> Result<Document, LocalError> {
         let Self {
@@ -70,40 +135,43 @@ impl IntoDocument for UserIdFilters<'_> {
             nin,
         } = self;
 
-        fn ids_to_bson(ids: &[UserId]) -> impl Iterator<Item = Result<Bson, LocalError>> + '_ {
-            ids.iter()
-                .cloned()
-                .map(|id| LocalUserId::try_from(id).map_err(Into::into))
-                .map(|result| {
-                    result
-                        .map(|id| to_bson(&id).map_err(Into::into))
-                        .and_then(identity)
-                })
+        fn id_to_bson(id: UserId) -> Result<Option<Bson>, LocalError> {
+            match LocalUserId::try_from(id) {
+                Ok(id) => Ok(Some(to_bson(&id)?)),
+                Err(_) => Ok(None),
+            }
+        }
+        fn ids_to_bson(ids: &[UserId]) -> Result<Vec<Bson>, LocalError> {
+            let ids = ids.iter().cloned().map(id_to_bson);
+            ids.filter_map(Result::transpose).collect()
         }
 
         let mut document = Document::new();
         if let Some(Equal(id)) = eq {
-            let id = id.into_owned();
-            let id = LocalUserId::try_from(id)?;
-            document.insert("$eq", to_bson(&id)?);
+            let Some(id) = id_to_bson(id.into_owned())? else {
+                return Ok(doc! { "$in": [] });
+            };
+            document.insert("$eq", id);
         }
         if let Some(NotEqual(id)) = ne {
-            let id = id.into_owned();
-            let id = LocalUserId::try_from(id)?;
-            document.insert("$ne", to_bson(&id)?);
+            if let Some(id) = id_to_bson(id.into_owned())? {
+                document.insert("$ne", id);
+            }
         }
         if let Some(In(ids)) = r#in {
-            let ids = ids_to_bson(ids.borrow()).collect::<Result<Vec<_>, _>>()?;
-            document.insert("$in", ids);
+            document.insert("$in", ids_to_bson(ids.borrow())?);
         }
         if let Some(NotIn(ids)) = nin {
-            let ids = ids_to_bson(ids.borrow()).collect::<Result<Vec<_>, _>>()?;
-            document.insert("$nin", ids);
+            document.insert("$nin", ids_to_bson(ids.borrow())?);
         }
         Ok(document)
     }
 }
 
+/// Translates filters of canonical user name.
+///
+/// Regex filter is translated by [user data filters](UserDataFilters),
+/// because it matches another field.
 impl IntoDocument for NameFilters<'_> {
     fn into_document(self) -> Result<Document, LocalError> {
         let Self {
@@ -111,27 +179,24 @@ impl IntoDocument for NameFilters<'_> {
             ne,
             r#in,
             nin,
-            regex,
+            regex: _,
         } = self;
 
         let mut document = Document::new();
         if let Some(Equal(name)) = eq {
-            document.insert("$eq", name.as_str());
+            document.insert("$eq", name.canonical());
         }
         if let Some(NotEqual(name)) = ne {
-            document.insert("$ne", name.as_str());
+            document.insert("$ne", name.canonical());
         }
         if let Some(In(ids)) = r#in {
-            let ids: Vec<_> = ids.iter().map(Name::as_str).collect();
+            let ids: Vec<_> = ids.iter().map(Name::canonical).collect();
             document.insert("$in", ids);
         }
         if let Some(NotIn(ids)) = nin {
-            let ids: Vec<_> = ids.iter().map(Name::as_str).collect();
+            let ids: Vec<_> = ids.iter().map(Name::canonical).collect();
             document.insert("$nin", ids);
         }
-        if let Some(Regex(regex)) = regex {
-            document.insert("$regex", &*regex);
-        }
         Ok(document)
     }
 }
@@ -161,8 +226,8 @@ impl IntoDocument for DisplayNameFilters<'_> {
             let ids: Vec<_> = ids.iter().map(DisplayName::as_str).collect();
             document.insert("$nin", ids);
         }
-        if let Some(Regex(regex)) = regex {
-            document.insert("$regex", &*regex);
+        if let Some(regex) = regex {
+            document.extend(regex_operators(regex));
         }
         Ok(document)
     }
@@ -203,6 +268,97 @@ impl IntoDocument for RoleFilters<'_> {
     }
 }
 
+/// Translates into the document of the account status fields, `kind` and `until`.
+///
+/// If the filter is evaluated at some time, the set of satisfying status kinds is translated:
+/// suspension which ends after that time has `until` greater than it,
+/// and `until` is missing from other statuses, so it is never greater.
+impl IntoDocument for AccountStatusFilters<'_> {
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            at,
+        } = self;
+
+        fn kinds_to_bson(
+            kinds: &[AccountStatusKind],
+        ) -> impl Iterator<Item = Result<Bson, LocalError>> + '_ {
+            kinds
+                .iter()
+                .copied()
+                .map(LocalAccountStatusKind::from)
+                .map(|kind| to_bson(&kind).map_err(Into::into))
+        }
+
+        let Some(at) = at else {
+            let mut document = Document::new();
+            if let Some(Equal(kind)) = eq {
+                let kind = LocalAccountStatusKind::from(kind.into_owned());
+                document.insert("$eq", to_bson(&kind)?);
+            }
+            if let Some(NotEqual(kind)) = ne {
+                let kind = LocalAccountStatusKind::from(kind.into_owned());
+                document.insert("$ne", to_bson(&kind)?);
+            }
+            if let Some(In(kinds)) = r#in {
+                let kinds = kinds_to_bson(kinds.borrow()).collect::<Result<Vec<_>, _>>()?;
+                document.insert("$in", kinds);
+            }
+            if let Some(NotIn(kinds)) = nin {
+                let kinds = kinds_to_bson(kinds.borrow()).collect::<Result<Vec<_>, _>>()?;
+                document.insert("$nin", kinds);
+            }
+            let mut status = Document::new();
+            insert_operators(&mut status, "kind", document);
+            return Ok(status);
+        };
+
+        let satisfies = |kind: AccountStatusKind| {
+            eq.as_ref().is_none_or(|Equal(eq)| **eq == kind)
+                && ne.as_ref().is_none_or(|NotEqual(ne)| **ne != kind)
+                && r#in.as_ref().is_none_or(|In(r#in)| r#in.contains(&kind))
+                && nin.as_ref().is_none_or(|NotIn(nin)| !nin.contains(&kind))
+        };
+        let kinds = |kinds: &[AccountStatusKind]| -> Result<Bson, LocalError> {
+            let kinds = kinds_to_bson(kinds).collect::<Result<Vec<_>, _>>()?;
+            Ok(kinds.into())
+        };
+        let at = BsonDateTime::from_chrono(at);
+        let (active, suspended, banned) = (
+            satisfies(AccountStatusKind::Active),
+            satisfies(AccountStatusKind::Suspended),
+            satisfies(AccountStatusKind::Banned),
+        );
+        let ongoing_or_active = [AccountStatusKind::Active, AccountStatusKind::Suspended];
+        let document = match (active, suspended, banned) {
+            (false, false, false) => doc! { "kind": { "$in": [] } },
+            (true, false, false) => doc! {
+                "kind": { "$in": kinds(&ongoing_or_active)? },
+                "until": { "$not": { "$gt": at } },
+            },
+            (false, true, false) => doc! { "until": { "$gt": at } },
+            (false, false, true) => {
+                doc! { "kind": { "$in": kinds(&[AccountStatusKind::Banned])? } }
+            }
+            (true, true, false) => doc! { "kind": { "$in": kinds(&ongoing_or_active)? } },
+            (true, false, true) => doc! { "until": { "$not": { "$gt": at } } },
+            (false, true, true) => doc! {
+                "kind": { "$nin": kinds(&[AccountStatusKind::Active])? },
+                "until": { "$not": { "$lte": at } },
+            },
+            (true, true, true) => Document::new(),
+        };
+        Ok(document)
+    }
+}
+
+/// Translates filters of canonical user email.
+///
+/// Regex filter is translated by [user data filters](UserDataFilters),
+/// because it matches another field.
 impl IntoDocument for OptionEmailFilters<'_> {
     fn into_document(self) -> Result<Document, LocalError> {
         let Self {
@@ -210,35 +366,32 @@ impl IntoDocument for OptionEmailFilters<'_> {
             ne,
             r#in,
             nin,
-            regex,
+            regex: _,
         } = self;
 
         let mut document = Document::new();
         if let Some(Equal(email)) = eq {
             let email = email.into_owned();
-            document.insert("$eq", email.as_ref().map(Email::as_str));
+            document.insert("$eq", email.as_ref().map(Email::canonical));
         }
         if let Some(NotEqual(email)) = ne {
             let email = email.into_owned();
-            document.insert("$ne", email.as_ref().map(Email::as_str));
+            document.insert("$ne", email.as_ref().map(Email::canonical));
         }
         if let Some(In(emails)) = r#in {
             let emails: Vec<_> = emails
                 .iter()
-                .map(|email| email.as_ref().map(Email::as_str))
+                .map(|email| email.as_ref().map(Email::canonical))
                 .collect();
             document.insert("$in", emails);
         }
         if let Some(NotIn(emails)) = nin {
             let emails: Vec<_> = emails
                 .iter()
-                .map(|email| email.as_ref().map(Email::as_str))
+                .map(|email| email.as_ref().map(Email::canonical))
                 .collect();
             document.insert("$nin", emails);
         }
-        if let Some(Regex(regex)) = regex {
-            document.insert("$regex", &*regex);
-        }
         Ok(document)
     }
 }
@@ -276,8 +429,150 @@ impl IntoDocument for OptionAvatarFilters<'_> {
                 .collect();
             document.insert("$nin", avatars);
         }
-        if let Some(Regex(regex)) = regex {
-            document.insert("$regex", &*regex);
+        if let Some(regex) = regex {
+            document.extend(regex_operators(regex));
+        }
+        Ok(document)
+    }
+}
+
+impl IntoDocument for OptionBioFilters<'_> {
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = self;
+
+        let mut document = Document::new();
+        if let Some(Equal(bio)) = eq {
+            let bio = bio.into_owned();
+            document.insert("$eq", bio.as_ref().map(Bio::as_str));
+        }
+        if let Some(NotEqual(bio)) = ne {
+            let bio = bio.into_owned();
+            document.insert("$ne", bio.as_ref().map(Bio::as_str));
+        }
+        if let Some(In(bios)) = r#in {
+            let bios: Vec<_> = bios
+                .iter()
+                .map(|bio| bio.as_ref().map(Bio::as_str))
+                .collect();
+            document.insert("$in", bios);
+        }
+        if let Some(NotIn(bios)) = nin {
+            let bios: Vec<_> = bios
+                .iter()
+                .map(|bio| bio.as_ref().map(Bio::as_str))
+                .collect();
+            document.insert("$nin", bios);
+        }
+        if let Some(regex) = regex {
+            document.extend(regex_operators(regex));
+        }
+        Ok(document)
+    }
+}
+
+impl IntoDocument for OptionLocaleFilters<'_> {
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self { eq, ne, r#in, nin } = self;
+
+        let mut document = Document::new();
+        if let Some(Equal(locale)) = eq {
+            let locale = locale.into_owned();
+            document.insert("$eq", locale.as_ref().map(Locale::as_str));
+        }
+        if let Some(NotEqual(locale)) = ne {
+            let locale = locale.into_owned();
+            document.insert("$ne", locale.as_ref().map(Locale::as_str));
+        }
+        if let Some(In(locales)) = r#in {
+            let locales: Vec<_> = locales
+                .iter()
+                .map(|locale| locale.as_ref().map(Locale::as_str))
+                .collect();
+            document.insert("$in", locales);
+        }
+        if let Some(NotIn(locales)) = nin {
+            let locales: Vec<_> = locales
+                .iter()
+                .map(|locale| locale.as_ref().map(Locale::as_str))
+                .collect();
+            document.insert("$nin", locales);
+        }
+        Ok(document)
+    }
+}
+
+impl IntoDocument for OptionTimeZoneFilters<'_> {
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self { eq, ne, r#in, nin } = self;
+
+        let mut document = Document::new();
+        if let Some(Equal(time_zone)) = eq {
+            let time_zone = time_zone.into_owned();
+            document.insert("$eq", time_zone.as_ref().map(TimeZone::as_str));
+        }
+        if let Some(NotEqual(time_zone)) = ne {
+            let time_zone = time_zone.into_owned();
+            document.insert("$ne", time_zone.as_ref().map(TimeZone::as_str));
+        }
+        if let Some(In(time_zones)) = r#in {
+            let time_zones: Vec<_> = time_zones
+                .iter()
+                .map(|time_zone| time_zone.as_ref().map(TimeZone::as_str))
+                .collect();
+            document.insert("$in", time_zones);
+        }
+        if let Some(NotIn(time_zones)) = nin {
+            let time_zones: Vec<_> = time_zones
+                .iter()
+                .map(|time_zone| time_zone.as_ref().map(TimeZone::as_str))
+                .collect();
+            document.insert("$nin", time_zones);
+        }
+        Ok(document)
+    }
+}
+
+impl IntoDocument for OptionPronounsFilters<'_> {
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = self;
+
+        let mut document = Document::new();
+        if let Some(Equal(pronouns)) = eq {
+            let pronouns = pronouns.into_owned();
+            document.insert("$eq", pronouns.as_ref().map(Pronouns::as_str));
+        }
+        if let Some(NotEqual(pronouns)) = ne {
+            let pronouns = pronouns.into_owned();
+            document.insert("$ne", pronouns.as_ref().map(Pronouns::as_str));
+        }
+        if let Some(In(pronouns_list)) = r#in {
+            let pronouns_list: Vec<_> = pronouns_list
+                .iter()
+                .map(|pronouns| pronouns.as_ref().map(Pronouns::as_str))
+                .collect();
+            document.insert("$in", pronouns_list);
+        }
+        if let Some(NotIn(pronouns_list)) = nin {
+            let pronouns_list: Vec<_> = pronouns_list
+                .iter()
+                .map(|pronouns| pronouns.as_ref().map(Pronouns::as_str))
+                .collect();
+            document.insert("$nin", pronouns_list);
+        }
+        if let Some(regex) = regex {
+            document.extend(regex_operators(regex));
         }
         Ok(document)
     }
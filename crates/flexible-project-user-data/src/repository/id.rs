@@ -13,6 +13,7 @@ impl GenerateId<User> for LocalGenerateUserId {
     type Error = Infallible;
 
     fn generate_id(&self) -> Result<UserId, Self::Error> {
-        LocalUserId::new().try_into()
+        let id = LocalUserId::new().into();
+        Ok(id)
     }
 }
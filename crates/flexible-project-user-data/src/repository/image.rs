@@ -0,0 +1,144 @@
+use std::{io::Cursor, sync::Arc};
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{model::ImageFormat, repository::ResizeImage};
+use image::{
+    imageops::FilterType,
+    io::{Limits, Reader},
+    ImageError, ImageOutputFormat,
+};
+use tokio::task::{self, JoinError};
+use typed_builder::TypedBuilder;
+
+/// Limits of the images which are decoded by the [image processor](LocalResizeImage).
+#[derive(Debug, Clone, Copy, TypedBuilder)]
+pub struct LocalResizeImageConfig {
+    /// Maximal width of the decoded image in pixels.
+    #[builder(default = 4096)]
+    pub max_width: u32,
+    /// Maximal height of the decoded image in pixels.
+    #[builder(default = 4096)]
+    pub max_height: u32,
+    /// Maximal count of bytes which could be allocated to decode the image.
+    #[builder(default = 128 * 1024 * 1024)]
+    pub max_alloc: u64,
+}
+
+impl Default for LocalResizeImageConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Implementation of image processor which uses `image` crate.
+///
+/// Images are decoded with configured limits, so small uploads which declare
+/// huge dimensions are rejected before the pixel buffer is allocated.
+/// Processing runs on the blocking thread pool of the Tokio runtime.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalResizeImage {
+    config: LocalResizeImageConfig,
+}
+
+impl LocalResizeImage {
+    /// Creates new image processor with provided limits.
+    pub fn new(config: LocalResizeImageConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait(?Send)]
+impl ResizeImage for LocalResizeImage {
+    type Error = LocalResizeImageError;
+
+    async fn resize_square(
+        &self,
+        content: Arc<[u8]>,
+        format: ImageFormat,
+        size: u32,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let Self { config } = *self;
+        let resized =
+            task::spawn_blocking(move || resize_square(&content, format, size, config)).await??;
+        Ok(resized)
+    }
+}
+
+fn resize_square(
+    content: &[u8],
+    format: ImageFormat,
+    size: u32,
+    config: LocalResizeImageConfig,
+) -> Result<Vec<u8>, ImageError> {
+    let LocalResizeImageConfig {
+        max_width,
+        max_height,
+        max_alloc,
+    } = config;
+    let format = match format {
+        ImageFormat::Png => image::ImageFormat::Png,
+        ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+    };
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(max_width);
+    limits.max_image_height = Some(max_height);
+    limits.max_alloc = Some(max_alloc);
+
+    let mut reader = Reader::with_format(Cursor::new(content), format);
+    reader.limits(limits);
+    let image = reader.decode()?;
+    let image = image.resize_to_fill(size, size, FilterType::Lanczos3);
+
+    let mut output = Cursor::new(Vec::new());
+    image.write_to(&mut output, ImageOutputFormat::Png)?;
+    Ok(output.into_inner())
+}
+
+/// Type of error which is returned when image processing fails.
+#[derive(Debug, Display, From, Error)]
+#[from(forward)]
+pub struct LocalResizeImageError {
+    kind: LocalResizeImageErrorKind,
+}
+
+#[derive(Debug, Display, From, Error)]
+enum LocalResizeImageErrorKind {
+    Image(ImageError),
+    Join(JoinError),
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use fp_user_domain::{model::ImageFormat, repository::ResizeImage};
+    use image::{ImageOutputFormat, RgbImage};
+    use tokio::runtime::Builder;
+
+    use super::{LocalResizeImage, LocalResizeImageConfig};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbImage::new(width, height);
+        let mut output = Cursor::new(Vec::new());
+        image.write_to(&mut output, ImageOutputFormat::Png).unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn limits() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let config = LocalResizeImageConfig::builder()
+            .max_width(32)
+            .max_height(32)
+            .build();
+        let resizer = LocalResizeImage::new(config);
+
+        let resize = |content: Vec<u8>| {
+            runtime.block_on(resizer.resize_square(content.into(), ImageFormat::Png, 8))
+        };
+        let resized = resize(png(32, 16)).unwrap();
+        assert_eq!(ImageFormat::detect(&resized), Some(ImageFormat::Png));
+        assert!(resize(png(64, 16)).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{
+    model::{Role, User, UserData, UserFilters, UserId, UserPatch},
+    repository::{
+        memory::{MemoryUserDatabase, MemoryUserDatabaseError, MemoryUsers},
+        UserConflict, UserDatabase, UserDatabaseError,
+    },
+};
+use futures::{stream::MapErr, TryStreamExt};
+use tokio::{fs, sync::Mutex};
+
+use crate::model::{LocalUser, LocalUserDataError, LocalUserIdError};
+
+/// Database of user data which is kept in memory and persisted into a JSON file.
+///
+/// Used to run the user service without MongoDB.
+/// The whole file is rewritten after each change of the users:
+/// changed users are written into a temporary file which then replaces the old one,
+/// and users in memory are changed only after the file was replaced successfully.
+#[derive(Debug, Clone)]
+pub struct LocalJsonUserDatabase {
+    inner: Arc<RwLock<MemoryUserDatabase>>,
+    path: Arc<PathBuf>,
+    write: Arc<Mutex<()>>,
+}
+
+impl LocalJsonUserDatabase {
+    /// Opens user database persisted into a JSON file by provided path.
+    ///
+    /// File is created on the first change of the users if it does not exist.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, LocalJsonError> {
+        let path = path.into();
+        let users = match fs::read(&path).await {
+            Ok(content) => {
+                let users: Vec<LocalUser> = serde_json::from_slice(&content)?;
+                users
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<User>, _>>()?
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let inner = MemoryUserDatabase::with_users(users)?;
+        let database = Self {
+            inner: Arc::new(RwLock::new(inner)),
+            path: Arc::new(path),
+            write: Arc::default(),
+        };
+        Ok(database)
+    }
+
+    /// Returns users in memory which were persisted last.
+    fn current(&self) -> MemoryUserDatabase {
+        let Self { inner, .. } = self;
+        inner.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Applies provided change to the copy of the users and persists it,
+    /// then replaces users in memory with the changed copy.
+    ///
+    /// Users in memory are left untouched if the change fails or cannot be persisted.
+    async fn change<T, Change, Output>(&self, change: Change) -> Result<T, LocalJsonError>
+    where
+        Change: FnOnce(MemoryUserDatabase) -> Output,
+        Output: Future<Output = Result<T, MemoryUserDatabaseError>>,
+    {
+        let Self { inner, path, write } = self;
+        let _write = write.lock().await;
+
+        let changed = MemoryUserDatabase::with_users(self.current().users())?;
+        let output = change(changed.clone()).await?;
+        save(path, changed.users()).await?;
+        *inner.write().unwrap_or_else(PoisonError::into_inner) = changed;
+        Ok(output)
+    }
+}
+
+/// Atomically replaces content of the file by provided path with provided users.
+async fn save(path: &Path, users: Vec<User>) -> Result<(), LocalJsonError> {
+    let users = users
+        .into_iter()
+        .map(LocalUser::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    let content = serde_json::to_vec_pretty(&users)?;
+
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content).await?;
+    fs::rename(&temp_path, path).await?;
+    Ok(())
+}
+
+#[async_trait(?Send)]
+impl UserDatabase for LocalJsonUserDatabase {
+    type Error = LocalJsonError;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        self.change(|users| async move { users.create(id, data).await })
+            .await
+    }
+
+    type Users = LocalJsonUsers;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let users = self.current().read(filter).await?;
+        Ok(users.map_err(Into::into))
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        let count = self.current().count(filter).await?;
+        Ok(count)
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        let counts = self.current().count_by_role(filter).await?;
+        Ok(counts)
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        self.change(|users| async move { users.update(id, data).await })
+            .await
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        self.change(|users| async move { users.patch(id, patch).await })
+            .await
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        self.change(|users| async move { users.delete(id).await })
+            .await
+    }
+}
+
+/// Stream of filtered user data from JSON file user database.
+pub type LocalJsonUsers = MapErr<MemoryUsers, fn(MemoryUserDatabaseError) -> LocalJsonError>;
+
+/// Type of error which is returned on JSON file user database failure.
+#[derive(Debug, Display, From, Error)]
+#[from(forward)]
+pub struct LocalJsonError {
+    kind: LocalJsonErrorKind,
+}
+
+impl UserDatabaseError for LocalJsonError {
+    fn conflict(&self) -> Option<UserConflict> {
+        match &self.kind {
+            LocalJsonErrorKind::Memory(error) => error.conflict(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Display, From, Error)]
+enum LocalJsonErrorKind {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Id(LocalUserIdError),
+    UserData(LocalUserDataError),
+    Memory(MemoryUserDatabaseError),
+}
@@ -0,0 +1,94 @@
+use std::{io, path::PathBuf};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{model::Mail, repository::Mailer};
+use rand::{rngs::OsRng, RngCore};
+
+/// Implementation of mailer which writes mail into the outbox directory
+/// of the local file system.
+///
+/// Each mail is written as a separate message file in RFC 5322 format,
+/// which is expected to be delivered by some mail transfer agent watching the outbox.
+/// Message file is written under temporary name and renamed when complete,
+/// so the agent never picks up partially written mail.
+#[derive(Debug, Clone)]
+pub struct LocalOutboxMailer {
+    outbox: PathBuf,
+    sender: String,
+}
+
+impl LocalOutboxMailer {
+    /// Creates new local outbox mailer with provided outbox directory
+    /// and address of the sender of the mail.
+    pub fn new(outbox: impl Into<PathBuf>, sender: impl Into<String>) -> Self {
+        let outbox = outbox.into();
+        let sender = sender.into();
+        Self { outbox, sender }
+    }
+
+    fn message(&self, mail: &Mail) -> String {
+        let Self { sender, .. } = self;
+        let (subject, body) = match mail {
+            Mail::PasswordReset {
+                name,
+                token,
+                expires_at,
+                ..
+            } => {
+                let body = format!(
+                    "Hello, {name}!\r\n\
+                     \r\n\
+                     Somebody requested to reset the password of your account.\r\n\
+                     Use the following token to set a new password: {token}\r\n\
+                     \r\n\
+                     The token expires at {expires_at}.\r\n\
+                     If you did not request it, just ignore this mail.\r\n",
+                    name = name.as_str(),
+                    token = token.as_str(),
+                    expires_at = expires_at.to_rfc2822(),
+                );
+                ("Password reset", body)
+            }
+        };
+        format!(
+            "From: {sender}\r\n\
+             To: {recipient}\r\n\
+             Subject: {subject}\r\n\
+             Date: {date}\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\
+             \r\n\
+             {body}",
+            recipient = mail.recipient().as_str(),
+            date = Utc::now().to_rfc2822(),
+        )
+    }
+}
+
+#[async_trait(?Send)]
+impl Mailer for LocalOutboxMailer {
+    type Error = LocalMailerError;
+
+    async fn send(&self, mail: Mail) -> Result<(), Self::Error> {
+        let Self { outbox, .. } = self;
+        let message = self.message(&mail);
+
+        let mut bytes = [0; 16];
+        OsRng.fill_bytes(&mut bytes);
+        let name = hex::encode(bytes);
+        tokio::fs::create_dir_all(outbox).await?;
+        let temporary = outbox.join(format!(".{name}.tmp"));
+        tokio::fs::write(&temporary, message).await?;
+        tokio::fs::rename(temporary, outbox.join(format!("{name}.eml"))).await?;
+        Ok(())
+    }
+}
+
+/// Type of error which is returned when local outbox mailer fails.
+#[derive(Debug, Display, From, Error)]
+pub enum LocalMailerError {
+    /// Mail cannot be written into the outbox.
+    #[display(fmt = "failed to write mail into the outbox: {}", _0)]
+    Io(io::Error),
+}
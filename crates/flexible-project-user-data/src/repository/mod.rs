@@ -1,10 +1,37 @@
 //! Implementation of local user repository.
 
 pub use self::{
+    any::{LocalAnyError, LocalAnyUserDatabase, LocalAnyUsers},
+    clock::LocalClock,
+    credentials::LocalCredentialsDatabase,
+    delay::LocalDelay,
     id::LocalGenerateUserId,
+    image::{LocalResizeImage, LocalResizeImageConfig, LocalResizeImageError},
+    json::{LocalJsonError, LocalJsonUserDatabase, LocalJsonUsers},
+    mailer::{LocalMailerError, LocalOutboxMailer},
+    name_history::LocalNameHistoryDatabase,
+    object_store::{LocalObjectStore, LocalObjectStoreError},
+    password::{LocalHashPassword, LocalHashPasswordError},
+    password_reset::{LocalGeneratePasswordResetToken, LocalPasswordResetDatabase},
+    record::{LocalUserRecordError, LocalUserRecordFormat, LocalUserRecords},
     user::{LocalError, LocalUserDatabase, LocalUsers},
 };
 
+mod any;
+mod clock;
+#[cfg(test)]
+mod conformance;
+mod credentials;
+mod delay;
 mod filter;
 mod id;
+mod image;
+mod json;
+mod mailer;
+mod name_history;
+mod object_store;
+mod password;
+mod password_reset;
+mod patch;
+mod record;
 mod user;
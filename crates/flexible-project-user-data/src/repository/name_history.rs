@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use fp_user_domain::{
+    model::{Name, NameChange, UserId},
+    repository::NameHistoryDatabase,
+};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, to_bson, DateTime as BsonDateTime},
+    options::{FindOneOptions, FindOptions},
+    results::DeleteResult,
+    Collection, IndexModel,
+};
+
+use crate::{
+    client::Client,
+    model::{LocalNameChange, LocalUserId},
+};
+
+use super::user::LocalError;
+
+/// Local database of user name changes.
+#[derive(Debug, Clone)]
+pub struct LocalNameHistoryDatabase {
+    collection: Collection<LocalNameChange>,
+}
+
+impl LocalNameHistoryDatabase {
+    /// Creates new local name history repository instance.
+    pub async fn new(client: Client) -> Result<Self, LocalError> {
+        let database = client.database();
+        let collection = database.collection(&client.config.name_history_collection);
+
+        let name_index = IndexModel::builder()
+            .keys(doc! { "name_canonical": 1, "changed_at": -1 })
+            .build();
+        let user_id_index = IndexModel::builder()
+            .keys(doc! { "user_id": 1, "changed_at": -1 })
+            .build();
+        collection
+            .create_indexes([name_index, user_id_index], None)
+            .await?;
+
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait(?Send)]
+impl NameHistoryDatabase for LocalNameHistoryDatabase {
+    type Error = LocalError;
+
+    async fn create(&self, change: NameChange) -> Result<NameChange, Self::Error> {
+        let Self { collection } = self;
+        let change = LocalNameChange::try_from(change)?;
+        collection.insert_one(&change, None).await?;
+        let change = change.try_into()?;
+        Ok(change)
+    }
+
+    async fn find_latest_by_name(&self, name: &Name) -> Result<Option<NameChange>, Self::Error> {
+        let Self { collection } = self;
+
+        let filter = doc! { "name_canonical": name.canonical() };
+        let options = FindOneOptions::builder()
+            .sort(doc! { "changed_at": -1 })
+            .build();
+        let change = collection.find_one(filter, options).await?;
+        let change = change.map(TryInto::try_into).transpose()?;
+        Ok(change)
+    }
+
+    async fn read_by_user(&self, user_id: UserId) -> Result<Vec<NameChange>, Self::Error> {
+        let Self { collection } = self;
+        let user_id = LocalUserId::try_from(user_id)?;
+
+        let filter = doc! { "user_id": to_bson(&user_id)? };
+        let options = FindOptions::builder()
+            .sort(doc! { "changed_at": -1 })
+            .build();
+        let changes: Vec<_> = collection
+            .find(filter, options)
+            .await?
+            .try_collect()
+            .await?;
+        let changes = changes
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        Ok(changes)
+    }
+
+    async fn delete(&self, change: NameChange) -> Result<bool, Self::Error> {
+        let Self { collection } = self;
+        let NameChange {
+            user_id,
+            name,
+            changed_at,
+        } = change;
+        let user_id = LocalUserId::try_from(user_id)?;
+
+        let filter = doc! {
+            "user_id": to_bson(&user_id)?,
+            "name": name.into_inner(),
+            "changed_at": BsonDateTime::from_chrono(changed_at),
+        };
+        let DeleteResult { deleted_count, .. } = collection.delete_one(filter, None).await?;
+        Ok(deleted_count > 0)
+    }
+
+    async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error> {
+        let Self { collection } = self;
+        let user_id = LocalUserId::try_from(user_id)?;
+
+        let filter = doc! { "user_id": to_bson(&user_id)? };
+        let DeleteResult { deleted_count, .. } = collection.delete_many(filter, None).await?;
+        Ok(deleted_count)
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::repository::ObjectStore;
+use url::{ParseError, Url};
+
+/// Implementation of object store which keeps objects in the local file system.
+///
+/// Objects are expected to be served by some static file server
+/// from the root directory of the store by the base URL.
+#[derive(Debug, Clone)]
+pub struct LocalObjectStore {
+    root: PathBuf,
+    base_url: Url,
+}
+
+impl LocalObjectStore {
+    /// Creates new local object store with provided root directory and base URL.
+    pub fn new(root: impl Into<PathBuf>, base_url: Url) -> Self {
+        let root = root.into();
+        Self { root, base_url }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, LocalObjectStoreError> {
+        let Self { root, .. } = self;
+        let key_path = Path::new(key);
+        let is_normal = key_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if key.is_empty() || !is_normal {
+            return Err(LocalObjectStoreError::InvalidKey);
+        }
+        Ok(root.join(key_path))
+    }
+
+    fn url(&self, key: &str) -> Result<Url, LocalObjectStoreError> {
+        let Self { base_url, .. } = self;
+        let mut base_url = base_url.clone();
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        let url = base_url.join(key)?;
+        Ok(url)
+    }
+}
+
+#[async_trait(?Send)]
+impl ObjectStore for LocalObjectStore {
+    type Error = LocalObjectStoreError;
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<Url, Self::Error> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, content).await?;
+        self.url(key)
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, Self::Error> {
+        let Self { root, .. } = self;
+        // only the deepest directory which contains every matching key is traversed
+        let directory = match prefix.rfind('/') {
+            Some(end) => self.path(&prefix[..end])?,
+            None => root.clone(),
+        };
+
+        let mut keys = Vec::new();
+        let mut directories = vec![directory];
+        while let Some(directory) = directories.pop() {
+            let mut entries = match tokio::fs::read_dir(&directory).await {
+                Ok(entries) => entries,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(root) else {
+                    continue;
+                };
+                let key: Vec<_> = relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect();
+                let key = key.join("/");
+                if key.starts_with(prefix) {
+                    keys.push(key);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+/// Type of error which is returned when local object store fails.
+#[derive(Debug, Display, From, Error)]
+pub enum LocalObjectStoreError {
+    /// Key of the object cannot be used as a relative path in the store.
+    #[display(fmt = "object key is invalid")]
+    InvalidKey,
+    /// URL of the object cannot be built.
+    #[display(fmt = "object URL is invalid: {}", _0)]
+    Url(ParseError),
+    /// File system error.
+    #[display(fmt = "file system error: {}", _0)]
+    Io(io::Error),
+}
@@ -0,0 +1,45 @@
+use argon2::{
+    password_hash::{Error, PasswordHash as ArgonPasswordHash, SaltString},
+    Argon2, PasswordHasher, PasswordVerifier,
+};
+use derive_more::{Display, Error, From};
+use fp_user_domain::{
+    model::{Password, PasswordHash},
+    repository::HashPassword,
+};
+use rand::rngs::OsRng;
+
+/// Implementation of user password hasher which uses Argon2 algorithm.
+#[derive(Debug, Default, Clone)]
+pub struct LocalHashPassword {
+    argon2: Argon2<'static>,
+}
+
+impl HashPassword for LocalHashPassword {
+    type Error = LocalHashPasswordError;
+
+    fn hash_password(&self, password: &Password) -> Result<PasswordHash, Self::Error> {
+        let Self { argon2 } = self;
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = argon2.hash_password(password.as_str().as_bytes(), &salt)?;
+        Ok(PasswordHash::new(hash.to_string()))
+    }
+
+    fn verify_password(
+        &self,
+        password: &Password,
+        hash: &PasswordHash,
+    ) -> Result<bool, Self::Error> {
+        let Self { argon2 } = self;
+        let hash = ArgonPasswordHash::new(hash.as_str())?;
+        match argon2.verify_password(password.as_str().as_bytes(), &hash) {
+            Ok(()) => Ok(true),
+            Err(Error::Password) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+/// Type of error which is returned when password hashing fails.
+#[derive(Debug, Display, Clone, From, Error)]
+pub struct LocalHashPasswordError(#[error(not(source))] Error);
@@ -0,0 +1,147 @@
+use std::{convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use fp_user_domain::{
+    model::{PasswordReset, PasswordResetToken, PasswordResetTokenHash, UserId},
+    repository::{GeneratePasswordResetToken, PasswordResetDatabase},
+};
+use mongodb::{
+    bson::{doc, to_bson, DateTime as BsonDateTime},
+    error::ErrorKind,
+    options::IndexOptions,
+    results::DeleteResult,
+    Collection, IndexModel,
+};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    client::Client,
+    model::{LocalPasswordReset, LocalUserId},
+};
+
+use super::user::LocalError;
+
+const INDEX_OPTIONS_CONFLICT_CODE: i32 = 85;
+
+/// Local database of pending password resets.
+#[derive(Debug, Clone)]
+pub struct LocalPasswordResetDatabase {
+    collection: Collection<LocalPasswordReset>,
+}
+
+impl LocalPasswordResetDatabase {
+    /// Creates new local password reset repository instance.
+    ///
+    /// Password resets are kept for provided retention after their creation,
+    /// even if they were already taken, so they are counted by the rate limit of requests.
+    /// Retention must not be less than both token lifetime and rate limit window.
+    pub async fn new(client: Client, retention: Duration) -> Result<Self, LocalError> {
+        let database = client.database();
+        let collection_name = &client.config.password_reset_collection;
+        let collection = database.collection(collection_name);
+
+        let user_id_index = IndexModel::builder()
+            .keys(doc! { "user_id": 1, "created_at": 1 })
+            .build();
+        let retention_index = {
+            let options = IndexOptions::builder().expire_after(retention).build();
+            IndexModel::builder()
+                .keys(doc! { "created_at": 1 })
+                .options(options)
+                .build()
+        };
+        collection.create_index(user_id_index, None).await?;
+        if let Err(error) = collection.create_index(retention_index, None).await {
+            let ErrorKind::Command(command) = error.kind.as_ref() else {
+                return Err(error.into());
+            };
+            if command.code != INDEX_OPTIONS_CONFLICT_CODE {
+                return Err(error.into());
+            }
+            // retention was changed since the index was created
+            let command = doc! {
+                "collMod": collection_name,
+                "index": {
+                    "keyPattern": { "created_at": 1 },
+                    "expireAfterSeconds": retention.as_secs() as i64,
+                },
+            };
+            database.run_command(command, None).await?;
+        }
+
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait(?Send)]
+impl PasswordResetDatabase for LocalPasswordResetDatabase {
+    type Error = LocalError;
+
+    async fn create(&self, reset: PasswordReset) -> Result<PasswordReset, Self::Error> {
+        let Self { collection } = self;
+        let reset = LocalPasswordReset::try_from(reset)?;
+        collection.insert_one(&reset, None).await?;
+        Ok(reset.into())
+    }
+
+    async fn take_by_token_hash(
+        &self,
+        token_hash: &PasswordResetTokenHash,
+    ) -> Result<Option<PasswordReset>, Self::Error> {
+        let Self { collection } = self;
+
+        let filter = doc! { "_id": token_hash.as_str(), "taken": { "$ne": true } };
+        let update = doc! { "$set": { "taken": true } };
+        let reset = collection.find_one_and_update(filter, update, None).await?;
+        Ok(reset.map(Into::into))
+    }
+
+    async fn count_created_since(
+        &self,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<u64, Self::Error> {
+        let Self { collection } = self;
+        let user_id = LocalUserId::try_from(user_id)?;
+
+        let filter = doc! {
+            "user_id": to_bson(&user_id)?,
+            "created_at": { "$gte": BsonDateTime::from_chrono(since) },
+        };
+        let count = collection.count_documents(filter, None).await?;
+        Ok(count)
+    }
+
+    async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error> {
+        let Self { collection } = self;
+        let user_id = LocalUserId::try_from(user_id)?;
+
+        let filter = doc! { "user_id": to_bson(&user_id)? };
+        let DeleteResult { deleted_count, .. } = collection.delete_many(filter, None).await?;
+        Ok(deleted_count)
+    }
+}
+
+/// Implementation of password reset token generator.
+///
+/// Tokens are random 256-bit values, their hashes are computed with SHA-256.
+#[derive(Debug, Default, Clone)]
+pub struct LocalGeneratePasswordResetToken;
+
+impl GeneratePasswordResetToken for LocalGeneratePasswordResetToken {
+    type Error = Infallible;
+
+    fn generate_token(&self) -> Result<PasswordResetToken, Self::Error> {
+        let mut bytes = [0; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = PasswordResetToken::new(hex::encode(bytes));
+        Ok(token)
+    }
+
+    fn hash_token(&self, token: &PasswordResetToken) -> PasswordResetTokenHash {
+        let hash = Sha256::digest(token.as_str());
+        PasswordResetTokenHash::new(hex::encode(hash))
+    }
+}
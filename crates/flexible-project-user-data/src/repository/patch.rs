@@ -0,0 +1,86 @@
+use fp_user_domain::model::{
+    Avatar, Bio, DisplayName, Email, Locale, Name, Pronouns, TimeZone, UserPatch,
+};
+use mongodb::bson::{to_bson, Document};
+
+use crate::model::{LocalAccountStatus, LocalRole};
+
+use super::{filter::IntoDocument, user::LocalError};
+
+impl IntoDocument for UserPatch {
+    /// Translates the patch into the update document with `$set` and `$unset` operators.
+    ///
+    /// Returns an empty document if the patch does not change any field.
+    fn into_document(self) -> Result<Document, LocalError> {
+        let Self {
+            name,
+            display_name,
+            role,
+            email,
+            avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = self;
+
+        let mut set = Document::new();
+        let mut unset = Document::new();
+        let mut optional = |field: &str, value: Option<Option<String>>| match value {
+            Some(Some(value)) => {
+                set.insert(format!("data.{field}"), value);
+            }
+            Some(None) => {
+                unset.insert(format!("data.{field}"), "");
+            }
+            None => {}
+        };
+        if let Some(email) = email {
+            let canonical = email.as_ref().map(Email::canonical);
+            optional("email", Some(email.map(Email::into_inner)));
+            optional("email_canonical", Some(canonical));
+        }
+        optional(
+            "avatar",
+            avatar.map(|avatar| avatar.map(Avatar::into_inner)),
+        );
+        optional("bio", bio.map(|bio| bio.map(Bio::into_inner)));
+        optional(
+            "locale",
+            locale.map(|locale| locale.map(Locale::into_inner)),
+        );
+        optional(
+            "time_zone",
+            time_zone.map(|time_zone| time_zone.map(TimeZone::into_inner)),
+        );
+        optional(
+            "pronouns",
+            pronouns.map(|pronouns| pronouns.map(Pronouns::into_inner)),
+        );
+
+        if let Some(name) = name {
+            set.insert("data.name_canonical", name.canonical());
+            set.insert("data.name", Name::into_inner(name));
+        }
+        if let Some(display_name) = display_name {
+            set.insert("data.display_name", DisplayName::into_inner(display_name));
+        }
+        if let Some(role) = role {
+            set.insert("data.role", to_bson(&LocalRole::from(role))?);
+        }
+        if let Some(status) = status {
+            let status = LocalAccountStatus::from(status);
+            set.insert("data.status", to_bson(&status)?);
+        }
+
+        let mut document = Document::new();
+        if !set.is_empty() {
+            document.insert("$set", set);
+        }
+        if !unset.is_empty() {
+            document.insert("$unset", unset);
+        }
+        Ok(document)
+    }
+}
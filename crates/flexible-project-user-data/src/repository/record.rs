@@ -0,0 +1,111 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Cursor, Lines},
+};
+
+use csv::{DeserializeRecordsIntoIter, ReaderBuilder, Trim, WriterBuilder};
+use derive_more::{Display, Error, From};
+use fp_user_domain::{model::UserRecord, repository::UserRecordFormat};
+
+use crate::model::LocalUserRecord;
+
+/// Implementation of user record format which supports JSON Lines and CSV.
+///
+/// Blank lines of JSON Lines content are skipped.
+/// CSV content must have a header with names of the record fields.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum LocalUserRecordFormat {
+    /// Each record is a JSON object on its own line.
+    JsonLines,
+    /// Each record is a row of comma separated values.
+    Csv,
+}
+
+impl UserRecordFormat for LocalUserRecordFormat {
+    type Error = LocalUserRecordError;
+
+    type Records = LocalUserRecords;
+
+    fn decode(&self, content: Vec<u8>) -> Self::Records {
+        let content = Cursor::new(content);
+        let kind = match self {
+            Self::JsonLines => LocalUserRecordsKind::JsonLines(content.lines()),
+            Self::Csv => {
+                let reader = ReaderBuilder::new().trim(Trim::All).from_reader(content);
+                LocalUserRecordsKind::Csv(reader.into_deserialize())
+            }
+        };
+        LocalUserRecords { kind }
+    }
+
+    fn encode(&self, record: &UserRecord, output: &mut Vec<u8>) -> Result<(), Self::Error> {
+        let record = LocalUserRecord::from(record.clone());
+        match self {
+            Self::JsonLines => {
+                serde_json::to_writer(&mut *output, &record)?;
+                output.push(b'\n');
+            }
+            Self::Csv => {
+                let mut writer = WriterBuilder::new()
+                    .has_headers(output.is_empty())
+                    .from_writer(output);
+                writer.serialize(&record)?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over user records decoded from the content.
+pub struct LocalUserRecords {
+    kind: LocalUserRecordsKind,
+}
+
+impl fmt::Debug for LocalUserRecords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self.kind {
+            LocalUserRecordsKind::JsonLines(_) => LocalUserRecordFormat::JsonLines,
+            LocalUserRecordsKind::Csv(_) => LocalUserRecordFormat::Csv,
+        };
+        f.debug_struct("LocalUserRecords")
+            .field("format", &format)
+            .finish_non_exhaustive()
+    }
+}
+
+enum LocalUserRecordsKind {
+    JsonLines(Lines<Cursor<Vec<u8>>>),
+    Csv(DeserializeRecordsIntoIter<Cursor<Vec<u8>>, LocalUserRecord>),
+}
+
+impl Iterator for LocalUserRecords {
+    type Item = Result<UserRecord, LocalUserRecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match &mut self.kind {
+            LocalUserRecordsKind::JsonLines(lines) => {
+                let line =
+                    lines.find(|line| !matches!(line, Ok(line) if line.trim().is_empty()))?;
+                line.map_err(Into::into)
+                    .and_then(|line| serde_json::from_str(&line).map_err(Into::into))
+            }
+            LocalUserRecordsKind::Csv(records) => records.next()?.map_err(Into::into),
+        };
+        Some(record.map(LocalUserRecord::into))
+    }
+}
+
+/// Type of error which is returned when user record cannot be decoded or encoded.
+#[derive(Debug, Display, From, Error)]
+#[from(forward)]
+pub struct LocalUserRecordError {
+    kind: LocalUserRecordErrorKind,
+}
+
+#[derive(Debug, Display, From, Error)]
+enum LocalUserRecordErrorKind {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Csv(csv::Error),
+}
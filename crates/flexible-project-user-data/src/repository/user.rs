@@ -1,27 +1,40 @@
-use std::pin::pin;
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use async_trait::async_trait;
 use derive_more::{Display, Error, From};
 use fp_user_domain::{
-    model::{User, UserData, UserFilters, UserId},
-    repository::UserDatabase,
+    model::{Role, User, UserData, UserFilters, UserId, UserPatch},
+    repository::{UserConflict, UserDatabase, UserDatabaseError},
 };
-use futures::Stream;
+use futures::{Stream, StreamExt, TryStreamExt};
 use mongodb::{
-    bson::{doc, ser, to_bson},
-    error::Error,
-    options::{FindOneAndUpdateOptions, IndexOptions, ReturnDocument},
+    bson::{de, doc, from_document, ser, to_bson},
+    error::{Error, ErrorKind, WriteFailure, RETRYABLE_WRITE_ERROR, TRANSIENT_TRANSACTION_ERROR},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
     results::InsertOneResult,
-    Collection, Cursor, IndexModel,
+    Collection, Cursor,
 };
 
 use crate::{
     client::Client,
-    model::{LocalUser, LocalUserData, LocalUserDataError, LocalUserId, LocalUserIdError},
+    migration::{EMAIL_INDEX, NAME_INDEX},
+    model::{
+        LocalRoleCount, LocalUser, LocalUserData, LocalUserDataError, LocalUserId, LocalUserIdError,
+    },
 };
 
 use super::filter::IntoDocument;
 
+const DUPLICATE_KEY_CODE: i32 = 11000;
+/// Codes of server errors which are caused by primary election, shutdown or network failure.
+const TRANSIENT_CODES: [i32; 12] = [
+    6, 7, 89, 91, 189, 262, 9001, 10107, 11600, 11602, 13435, 13436,
+];
+
 /// Local database of user data.
 #[derive(Debug, Clone)]
 pub struct LocalUserDatabase {
@@ -30,43 +43,53 @@ pub struct LocalUserDatabase {
 
 impl LocalUserDatabase {
     /// Creates new local user repository instance.
+    ///
+    /// Unique indexes of the user collection are created by [migrations](crate::migration),
+    /// which must be applied before the repository is used.
     pub async fn new(client: Client) -> Result<Self, LocalError> {
-        let database = client.inner.database("flexible-project-user");
-        let collection = database.collection("user");
-
-        let name_index = {
-            let options = IndexOptions::builder().unique(true).build();
-            IndexModel::builder()
-                .keys(doc! { "name": 1 })
-                .options(options)
-                .build()
-        };
-        let email_index = {
-            let options = IndexOptions::builder()
-                .unique(true)
-                .partial_filter_expression(doc! { "email": { "$exists": true, "$type": "string" } })
-                .build();
-            IndexModel::builder()
-                .keys(doc! { "email": 1 })
-                .options(options)
-                .build()
-        };
-        collection
-            .create_indexes([name_index, email_index], None)
-            .await?;
+        let database = client.database();
+        let collection = database.collection(&client.config.user_collection);
 
         Ok(Self { collection })
     }
 }
 
+/// Maps duplicate key error of the write onto user uniqueness conflict.
+///
+/// MongoDB driver does not expose key pattern and key value of the violated unique index,
+/// so the conflict is found by the name of the index reported in the error message,
+/// e.g. `E11000 duplicate key error collection: fp.users index: unique_name_canonical dup key: ...`.
+fn conflict(error: Error) -> LocalError {
+    let message = match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(error)) if error.code == DUPLICATE_KEY_CODE => {
+            &error.message
+        }
+        ErrorKind::Command(error) if error.code == DUPLICATE_KEY_CODE => &error.message,
+        _ => return error.into(),
+    };
+    let index = message
+        .split_once("index: ")
+        .and_then(|(_, rest)| rest.split_whitespace().next());
+    let conflict = match index {
+        Some(NAME_INDEX) => Some(UserConflict::Name),
+        Some(EMAIL_INDEX) => Some(UserConflict::Email),
+        _ => None,
+    };
+    match conflict {
+        Some(conflict) => LocalErrorKind::Conflict(conflict).into(),
+        None => error.into(),
+    }
+}
+
 #[async_trait(?Send)]
 impl UserDatabase for LocalUserDatabase {
     type Error = LocalError;
 
     async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
         let Self { collection } = self;
-        let user = User { id, data }.try_into()?;
-        let InsertOneResult { inserted_id, .. } = collection.insert_one(&user, None).await?;
+        let user: LocalUser = User { id, data }.try_into()?;
+        let InsertOneResult { inserted_id, .. } =
+            collection.insert_one(&user, None).await.map_err(conflict)?;
 
         let filter = doc! { "_id": inserted_id };
         let user = collection
@@ -87,35 +110,77 @@ impl UserDatabase for LocalUserDatabase {
         Ok(users)
     }
 
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        let Self { collection } = self;
+        let filter = filter.into_document()?;
+        let count = collection.count_documents(filter, None).await?;
+        Ok(count)
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        let Self { collection } = self;
+        let filter = filter.into_document()?;
+        let pipeline = [
+            doc! { "$match": filter },
+            doc! { "$group": { "_id": "$data.role", "count": { "$sum": 1 } } },
+        ];
+        let counts: Vec<_> = collection
+            .aggregate(pipeline, None)
+            .await?
+            .try_collect()
+            .await?;
+        let counts = counts
+            .into_iter()
+            .map(|count| {
+                let LocalRoleCount { role, count } = from_document(count)?;
+                Ok((role.into(), count))
+            })
+            .collect::<Result<_, LocalError>>()?;
+        Ok(counts)
+    }
+
     async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
         let Self { collection } = self;
         let id = LocalUserId::try_from(id)?;
-        let LocalUserData {
-            name,
-            display_name,
-            role,
-            email,
-            avatar,
-        } = data.into();
+        let data = LocalUserData::from(data);
 
         let filter = doc! { "_id": to_bson(&id)? };
-        let update = doc! {
-            "name": name,
-            "display_name": display_name,
-            "role": to_bson(&role)?,
-            "email": email,
-            "avatar": avatar,
-        };
+        let update = doc! { "$set": { "data": to_bson(&data)? } };
         let options = FindOneAndUpdateOptions::builder()
             .return_document(ReturnDocument::After)
             .build();
         let user = collection
             .find_one_and_update(filter, update, options)
-            .await?;
+            .await
+            .map_err(conflict)?;
         let user = user.ok_or(LocalErrorKind::NoUser)?.try_into()?;
         Ok(user)
     }
 
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let Self { collection } = self;
+        let id = LocalUserId::try_from(id)?;
+
+        let filter = doc! { "_id": to_bson(&id)? };
+        let update = patch.into_document()?;
+        let user = if update.is_empty() {
+            collection.find_one(filter, None).await?
+        } else {
+            let options = FindOneAndUpdateOptions::builder()
+                .return_document(ReturnDocument::After)
+                .build();
+            collection
+                .find_one_and_update(filter, update, options)
+                .await
+                .map_err(conflict)?
+        };
+        let user = user.map(User::try_from).transpose()?;
+        Ok(user)
+    }
+
     async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
         let Self { collection } = self;
         let id = LocalUserId::try_from(id)?;
@@ -134,13 +199,43 @@ pub struct LocalError {
     kind: LocalErrorKind,
 }
 
+impl UserDatabaseError for LocalError {
+    fn conflict(&self) -> Option<UserConflict> {
+        match self.kind {
+            LocalErrorKind::Conflict(conflict) => Some(conflict),
+            _ => None,
+        }
+    }
+
+    fn is_transient(&self) -> bool {
+        let LocalErrorKind::Database(error) = &self.kind else {
+            return false;
+        };
+        if error.contains_label(RETRYABLE_WRITE_ERROR)
+            || error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+        {
+            return true;
+        }
+        match error.kind.as_ref() {
+            ErrorKind::Io(_)
+            | ErrorKind::ConnectionPoolCleared { .. }
+            | ErrorKind::ServerSelection { .. } => true,
+            ErrorKind::Command(error) => TRANSIENT_CODES.contains(&error.code),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Display, Clone, From, Error)]
 enum LocalErrorKind {
     #[display(fmt = "no user was found by provided identifier")]
     NoUser,
+    #[from(ignore)]
+    Conflict(#[error(not(source))] UserConflict),
     Id(LocalUserIdError),
     UserData(LocalUserDataError),
     ToBson(ser::Error),
+    FromBson(de::Error),
     Database(Error),
 }
 
@@ -153,10 +248,7 @@ pub struct LocalUsers {
 impl Stream for LocalUsers {
     type Item = Result<User, LocalError>;
 
-    fn poll_next(
-        mut self: std::pin::Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Option<Self::Item>> {
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         fn to_user(result: Result<LocalUser, Error>) -> Result<User, LocalError> {
             match result {
                 Ok(user) => user.try_into().map_err(Into::into),
@@ -164,7 +256,7 @@ impl Stream for LocalUsers {
             }
         }
 
-        let cursor = pin!(&mut self.cursor);
-        cursor.poll_next(cx).map(|user| user.map(to_user))
+        let Self { cursor } = &mut *self;
+        cursor.poll_next_unpin(cx).map(|user| user.map(to_user))
     }
 }
@@ -0,0 +1,255 @@
+use derive_more::Display;
+
+use super::{id::UserId, role::Role};
+
+/// Actor which performs an action on users of the system.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq)]
+pub enum Actor {
+    /// An authenticated user of the system.
+    #[display(fmt = r#"user "{}" with role "{}""#, id, role)]
+    User {
+        /// Identifier of the acting user.
+        id: UserId,
+        /// Role of the acting user.
+        role: Role,
+    },
+    /// The system itself (e.g. other backend service) which has no restrictions.
+    #[display(fmt = "system")]
+    System,
+}
+
+/// Action which could be performed by an [actor](Actor) on the user of the system.
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum UserAction {
+    /// Creation of new user.
+    #[display(fmt = "create user")]
+    Create,
+    /// Read of private data of the user: email, account status and role.
+    #[display(fmt = "read private data")]
+    Read,
+    /// Update of the user name.
+    #[display(fmt = "update name")]
+    UpdateName,
+    /// Update of the user display name.
+    #[display(fmt = "update display name")]
+    UpdateDisplayName,
+    /// Update of the user email.
+    #[display(fmt = "update email")]
+    UpdateEmail,
+    /// Update of the user avatar.
+    #[display(fmt = "update avatar")]
+    UpdateAvatar,
+    /// Update of the user profile: bio, locale, time zone and pronouns.
+    #[display(fmt = "update profile")]
+    UpdateProfile,
+    /// Change of the user role.
+    #[display(fmt = "change role")]
+    ChangeRole,
+    /// Deletion of the user.
+    #[display(fmt = "delete user")]
+    Delete,
+    /// Temporary suspension of the user account.
+    #[display(fmt = "suspend user")]
+    Suspend,
+    /// Removal of the user account suspension.
+    #[display(fmt = "unsuspend user")]
+    Unsuspend,
+    /// Permanent ban of the user account.
+    #[display(fmt = "ban user")]
+    Ban,
+    /// Export of all personal data of the user.
+    #[display(fmt = "export personal data")]
+    ExportPersonalData,
+    /// Erasure of all data of the user from the system.
+    #[display(fmt = "erase user")]
+    Erase,
+    /// Creation of many users at once.
+    #[display(fmt = "import users")]
+    Import,
+    /// Export of many users at once.
+    #[display(fmt = "export users")]
+    Export,
+}
+
+impl UserAction {
+    /// Checks if the action is a moderation action.
+    pub fn is_moderation(self) -> bool {
+        matches!(self, Self::Suspend | Self::Unsuspend | Self::Ban)
+    }
+
+    /// Checks if the action is performed on many users at once.
+    pub fn is_bulk(self) -> bool {
+        matches!(self, Self::Import | Self::Export)
+    }
+}
+
+impl Actor {
+    /// Checks if the actor is allowed to perform an action on the user by its identifier.
+    ///
+    /// Authorization policy is the following:
+    /// - the system and administrators are allowed to do everything;
+    /// - moderators are allowed to update display names, avatars and profiles of any user
+    ///   and to moderate (suspend, unsuspend or ban) any user except themselves;
+    /// - any user is allowed to read private data of itself, to update, delete, erase
+    ///   and export personal data of itself, but not to change its role;
+    /// - only the system and administrators are allowed to create new users
+    ///   and to perform [bulk](Actor::is_allowed_in_bulk) actions.
+    pub fn is_allowed(&self, action: UserAction, target: &UserId) -> bool {
+        let (id, role) = match self {
+            Self::System => return true,
+            Self::User { id, role } => (id, *role),
+        };
+        match (role, action) {
+            (Role::Administrator, _) => true,
+            (_, UserAction::Create | UserAction::ChangeRole) => false,
+            (_, action) if action.is_bulk() => false,
+            (
+                Role::Moderator,
+                UserAction::UpdateDisplayName
+                | UserAction::UpdateAvatar
+                | UserAction::UpdateProfile,
+            ) => true,
+            (Role::Moderator, action) if action.is_moderation() => id != target,
+            (_, action) if action.is_moderation() => false,
+            (_, _) => id == target,
+        }
+    }
+
+    /// Checks if the actor is allowed to create new users.
+    ///
+    /// Creation has no existing target, so identifier of the new user
+    /// is not needed to authorize the actor.
+    pub fn is_allowed_to_create(&self) -> bool {
+        match self {
+            Self::System => true,
+            Self::User { role, .. } => *role == Role::Administrator,
+        }
+    }
+
+    /// Checks if the actor is allowed to read private data of every user of the system,
+    /// so it could filter and count users by their email, account status and role.
+    ///
+    /// Only the system and administrators are allowed to read private data of every user.
+    pub fn is_allowed_to_read_all(&self) -> bool {
+        match self {
+            Self::System => true,
+            Self::User { role, .. } => *role == Role::Administrator,
+        }
+    }
+
+    /// Checks if the actor is allowed to perform a bulk action on many users at once.
+    ///
+    /// Only the system and administrators are allowed to perform bulk actions.
+    pub fn is_allowed_in_bulk(&self, action: UserAction) -> bool {
+        match self {
+            Self::System => action.is_bulk(),
+            Self::User { role, .. } => *role == Role::Administrator && action.is_bulk(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Actor, UserAction};
+    use crate::model::{Role, UserId};
+
+    fn actor(id: &str, role: Role) -> Actor {
+        let id = UserId::new(id);
+        Actor::User { id, role }
+    }
+
+    #[test]
+    fn system() {
+        let target = UserId::new("target");
+        assert!(Actor::System.is_allowed(UserAction::Create, &target));
+        assert!(Actor::System.is_allowed_to_create());
+        assert!(Actor::System.is_allowed(UserAction::ChangeRole, &target));
+        assert!(Actor::System.is_allowed(UserAction::Delete, &target));
+        assert!(Actor::System.is_allowed(UserAction::Read, &target));
+        assert!(Actor::System.is_allowed_to_read_all());
+    }
+
+    #[test]
+    fn administrator() {
+        let target = UserId::new("target");
+        let administrator = actor("admin", Role::Administrator);
+        assert!(administrator.is_allowed(UserAction::Create, &target));
+        assert!(administrator.is_allowed_to_create());
+        assert!(administrator.is_allowed(UserAction::UpdateEmail, &target));
+        assert!(administrator.is_allowed(UserAction::ChangeRole, &target));
+        assert!(administrator.is_allowed(UserAction::Delete, &target));
+        assert!(administrator.is_allowed(UserAction::Read, &target));
+        assert!(administrator.is_allowed_to_read_all());
+    }
+
+    #[test]
+    fn moderator() {
+        let target = UserId::new("target");
+        let moderator = actor("moderator", Role::Moderator);
+        assert!(moderator.is_allowed(UserAction::UpdateDisplayName, &target));
+        assert!(moderator.is_allowed(UserAction::UpdateAvatar, &target));
+        assert!(moderator.is_allowed(UserAction::UpdateProfile, &target));
+        assert!(!moderator.is_allowed(UserAction::UpdateName, &target));
+        assert!(!moderator.is_allowed(UserAction::UpdateEmail, &target));
+        assert!(!moderator.is_allowed(UserAction::Delete, &target));
+        assert!(!moderator.is_allowed(UserAction::Erase, &target));
+        assert!(!moderator.is_allowed(UserAction::ChangeRole, &target));
+        assert!(moderator.is_allowed(UserAction::Suspend, &target));
+        assert!(moderator.is_allowed(UserAction::Unsuspend, &target));
+        assert!(moderator.is_allowed(UserAction::Ban, &target));
+        assert!(!moderator.is_allowed_to_create());
+        assert!(!moderator.is_allowed(UserAction::Read, &target));
+        assert!(!moderator.is_allowed_to_read_all());
+
+        let id = UserId::new("moderator");
+        assert!(!moderator.is_allowed(UserAction::Suspend, &id));
+        assert!(!moderator.is_allowed(UserAction::Ban, &id));
+    }
+
+    #[test]
+    fn self_service() {
+        let id = UserId::new("user");
+        let user = actor("user", Role::User);
+        assert!(user.is_allowed(UserAction::Read, &id));
+        assert!(user.is_allowed(UserAction::UpdateName, &id));
+        assert!(user.is_allowed(UserAction::UpdateDisplayName, &id));
+        assert!(user.is_allowed(UserAction::UpdateEmail, &id));
+        assert!(user.is_allowed(UserAction::UpdateAvatar, &id));
+        assert!(user.is_allowed(UserAction::UpdateProfile, &id));
+        assert!(user.is_allowed(UserAction::Delete, &id));
+        assert!(user.is_allowed(UserAction::ExportPersonalData, &id));
+        assert!(user.is_allowed(UserAction::Erase, &id));
+        assert!(!user.is_allowed(UserAction::ChangeRole, &id));
+        assert!(!user.is_allowed(UserAction::Create, &id));
+        assert!(!user.is_allowed_to_create());
+        assert!(!user.is_allowed(UserAction::Suspend, &id));
+        assert!(!user.is_allowed(UserAction::Unsuspend, &id));
+        assert!(!user.is_allowed(UserAction::Ban, &id));
+    }
+
+    #[test]
+    fn other_user() {
+        let target = UserId::new("target");
+        let user = actor("user", Role::User);
+        assert!(!user.is_allowed(UserAction::Read, &target));
+        assert!(!user.is_allowed_to_read_all());
+        assert!(!user.is_allowed(UserAction::UpdateName, &target));
+        assert!(!user.is_allowed(UserAction::UpdateDisplayName, &target));
+        assert!(!user.is_allowed(UserAction::UpdateAvatar, &target));
+        assert!(!user.is_allowed(UserAction::UpdateProfile, &target));
+        assert!(!user.is_allowed(UserAction::Delete, &target));
+        assert!(!user.is_allowed(UserAction::ExportPersonalData, &target));
+        assert!(!user.is_allowed(UserAction::Erase, &target));
+    }
+
+    #[test]
+    fn bulk() {
+        let id = UserId::new("user");
+        assert!(Actor::System.is_allowed_in_bulk(UserAction::Import));
+        assert!(actor("admin", Role::Administrator).is_allowed_in_bulk(UserAction::Export));
+        assert!(!actor("admin", Role::Administrator).is_allowed_in_bulk(UserAction::Delete));
+        assert!(!actor("moderator", Role::Moderator).is_allowed_in_bulk(UserAction::Export));
+        assert!(!actor("user", Role::User).is_allowed_in_bulk(UserAction::Import));
+        assert!(!actor("user", Role::User).is_allowed(UserAction::Export, &id));
+    }
+}
@@ -1,9 +1,12 @@
-use std::borrow::{Borrow, Cow};
+use std::{
+    borrow::{Borrow, Cow},
+    net::{Ipv4Addr, Ipv6Addr},
+};
 
 use derive_more::{Display, Error};
 use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
 use typed_builder::TypedBuilder;
-use url::Url;
+use url::{Host, ParseError, Url};
 
 /// User avatar URL of the user in the system.
 #[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -12,15 +15,16 @@ pub struct Avatar(Url);
 impl Avatar {
     /// Creates new user avatar URL from input string.
     ///
+    /// Only the structure of the URL is checked, so avatars which were stored earlier
+    /// could be read even if they do not conform to the current [avatar policy](AvatarPolicy).
+    ///
     /// # Errors
     ///
     /// This function will return an error
     /// if input string does not match user avatar URL requirements.
     pub fn new(url: impl Into<String>) -> Result<Self, AvatarError> {
         let url = url.into();
-        let Ok(url) = url.parse() else {
-            return Err(AvatarError::Invalid);
-        };
+        let url = url.parse::<Url>().map_err(AvatarError::Invalid)?;
         Ok(Self(url))
     }
 
@@ -37,12 +41,141 @@ impl Avatar {
     }
 }
 
+/// Policy which user avatar URLs should conform to.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct AvatarPolicy {
+    /// Schemes which user avatar URL could have.
+    #[builder(default = vec!["https".to_string()])]
+    pub allowed_schemes: Vec<String>,
+    /// Maximal length of user avatar URL.
+    #[builder(default = 2048)]
+    pub max_length: usize,
+    /// Hosts which user avatar URL could point to, or `None` if any host is allowed.
+    #[builder(default, setter(strip_option))]
+    pub allowed_hosts: Option<Vec<String>>,
+    /// Whether user avatar URL could point to loopback, private or link-local hosts.
+    #[builder(default = false)]
+    pub allow_private_hosts: bool,
+}
+
+impl AvatarPolicy {
+    /// Checks if provided user avatar URL conforms to the policy.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if provided user avatar URL is not allowed by the policy.
+    pub fn check(&self, avatar: &Avatar) -> Result<(), AvatarError> {
+        let Self {
+            allowed_schemes,
+            max_length,
+            allowed_hosts,
+            allow_private_hosts,
+        } = self;
+        let Avatar(url) = avatar;
+
+        let length = url.as_str().len();
+        if length > *max_length {
+            let error = AvatarError::TooLong {
+                max: *max_length,
+                actual: length,
+            };
+            return Err(error);
+        }
+        let is_scheme_allowed = allowed_schemes
+            .iter()
+            .any(|scheme| scheme.eq_ignore_ascii_case(url.scheme()));
+        if !is_scheme_allowed {
+            return Err(AvatarError::SchemeNotAllowed);
+        }
+        let Some(host) = url.host() else {
+            return Err(AvatarError::NoHost);
+        };
+        if !allow_private_hosts && is_private_host(&host) {
+            return Err(AvatarError::PrivateHost);
+        }
+        if let Some(allowed_hosts) = allowed_hosts {
+            let host = host.to_string();
+            let is_host_allowed = allowed_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(&host));
+            if !is_host_allowed {
+                return Err(AvatarError::HostNotAllowed);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for AvatarPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+fn is_private_host(host: &Host<&str>) -> bool {
+    match *host {
+        Host::Domain(domain) => {
+            let domain = domain.trim_end_matches('.');
+            domain.eq_ignore_ascii_case("localhost")
+                || domain.to_ascii_lowercase().ends_with(".localhost")
+        }
+        Host::Ipv4(address) => is_private_ipv4(address),
+        Host::Ipv6(address) => is_private_ipv6(address),
+    }
+}
+
+fn is_private_ipv4(address: Ipv4Addr) -> bool {
+    let [first, second, ..] = address.octets();
+    let is_shared = first == 100 && (64..128).contains(&second);
+    address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_broadcast()
+        || is_shared
+}
+
+fn is_private_ipv6(address: Ipv6Addr) -> bool {
+    if let Some(address) = address.to_ipv4_mapped() {
+        return is_private_ipv4(address);
+    }
+    let [first, ..] = address.segments();
+    let is_unique_local = first & 0xfe00 == 0xfc00;
+    let is_link_local = first & 0xffc0 == 0xfe80;
+    address.is_loopback() || address.is_unspecified() || is_unique_local || is_link_local
+}
+
 /// Type of error which is returned when input does not meet user avatar URL requirements.
-#[derive(Debug, Display, Clone, Copy, Error)]
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum AvatarError {
-    /// User avatar URL does not meet requirements.
-    #[display(fmt = "user avatar URL does not meet requirements")]
-    Invalid,
+    /// User avatar URL cannot be parsed.
+    #[display(fmt = "user avatar URL is invalid: {}", _0)]
+    Invalid(ParseError),
+    /// User avatar URL is longer than allowed by the policy.
+    #[display(
+        fmt = "user avatar URL length {} exceeds maximal length {}",
+        actual,
+        max
+    )]
+    TooLong {
+        /// Maximal length of user avatar URL.
+        max: usize,
+        /// Actual length of user avatar URL.
+        actual: usize,
+    },
+    /// Scheme of user avatar URL is not allowed by the policy.
+    #[display(fmt = "user avatar URL scheme is not allowed")]
+    SchemeNotAllowed,
+    /// User avatar URL has no host.
+    #[display(fmt = "user avatar URL has no host")]
+    NoHost,
+    /// Host of user avatar URL is not allowed by the policy.
+    #[display(fmt = "user avatar URL host is not allowed")]
+    HostNotAllowed,
+    /// User avatar URL points to loopback, private or link-local host.
+    #[display(fmt = "user avatar URL points to private host")]
+    PrivateHost,
 }
 
 /// Filters for user avatar URL of the backend.
@@ -95,6 +228,8 @@ pub struct OptionAvatarFilters<'a> {
     /// Not in user avatar filter.
     pub nin: Option<NotIn<Cow<'a, [Option<Avatar>]>>>,
     /// Regex user avatar filter.
+    ///
+    /// Absent user avatar never matches the regex.
     pub regex: Option<Regex<Cow<'a, str>>>,
 }
 
@@ -115,22 +250,28 @@ where
             && ne.satisfies(Cow::Borrowed(input))
             && r#in.as_ref().map(In::as_deref).satisfies(input)
             && nin.as_ref().map(NotIn::as_deref).satisfies(input)
-            && input
-                .as_ref()
-                .map(|input| regex.satisfies(input.as_str()))
-                .unwrap_or(true)
+            && match input {
+                Some(input) => regex.satisfies(input.as_str()),
+                None => regex.is_none(),
+            }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Avatar, AvatarError};
+    use super::{Avatar, AvatarError, AvatarPolicy};
+
+    fn check(policy: &AvatarPolicy, url: &str) -> Result<(), AvatarError> {
+        let avatar = Avatar::new(url).unwrap();
+        policy.check(&avatar)
+    }
 
     #[test]
     fn valid_ones() {
         let Avatar(_) = Avatar::new("https://vk.com/im").unwrap();
         let Avatar(_) = Avatar::new("https://github.com/rust-lang/rust/issues").unwrap();
         let Avatar(_) = Avatar::new("https://docs.rs/url/latest/url/index.html").unwrap();
+        let Avatar(_) = Avatar::new("http://localhost/avatars/1/256.png").unwrap();
     }
 
     #[test]
@@ -138,4 +279,60 @@ mod test {
         let _: AvatarError = Avatar::new("http://[:::1]").unwrap_err();
         let _: AvatarError = Avatar::new("../main.css").unwrap_err();
     }
+
+    #[test]
+    fn unsafe_schemes() {
+        let policy = AvatarPolicy::default();
+        assert!(check(&policy, "https://vk.com/im").is_ok());
+        let urls = [
+            "javascript:alert(1)",
+            "data:image/png;base64,AAAA",
+            "file:///etc/passwd",
+            "ftp://example.com/avatar.png",
+            "http://example.com/avatar.png",
+        ];
+        for url in urls {
+            let error = check(&policy, url).unwrap_err();
+            assert!(matches!(error, AvatarError::SchemeNotAllowed), "{url}");
+        }
+    }
+
+    #[test]
+    fn private_hosts() {
+        let policy = AvatarPolicy::default();
+        let urls = [
+            "https://localhost/avatar.png",
+            "https://api.localhost/avatar.png",
+            "https://127.0.0.1/avatar.png",
+            "https://10.0.0.1/avatar.png",
+            "https://192.168.1.1/avatar.png",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/avatar.png",
+            "https://[fd00::1]/avatar.png",
+            "https://[::ffff:127.0.0.1]/avatar.png",
+        ];
+        for url in urls {
+            let error = check(&policy, url).unwrap_err();
+            assert!(matches!(error, AvatarError::PrivateHost), "{url}");
+        }
+
+        let policy = AvatarPolicy::builder().allow_private_hosts(true).build();
+        assert!(check(&policy, "https://localhost/avatar.png").is_ok());
+    }
+
+    #[test]
+    fn policy() {
+        let policy = AvatarPolicy::builder()
+            .allowed_schemes(vec!["https".into(), "http".into()])
+            .allowed_hosts(vec!["cdn.example.com".into()])
+            .max_length(40)
+            .build();
+        assert!(check(&policy, "http://cdn.example.com/a.png").is_ok());
+
+        let error = check(&policy, "https://example.com/a.png").unwrap_err();
+        assert!(matches!(error, AvatarError::HostNotAllowed));
+        let url = "https://cdn.example.com/very/long/path/to/avatar.png";
+        let error = check(&policy, url).unwrap_err();
+        assert!(matches!(error, AvatarError::TooLong { max: 40, .. }));
+    }
 }
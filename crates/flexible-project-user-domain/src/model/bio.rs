@@ -0,0 +1,178 @@
+use std::borrow::{Borrow, Cow};
+
+use derive_more::{Display, Error};
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
+use typed_builder::TypedBuilder;
+
+/// Short biography of the user in the system written in Markdown.
+///
+/// Line endings are normalized to `\n` and whitespace is trimmed from both ends.
+/// Markdown is not rendered nor sanitized by the system, so clients are responsible for it.
+///
+/// These requirements are:
+/// - must not be empty;
+/// - must not be larger than 500 characters in length;
+/// - must not contain control characters other than line feed and tab.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bio(String);
+
+impl Bio {
+    /// Creates new user bio from input string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if input string does not match user bio requirements.
+    pub fn new(bio: impl Into<String>) -> Result<Self, BioError> {
+        const MIN_LENGTH: usize = 1;
+        const MAX_LENGTH: usize = 500;
+
+        let bio = bio.into().replace("\r\n", "\n");
+        let bio = bio.trim().to_owned();
+        let length = bio.chars().count();
+        if length < MIN_LENGTH {
+            let error = BioError::TooShort {
+                min: MIN_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if length > MAX_LENGTH {
+            let error = BioError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+
+        let invalid_character = bio
+            .chars()
+            .enumerate()
+            .find(|&(_, ch)| ch.is_control() && !matches!(ch, '\n' | '\t'));
+        if let Some((position, ch)) = invalid_character {
+            return Err(BioError::InvalidCharacter { ch, position });
+        }
+        Ok(Self(bio))
+    }
+
+    /// Extracts string slice from a user bio.
+    pub fn as_str(&self) -> &str {
+        let Self(bio) = self;
+        bio.as_str()
+    }
+
+    /// Converts user bio into a string.
+    pub fn into_inner(self) -> String {
+        let Self(bio) = self;
+        bio
+    }
+}
+
+/// Type of error which is returned when input does not meet user bio requirements.
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BioError {
+    /// User bio has fewer characters than required.
+    #[display(fmt = "user bio must have at least {} characters, got {}", min, actual)]
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User bio has more characters than allowed.
+    #[display(fmt = "user bio must have at most {} characters, got {}", max, actual)]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User bio contains character which is not allowed.
+    #[display(
+        fmt = "user bio contains invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+}
+
+/// Filters for optional user bio of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionBioFilters<'a> {
+    /// Equality user bio filter.
+    pub eq: Option<Equal<Cow<'a, Option<Bio>>>>,
+    /// Inequality user bio filter.
+    pub ne: Option<NotEqual<Cow<'a, Option<Bio>>>>,
+    /// In user bio filter.
+    pub r#in: Option<In<Cow<'a, [Option<Bio>]>>>,
+    /// Not in user bio filter.
+    pub nin: Option<NotIn<Cow<'a, [Option<Bio>]>>>,
+    /// Regex user bio filter.
+    ///
+    /// Absent user bio never matches the regex.
+    pub regex: Option<Regex<Cow<'a, str>>>,
+}
+
+impl<Input> Filter<Input> for OptionBioFilters<'_>
+where
+    Input: Borrow<Option<Bio>>,
+{
+    fn satisfies(&self, input: Input) -> bool {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = self;
+        let input = input.borrow();
+        eq.satisfies(Cow::Borrowed(input))
+            && ne.satisfies(Cow::Borrowed(input))
+            && r#in.as_ref().map(In::as_deref).satisfies(input)
+            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+            && match input {
+                Some(input) => regex.satisfies(input.as_str()),
+                None => regex.is_none(),
+            }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bio, BioError};
+
+    #[test]
+    fn valid_ones() {
+        let Bio(_) = Bio::new("Rust developer from **Moscow**").unwrap();
+        let bio = Bio::new("  # About me\r\n\n- likes\tRust  ").unwrap();
+        assert_eq!(bio.as_str(), "# About me\n\n- likes\tRust");
+    }
+
+    #[test]
+    fn invalid() {
+        let error = Bio::new(" \n ").unwrap_err();
+        assert_eq!(error, BioError::TooShort { min: 1, actual: 0 });
+        let error = Bio::new("a".repeat(501)).unwrap_err();
+        assert_eq!(
+            error,
+            BioError::TooLong {
+                max: 500,
+                actual: 501
+            }
+        );
+        let error = Bio::new("bell\u{7}").unwrap_err();
+        let expected = BioError::InvalidCharacter {
+            ch: '\u{7}',
+            position: 4,
+        };
+        assert_eq!(error, expected);
+    }
+}
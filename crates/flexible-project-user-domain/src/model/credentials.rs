@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+
+use super::password::PasswordHash;
+
+/// Credentials of the user in the system which are used to sign in.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct UserCredentials {
+    /// Hash of the password of the user.
+    pub password_hash: PasswordHash,
+    /// Time of the last password change.
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,38 +1,65 @@
 use std::borrow::{Borrow, Cow};
 
 use derive_more::{Display, Error};
-use fancy_regex::Regex as FancyRegex;
 use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
-use once_cell::sync::Lazy;
 use typed_builder::TypedBuilder;
+use unicode_normalization::UnicodeNormalization;
 
 /// Display name of the user in the system with strong requirements about its content.
 ///
+/// Input is normalized before it is checked against requirements:
+/// - Unicode NFC normalization is applied;
+/// - invisible characters (zero-width spaces, joiners, fillers etc.) are removed;
+/// - whitespace sequences are collapsed into one space and trimmed from both ends.
+///
 /// These requirements are:
 /// - must not be empty;
 /// - must not be larger than 128 characters in length;
+/// - must not contain control characters or bidirectional embeddings, overrides and isolates
+///   (directional marks such as U+200E and U+200F are allowed);
 /// - must contain at least one letter.
 #[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DisplayName(String);
 
 impl DisplayName {
-    /// Creates new user display name from input string.
+    /// Creates new user display name from normalized input string.
     ///
     /// # Errors
     ///
     /// This function will return an error
     /// if input string does not match user display name requirements.
+    /// Position of invalid character is reported for the input string before normalization.
     pub fn new(display_name: impl Into<String>) -> Result<Self, DisplayNameError> {
-        static REGEX: Lazy<FancyRegex> = Lazy::new(|| {
-            FancyRegex::new(r"^(?=.*?\p{L}).{1,128}$").expect("regex pattern should be parsed")
-        });
+        const MIN_LENGTH: usize = 1;
+        const MAX_LENGTH: usize = 128;
 
         let display_name = display_name.into();
-        let is_valid = REGEX
-            .is_match(&display_name)
-            .expect("input name matching should be successful");
-        if !is_valid {
-            return Err(DisplayNameError::Invalid);
+        let invalid_character = display_name
+            .chars()
+            .enumerate()
+            .find(|&(_, ch)| ch.is_control() || is_bidi_control(ch));
+        if let Some((position, ch)) = invalid_character {
+            return Err(DisplayNameError::InvalidCharacter { ch, position });
+        }
+
+        let display_name = normalize(&display_name);
+        let length = display_name.chars().count();
+        if length < MIN_LENGTH {
+            let error = DisplayNameError::TooShort {
+                min: MIN_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if length > MAX_LENGTH {
+            let error = DisplayNameError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if !display_name.chars().any(char::is_alphabetic) {
+            return Err(DisplayNameError::NoLetter);
         }
         Ok(Self(display_name))
     }
@@ -48,14 +75,111 @@ impl DisplayName {
         let Self(display_name) = self;
         display_name
     }
+
+    /// Returns confusable skeleton of the user display name as defined by
+    /// [Unicode Technical Standard #39](https://www.unicode.org/reports/tr39/#Confusable_Detection).
+    ///
+    /// Display names with equal skeletons look alike (ignoring case),
+    /// for example `Admin` and `Аdmіn` with cyrillic letters.
+    pub fn skeleton(&self) -> String {
+        let lowercase = self.as_str().to_lowercase();
+        unicode_security::skeleton(&lowercase).collect()
+    }
+
+    /// Checks if this user display name could be confused with other one.
+    pub fn is_confusable_with(&self, other: &Self) -> bool {
+        self.skeleton() == other.skeleton()
+    }
+}
+
+fn normalize(display_name: &str) -> String {
+    let mut normalized = String::with_capacity(display_name.len());
+    for ch in display_name.nfc().filter(|&ch| !is_invisible(ch)) {
+        if !ch.is_whitespace() {
+            normalized.push(ch);
+            continue;
+        }
+        if !normalized.is_empty() && !normalized.ends_with(' ') {
+            normalized.push(' ');
+        }
+    }
+    let trimmed_length = normalized.trim_end().len();
+    normalized.truncate(trimmed_length);
+    normalized
+}
+
+fn is_bidi_control(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn is_invisible(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{17B4}'
+            | '\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200D}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2800}'
+            | '\u{3164}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0000}'..='\u{E007F}'
+    )
 }
 
 /// Type of error which is returned when input does not meet user display name requirements.
-#[derive(Debug, Display, Clone, Copy, Error)]
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum DisplayNameError {
-    /// User display name does not meet requirements.
-    #[display(fmt = "user display name does not meet requirements")]
-    Invalid,
+    /// User display name has fewer characters than required.
+    #[display(
+        fmt = "user display name must have at least {} characters, got {}",
+        min,
+        actual
+    )]
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User display name has more characters than allowed.
+    #[display(
+        fmt = "user display name must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User display name contains character which is not allowed.
+    #[display(
+        fmt = "user display name contains invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+    /// User display name does not contain any letter.
+    #[display(fmt = "user display name must contain at least one letter")]
+    NoLetter,
 }
 
 /// Filters for user display name of the backend.
@@ -114,8 +238,73 @@ mod test {
 
     #[test]
     fn no_letters() {
-        let _: DisplayNameError = DisplayName::new("__1__").unwrap_err();
+        let error = DisplayName::new("__1__").unwrap_err();
+        assert_eq!(error, DisplayNameError::NoLetter);
         let _: DisplayNameError = DisplayName::new("0123456789").unwrap_err();
         let _: DisplayNameError = DisplayName::new("&%!@@(*&@$@*").unwrap_err();
     }
+
+    #[test]
+    fn detailed_errors() {
+        let error = DisplayName::new("").unwrap_err();
+        assert_eq!(error, DisplayNameError::TooShort { min: 1, actual: 0 });
+        let error = DisplayName::new("a".repeat(129)).unwrap_err();
+        assert_eq!(
+            error,
+            DisplayNameError::TooLong {
+                max: 128,
+                actual: 129
+            }
+        );
+        let error = DisplayName::new("Тимур\nТугушев").unwrap_err();
+        let expected = DisplayNameError::InvalidCharacter {
+            ch: '\n',
+            position: 5,
+        };
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn normalization() {
+        let display_name = DisplayName::new("  Timur \u{3000}  Tugushev ").unwrap();
+        assert_eq!(display_name.as_str(), "Timur Tugushev");
+        let display_name = DisplayName::new("Ti\u{200B}mur\u{FEFF}").unwrap();
+        assert_eq!(display_name.as_str(), "Timur");
+        let display_name = DisplayName::new("e\u{0301}").unwrap();
+        assert_eq!(display_name.as_str(), "\u{00E9}");
+
+        let error = DisplayName::new(" \u{200B} ").unwrap_err();
+        assert_eq!(error, DisplayNameError::TooShort { min: 1, actual: 0 });
+        let error = DisplayName::new("admin\u{202E}").unwrap_err();
+        let expected = DisplayNameError::InvalidCharacter {
+            ch: '\u{202E}',
+            position: 5,
+        };
+        assert_eq!(error, expected);
+        let error = DisplayName::new("\u{2067}Тимур").unwrap_err();
+        let expected = DisplayNameError::InvalidCharacter {
+            ch: '\u{2067}',
+            position: 0,
+        };
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn directional_marks() {
+        let display_name = DisplayName::new("Timur \u{200F}תימור\u{200E}").unwrap();
+        assert_eq!(display_name.as_str(), "Timur \u{200F}תימור\u{200E}");
+        let display_name = DisplayName::new("\u{200E}Timur").unwrap();
+        assert_eq!(display_name.as_str(), "\u{200E}Timur");
+    }
+
+    #[test]
+    fn confusables() {
+        let admin = DisplayName::new("Admin").unwrap();
+        let impostor = DisplayName::new("\u{0410}dm\u{0456}n").unwrap();
+        assert!(admin.is_confusable_with(&impostor));
+        let impostor = DisplayName::new("admin").unwrap();
+        assert!(admin.is_confusable_with(&impostor));
+        let other = DisplayName::new("Moderator").unwrap();
+        assert!(!admin.is_confusable_with(&other));
+    }
 }
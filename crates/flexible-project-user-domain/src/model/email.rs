@@ -17,9 +17,49 @@ impl Email {
     /// This function will return an error
     /// if input string does not match user email requirements.
     pub fn new(email: impl Into<String>) -> Result<Self, EmailError> {
+        const LOCAL_PART_MAX_LENGTH: usize = 64;
+        const DOMAIN_MAX_LENGTH: usize = 254;
+
         let email = email.into();
-        let is_valid = EmailAddress::is_valid(&email);
-        if !is_valid {
+        let Some((local_part, domain)) = email.rsplit_once('@') else {
+            return Err(EmailError::MissingSeparator);
+        };
+        if local_part.is_empty() {
+            return Err(EmailError::EmptyLocalPart);
+        }
+        if domain.is_empty() {
+            return Err(EmailError::EmptyDomain);
+        }
+        let local_part_length = local_part.chars().count();
+        if local_part_length > LOCAL_PART_MAX_LENGTH {
+            let error = EmailError::LocalPartTooLong {
+                max: LOCAL_PART_MAX_LENGTH,
+                actual: local_part_length,
+            };
+            return Err(error);
+        }
+        let domain_length = domain.chars().count();
+        if domain_length > DOMAIN_MAX_LENGTH {
+            let error = EmailError::DomainTooLong {
+                max: DOMAIN_MAX_LENGTH,
+                actual: domain_length,
+            };
+            return Err(error);
+        }
+
+        // quoted local parts and domain literals are left to the full address parser
+        let is_dot_atom = |part: &str| !part.starts_with(['"', '[']);
+        if is_dot_atom(local_part) {
+            let is_allowed = |ch: char| ch.is_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(ch);
+            check_dot_atom(local_part, 0, is_allowed)?;
+        }
+        if is_dot_atom(domain) {
+            let offset = local_part_length + 1;
+            let is_allowed = |ch: char| ch.is_alphanumeric() || ch == '-';
+            check_dot_atom(domain, offset, is_allowed)?;
+        }
+
+        if !EmailAddress::is_valid(&email) {
             return Err(EmailError::Invalid);
         }
         Ok(Self(email))
@@ -36,12 +76,206 @@ impl Email {
         let Self(email) = self;
         email
     }
+
+    /// Returns domain part of the user email (after `@` character).
+    pub fn domain(&self) -> &str {
+        let (_, domain) = self
+            .as_str()
+            .rsplit_once('@')
+            .expect("user email should contain separator");
+        domain
+    }
+
+    /// Returns canonical (case-folded) form of the user email.
+    ///
+    /// User emails with equal canonical forms are considered the same user email
+    /// when checked for uniqueness or compared by [filters](EmailFilters),
+    /// while the original casing is preserved for display.
+    pub fn canonical(&self) -> String {
+        Self::canonical_form(self.as_str())
+    }
+
+    /// Returns canonical form of the raw user email without checking user email requirements,
+    /// e.g. of the user email stored before the requirements were changed.
+    pub fn canonical_form(email: &str) -> String {
+        email.to_lowercase()
+    }
+}
+
+/// Policy which domains of user emails should conform to.
+///
+/// Domains are compared case-insensitively.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct EmailPolicy {
+    /// Domains which user email could have, or `None` if any domain is allowed.
+    #[builder(default, setter(strip_option))]
+    pub allowed_domains: Option<Vec<String>>,
+    /// Domains which user email must not have, such as disposable email providers.
+    ///
+    /// Denied domains take precedence over allowed ones.
+    #[builder(default)]
+    pub denied_domains: Vec<String>,
+    /// Whether subdomains of allowed or denied domains are matched too,
+    /// so `mail.example.com` is matched by `example.com`.
+    #[builder(default = true)]
+    pub match_subdomains: bool,
+}
+
+impl Default for EmailPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl EmailPolicy {
+    /// Checks if domain of provided user email conforms to the policy.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if domain of provided user email is denied or not allowed by the policy.
+    pub fn check(&self, email: &Email) -> Result<(), EmailDomainError> {
+        let Self {
+            allowed_domains,
+            denied_domains,
+            match_subdomains,
+        } = self;
+
+        let domain = email.domain().trim_end_matches('.').to_lowercase();
+        let matches = |pattern: &String| {
+            let pattern = pattern.trim_end_matches('.').to_lowercase();
+            let is_subdomain = || {
+                domain
+                    .strip_suffix(pattern.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+            };
+            domain == pattern || (*match_subdomains && is_subdomain())
+        };
+        if denied_domains.iter().any(matches) {
+            return Err(EmailDomainError::Denied);
+        }
+        if let Some(allowed_domains) = allowed_domains {
+            if !allowed_domains.iter().any(matches) {
+                return Err(EmailDomainError::NotAllowed);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Type of error which is returned when domain of user email does not conform to the policy.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum EmailDomainError {
+    /// Domain of user email is in the list of denied domains.
+    #[display(fmt = "user email domain is denied")]
+    Denied,
+    /// Domain of user email is not in the list of allowed domains.
+    #[display(fmt = "user email domain is not allowed")]
+    NotAllowed,
+}
+
+fn check_dot_atom(
+    part: &str,
+    offset: usize,
+    is_allowed: impl Fn(char) -> bool,
+) -> Result<(), EmailError> {
+    let mut is_previous_separator = false;
+    for (index, ch) in part.chars().enumerate() {
+        let position = offset + index;
+        let is_separator = ch == '.';
+        if !is_separator && !is_allowed(ch) {
+            return Err(EmailError::InvalidCharacter { ch, position });
+        }
+        if is_separator && index == 0 {
+            return Err(EmailError::LeadingSeparator { position });
+        }
+        if is_separator && is_previous_separator {
+            return Err(EmailError::ConsecutiveSeparators { position });
+        }
+        is_previous_separator = is_separator;
+    }
+    if is_previous_separator {
+        let position = offset + part.chars().count() - 1;
+        return Err(EmailError::TrailingSeparator { position });
+    }
+    Ok(())
 }
 
 /// Type of error which is returned when input does not meet user email requirements.
-#[derive(Debug, Display, Clone, Copy, Error)]
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum EmailError {
-    /// User email does not meet requirements.
+    /// User email does not contain `@` character.
+    #[display(fmt = "user email must contain '@' character")]
+    MissingSeparator,
+    /// Local part of user email (before `@` character) is empty.
+    #[display(fmt = "user email must not have empty local part")]
+    EmptyLocalPart,
+    /// Local part of user email has more characters than allowed.
+    #[display(
+        fmt = "user email local part must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    LocalPartTooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Domain of user email (after `@` character) is empty.
+    #[display(fmt = "user email must not have empty domain")]
+    EmptyDomain,
+    /// Domain of user email has more characters than allowed.
+    #[display(
+        fmt = "user email domain must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    DomainTooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User email contains character which is not allowed.
+    #[display(
+        fmt = "user email contains invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+    /// Local part or domain of user email starts with `.` character.
+    #[display(fmt = "user email contains leading separator at position {}", position)]
+    LeadingSeparator {
+        /// Position of the separator.
+        position: usize,
+    },
+    /// Local part or domain of user email ends with `.` character.
+    #[display(
+        fmt = "user email contains trailing separator at position {}",
+        position
+    )]
+    TrailingSeparator {
+        /// Position of the separator.
+        position: usize,
+    },
+    /// User email contains `.` characters next to each other.
+    #[display(
+        fmt = "user email contains consecutive separators at position {}",
+        position
+    )]
+    ConsecutiveSeparators {
+        /// Position of the second separator in a row.
+        position: usize,
+    },
+    /// User email does not meet other requirements of email address format.
     #[display(fmt = "user email does not meet requirements")]
     Invalid,
 }
@@ -51,6 +285,9 @@ pub enum EmailError {
 #[builder(field_defaults(default, setter(into, strip_option)))]
 pub struct EmailFilters<'a> {
     /// Equality user email filter.
+    ///
+    /// Equality, inequality, in and not in filters compare
+    /// [canonical forms](Email::canonical) of user emails.
     pub eq: Option<Equal<Cow<'a, Email>>>,
     /// Inequality user email filter.
     pub ne: Option<NotEqual<Cow<'a, Email>>>,
@@ -75,10 +312,24 @@ where
             regex,
         } = self;
         let input = input.borrow();
-        eq.satisfies(Cow::Borrowed(input))
-            && ne.satisfies(Cow::Borrowed(input))
-            && r#in.as_ref().map(In::as_deref).satisfies(input)
-            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+        let canonical = input.canonical();
+        let canonical_all =
+            |emails: &[Email]| emails.iter().map(Email::canonical).collect::<Vec<_>>();
+        eq.as_ref()
+            .map(|Equal(email)| Equal(email.canonical()))
+            .satisfies(&canonical)
+            && ne
+                .as_ref()
+                .map(|NotEqual(email)| NotEqual(email.canonical()))
+                .satisfies(&canonical)
+            && r#in
+                .as_ref()
+                .map(|In(emails)| In(canonical_all(emails)))
+                .satisfies(&canonical)
+            && nin
+                .as_ref()
+                .map(|NotIn(emails)| NotIn(canonical_all(emails)))
+                .satisfies(&canonical)
             && regex.satisfies(input.as_str())
     }
 }
@@ -88,6 +339,9 @@ where
 #[builder(field_defaults(default, setter(into, strip_option)))]
 pub struct OptionEmailFilters<'a> {
     /// Equality user email filter.
+    ///
+    /// Equality, inequality, in and not in filters compare
+    /// [canonical forms](Email::canonical) of user emails.
     pub eq: Option<Equal<Cow<'a, Option<Email>>>>,
     /// Inequality user email filter.
     pub ne: Option<NotEqual<Cow<'a, Option<Email>>>>,
@@ -96,6 +350,8 @@ pub struct OptionEmailFilters<'a> {
     /// Not in user email filter.
     pub nin: Option<NotIn<Cow<'a, [Option<Email>]>>>,
     /// Regex user email filter.
+    ///
+    /// Absent user email never matches the regex.
     pub regex: Option<Regex<Cow<'a, str>>>,
 }
 
@@ -112,20 +368,38 @@ where
             regex,
         } = self;
         let input = input.borrow();
-        eq.satisfies(Cow::Borrowed(input))
-            && ne.satisfies(Cow::Borrowed(input))
-            && r#in.as_ref().map(In::as_deref).satisfies(input)
-            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
-            && input
+        let canonical = input.as_ref().map(Email::canonical);
+        let canonical_all = |emails: &[Option<Email>]| {
+            emails
+                .iter()
+                .map(|email| email.as_ref().map(Email::canonical))
+                .collect::<Vec<_>>()
+        };
+        eq.as_ref()
+            .map(|Equal(email)| Equal(email.as_ref().as_ref().map(Email::canonical)))
+            .satisfies(&canonical)
+            && ne
                 .as_ref()
-                .map(|input| regex.satisfies(input.as_str()))
-                .unwrap_or(true)
+                .map(|NotEqual(email)| NotEqual(email.as_ref().as_ref().map(Email::canonical)))
+                .satisfies(&canonical)
+            && r#in
+                .as_ref()
+                .map(|In(emails)| In(canonical_all(emails)))
+                .satisfies(&canonical)
+            && nin
+                .as_ref()
+                .map(|NotIn(emails)| NotIn(canonical_all(emails)))
+                .satisfies(&canonical)
+            && match input {
+                Some(input) => regex.satisfies(input.as_str()),
+                None => regex.is_none(),
+            }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Email, EmailError};
+    use super::{Email, EmailDomainError, EmailError, EmailPolicy};
 
     #[test]
     fn valid_ones() {
@@ -143,4 +417,52 @@ mod test {
         let _: EmailError = Email::new("@email.com").unwrap_err();
         let _: EmailError = Email::new(r#"is"especially"not\allowed@email.com"#).unwrap_err();
     }
+
+    #[test]
+    fn detailed_errors() {
+        let error = Email::new("plaintext").unwrap_err();
+        assert_eq!(error, EmailError::MissingSeparator);
+        let error = Email::new("@email.com").unwrap_err();
+        assert_eq!(error, EmailError::EmptyLocalPart);
+        let error = Email::new("example@").unwrap_err();
+        assert_eq!(error, EmailError::EmptyDomain);
+        let error = Email::new("example..name@email.com").unwrap_err();
+        assert_eq!(error, EmailError::ConsecutiveSeparators { position: 8 });
+        let error = Email::new("example@.email.com").unwrap_err();
+        assert_eq!(error, EmailError::LeadingSeparator { position: 8 });
+        let error = Email::new("example@email.com.").unwrap_err();
+        assert_eq!(error, EmailError::TrailingSeparator { position: 17 });
+        let error = Email::new("exa mple@email.com").unwrap_err();
+        let expected = EmailError::InvalidCharacter {
+            ch: ' ',
+            position: 3,
+        };
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn domain_policy() {
+        let policy = EmailPolicy::builder()
+            .allowed_domains(vec!["mirea.ru".into()])
+            .denied_domains(vec!["spam.mirea.ru".into()])
+            .build();
+        let email = Email::new("tugushev.t.r@edu.MIREA.ru").unwrap();
+        assert_eq!(policy.check(&email), Ok(()));
+        let email = Email::new("someone@mirea.ru").unwrap();
+        assert_eq!(policy.check(&email), Ok(()));
+        let email = Email::new("someone@notmirea.ru").unwrap();
+        assert_eq!(policy.check(&email), Err(EmailDomainError::NotAllowed));
+        let email = Email::new("someone@mail.spam.mirea.ru").unwrap();
+        assert_eq!(policy.check(&email), Err(EmailDomainError::Denied));
+
+        let policy = EmailPolicy::builder()
+            .allowed_domains(vec!["mirea.ru".into()])
+            .match_subdomains(false)
+            .build();
+        let email = Email::new("tugushev.t.r@edu.mirea.ru").unwrap();
+        assert_eq!(policy.check(&email), Err(EmailDomainError::NotAllowed));
+
+        let email = Email::new("example@email.com").unwrap();
+        assert_eq!(EmailPolicy::default().check(&email), Ok(()));
+    }
 }
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+
+use super::id::UserId;
+
+/// Event of the user microservice which is consumed by other services of the system.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum UserEvent {
+    /// All the data of the user was erased from the user microservice.
+    ///
+    /// Other services must scrub their references to the user,
+    /// such as workspace members or notifications.
+    Erased {
+        /// Identifier of the erased user.
+        id: UserId,
+        /// Time when the user was erased.
+        erased_at: DateTime<Utc>,
+    },
+}
+
+impl UserEvent {
+    /// Returns identifier of the user which the event is about.
+    pub fn user_id(&self) -> &UserId {
+        match self {
+            Self::Erased { id, .. } => id,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use derive_more::Display;
+
+/// Format of the image which could be uploaded into the system.
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Portable Network Graphics image.
+    #[display(fmt = "PNG")]
+    Png,
+    /// JPEG image.
+    #[display(fmt = "JPEG")]
+    Jpeg,
+}
+
+impl ImageFormat {
+    /// Detects format of the image by the signature of its content.
+    ///
+    /// Returns `None` if image format is not supported.
+    pub fn detect(content: &[u8]) -> Option<Self> {
+        const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+        const JPEG_SIGNATURE: &[u8] = b"\xff\xd8\xff";
+
+        if content.starts_with(PNG_SIGNATURE) {
+            return Some(Self::Png);
+        }
+        if content.starts_with(JPEG_SIGNATURE) {
+            return Some(Self::Jpeg);
+        }
+        None
+    }
+
+    /// Returns media type of the image format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// Returns file extension of the image format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ImageFormat;
+
+    #[test]
+    fn detection() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        assert_eq!(ImageFormat::detect(png), Some(ImageFormat::Png));
+        let jpeg = b"\xff\xd8\xff\xe0\0\x10JFIF";
+        assert_eq!(ImageFormat::detect(jpeg), Some(ImageFormat::Jpeg));
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(ImageFormat::detect(b""), None);
+        assert_eq!(ImageFormat::detect(b"GIF89a"), None);
+        assert_eq!(ImageFormat::detect(b"<svg></svg>"), None);
+    }
+}
@@ -0,0 +1,172 @@
+use std::borrow::{Borrow, Cow};
+
+use derive_more::{Display, Error};
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn};
+use typed_builder::TypedBuilder;
+
+/// Preferred locale of the user in the system as [BCP 47](https://www.rfc-editor.org/info/bcp47)
+/// language tag, such as `en`, `ru-RU` or `zh-Hant-TW`.
+///
+/// Language tag is stored in its canonical case:
+/// language subtag is lowercase, script subtag is titlecase and region subtag is uppercase.
+///
+/// These requirements are:
+/// - must not be larger than 35 characters in length;
+/// - subtags must be separated by `-` character;
+/// - language subtag must contain 2, 3 or from 5 to 8 latin letters;
+/// - other subtags must contain from 1 to 8 latin letters or digits.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Locale(String);
+
+impl Locale {
+    /// Creates new user locale from input string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if input string does not match user locale requirements.
+    pub fn new(locale: impl Into<String>) -> Result<Self, LocaleError> {
+        const MAX_LENGTH: usize = 35;
+
+        let locale = locale.into();
+        let length = locale.chars().count();
+        if length > MAX_LENGTH {
+            let error = LocaleError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+
+        let mut subtags = locale.split('-');
+        let language = subtags.next().unwrap_or_default();
+        let is_language_valid = matches!(language.len(), 2 | 3 | 5..=8)
+            && language.chars().all(|ch| ch.is_ascii_alphabetic());
+        if !is_language_valid {
+            return Err(LocaleError::InvalidLanguage);
+        }
+
+        let mut canonical = language.to_ascii_lowercase();
+        let mut position = language.len() + 1;
+        for (index, subtag) in subtags.enumerate() {
+            let is_subtag_valid = matches!(subtag.len(), 1..=8)
+                && subtag.chars().all(|ch| ch.is_ascii_alphanumeric());
+            if !is_subtag_valid {
+                return Err(LocaleError::InvalidSubtag { position });
+            }
+            position += subtag.len() + 1;
+
+            let is_alphabetic = subtag.chars().all(|ch| ch.is_ascii_alphabetic());
+            let subtag = match subtag.len() {
+                4 if index == 0 && is_alphabetic => {
+                    let (first, rest) = subtag.split_at(1);
+                    first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+                }
+                2 if is_alphabetic => subtag.to_ascii_uppercase(),
+                _ => subtag.to_ascii_lowercase(),
+            };
+            canonical.push('-');
+            canonical.push_str(&subtag);
+        }
+        Ok(Self(canonical))
+    }
+
+    /// Extracts string slice from a user locale.
+    pub fn as_str(&self) -> &str {
+        let Self(locale) = self;
+        locale.as_str()
+    }
+
+    /// Converts user locale into a string.
+    pub fn into_inner(self) -> String {
+        let Self(locale) = self;
+        locale
+    }
+}
+
+/// Type of error which is returned when input does not meet user locale requirements.
+///
+/// Positions of the subtags are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum LocaleError {
+    /// User locale has more characters than allowed.
+    #[display(
+        fmt = "user locale must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Language subtag of user locale is invalid.
+    #[display(fmt = "user locale has invalid language subtag")]
+    InvalidLanguage,
+    /// Subtag of user locale after language subtag is invalid.
+    #[display(fmt = "user locale has invalid subtag at position {}", position)]
+    InvalidSubtag {
+        /// Position of the first character of the subtag.
+        position: usize,
+    },
+}
+
+/// Filters for optional user locale of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionLocaleFilters<'a> {
+    /// Equality user locale filter.
+    pub eq: Option<Equal<Cow<'a, Option<Locale>>>>,
+    /// Inequality user locale filter.
+    pub ne: Option<NotEqual<Cow<'a, Option<Locale>>>>,
+    /// In user locale filter.
+    pub r#in: Option<In<Cow<'a, [Option<Locale>]>>>,
+    /// Not in user locale filter.
+    pub nin: Option<NotIn<Cow<'a, [Option<Locale>]>>>,
+}
+
+impl<Input> Filter<Input> for OptionLocaleFilters<'_>
+where
+    Input: Borrow<Option<Locale>>,
+{
+    fn satisfies(&self, input: Input) -> bool {
+        let Self { eq, ne, r#in, nin } = self;
+        let input = input.borrow();
+        eq.satisfies(Cow::Borrowed(input))
+            && ne.satisfies(Cow::Borrowed(input))
+            && r#in.as_ref().map(In::as_deref).satisfies(input)
+            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Locale, LocaleError};
+
+    #[test]
+    fn valid_ones() {
+        let locale = Locale::new("en").unwrap();
+        assert_eq!(locale.as_str(), "en");
+        let locale = Locale::new("RU-ru").unwrap();
+        assert_eq!(locale.as_str(), "ru-RU");
+        let locale = Locale::new("zh-hant-tw").unwrap();
+        assert_eq!(locale.as_str(), "zh-Hant-TW");
+        let locale = Locale::new("es-419").unwrap();
+        assert_eq!(locale.as_str(), "es-419");
+    }
+
+    #[test]
+    fn invalid() {
+        let error = Locale::new("").unwrap_err();
+        assert_eq!(error, LocaleError::InvalidLanguage);
+        let error = Locale::new("e").unwrap_err();
+        assert_eq!(error, LocaleError::InvalidLanguage);
+        let error = Locale::new("en_US").unwrap_err();
+        assert_eq!(error, LocaleError::InvalidLanguage);
+        let error = Locale::new("en--US").unwrap_err();
+        assert_eq!(error, LocaleError::InvalidSubtag { position: 3 });
+        let error = Locale::new("en-verylongsubtag").unwrap_err();
+        assert_eq!(error, LocaleError::InvalidSubtag { position: 3 });
+    }
+}
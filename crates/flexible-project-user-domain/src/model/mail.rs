@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+use super::{email::Email, name::Name, password_reset::PasswordResetToken};
+
+/// Mail which is sent by the system to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mail {
+    /// Mail with a token which allows the user to reset its password.
+    PasswordReset {
+        /// Email of the user to send mail to.
+        recipient: Email,
+        /// Name of the user which requested password reset.
+        name: Name,
+        /// Secret token which allows to reset the password.
+        token: PasswordResetToken,
+        /// Time after which password reset token cannot be used.
+        expires_at: DateTime<Utc>,
+    },
+}
+
+impl Mail {
+    /// Returns email of the recipient of the mail.
+    pub fn recipient(&self) -> &Email {
+        match self {
+            Self::PasswordReset { recipient, .. } => recipient,
+        }
+    }
+}
@@ -1,19 +1,49 @@
 //! Model of the user microservice domain layer.
 
 pub use self::{
-    avatar::{Avatar, AvatarError, AvatarFilters, OptionAvatarFilters},
+    actor::{Actor, UserAction},
+    avatar::{Avatar, AvatarError, AvatarFilters, AvatarPolicy, OptionAvatarFilters},
+    bio::{Bio, BioError, OptionBioFilters},
+    credentials::UserCredentials,
     display_name::{DisplayName, DisplayNameError, DisplayNameFilters},
-    email::{Email, EmailError, EmailFilters, OptionEmailFilters},
+    email::{Email, EmailDomainError, EmailError, EmailFilters, EmailPolicy, OptionEmailFilters},
+    event::UserEvent,
     id::{UserId, UserIdFilters},
+    image::ImageFormat,
+    locale::{Locale, LocaleError, OptionLocaleFilters},
+    mail::Mail,
     name::{Name, NameError, NameFilters},
+    name_history::NameChange,
+    password::{Password, PasswordError, PasswordHash},
+    password_reset::{PasswordReset, PasswordResetToken, PasswordResetTokenHash},
+    personal_data::{PersonalData, PersonalDataEntry},
+    pronouns::{OptionPronounsFilters, Pronouns, PronounsError},
+    record::{UserRecord, UserRecordError},
     role::{Role, RoleFilters},
-    user::{User, UserData, UserDataFilters, UserFilters},
+    status::{AccountStatus, AccountStatusFilters, AccountStatusKind},
+    time_zone::{OptionTimeZoneFilters, TimeZone, TimeZoneError},
+    user::{User, UserData, UserDataFilters, UserFilters, UserPatch, VisibleUser},
 };
 
+mod actor;
 mod avatar;
+mod bio;
+mod credentials;
 mod display_name;
 mod email;
+mod event;
 mod id;
+mod image;
+mod locale;
+mod mail;
 mod name;
+mod name_history;
+mod password;
+mod password_reset;
+mod personal_data;
+mod pronouns;
+mod record;
 mod role;
+mod status;
+mod time_zone;
 mod user;
@@ -1,9 +1,7 @@
 use std::borrow::{Borrow, Cow};
 
 use derive_more::{Display, Error};
-use fancy_regex::Regex as FancyRegex;
 use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
-use once_cell::sync::Lazy;
 use typed_builder::TypedBuilder;
 
 /// Name of the user in the system with strong requirements about its content.
@@ -25,17 +23,42 @@ impl Name {
     /// This function will return an error
     /// if input string does not match user name requirements.
     pub fn new(name: impl Into<String>) -> Result<Self, NameError> {
-        static REGEX: Lazy<FancyRegex> = Lazy::new(|| {
-            FancyRegex::new(r"^(?=.{4,32}$)(?![-_.])(?!.*[-_.]{2})[a-zA-Z\d\-_.]+(?<![-_.])$")
-                .expect("regex pattern should be parsed")
-        });
+        const MIN_LENGTH: usize = 4;
+        const MAX_LENGTH: usize = 32;
 
         let name = name.into();
-        let is_valid = REGEX
-            .is_match(&name)
-            .expect("input name matching should be successful");
-        if !is_valid {
-            return Err(NameError::Invalid);
+        let length = name.chars().count();
+        if length < MIN_LENGTH {
+            let error = NameError::TooShort {
+                min: MIN_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if length > MAX_LENGTH {
+            let error = NameError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+
+        let mut is_previous_separator = false;
+        for (position, ch) in name.chars().enumerate() {
+            let is_separator = matches!(ch, '-' | '_' | '.');
+            if !is_separator && !ch.is_ascii_alphanumeric() {
+                return Err(NameError::InvalidCharacter { ch, position });
+            }
+            if is_separator && position == 0 {
+                return Err(NameError::LeadingSeparator);
+            }
+            if is_separator && is_previous_separator {
+                return Err(NameError::ConsecutiveSeparators { position });
+            }
+            is_previous_separator = is_separator;
+        }
+        if is_previous_separator {
+            return Err(NameError::TrailingSeparator);
         }
         Ok(Self(name))
     }
@@ -51,14 +74,75 @@ impl Name {
         let Self(name) = self;
         name
     }
+
+    /// Returns canonical (case-folded) form of the user name.
+    ///
+    /// User names with equal canonical forms are considered the same user name
+    /// when checked for uniqueness or compared by [filters](NameFilters),
+    /// while the original casing is preserved for display.
+    pub fn canonical(&self) -> String {
+        Self::canonical_form(self.as_str())
+    }
+
+    /// Returns canonical form of the raw user name without checking user name requirements,
+    /// e.g. of the user name stored before the requirements were changed.
+    pub fn canonical_form(name: &str) -> String {
+        name.to_ascii_lowercase()
+    }
 }
 
 /// Type of error which is returned when input does not meet user name requirements.
-#[derive(Debug, Display, Clone, Copy, Error)]
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum NameError {
-    /// User name does not meet requirements.
-    #[display(fmt = "user name does not meet requirements")]
-    Invalid,
+    /// User name has fewer characters than required.
+    #[display(
+        fmt = "user name must have at least {} characters, got {}",
+        min,
+        actual
+    )]
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User name has more characters than allowed.
+    #[display(fmt = "user name must have at most {} characters, got {}", max, actual)]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User name contains character which is not allowed.
+    #[display(
+        fmt = "user name contains invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+    /// User name starts with `-`, `_` or `.` character.
+    #[display(fmt = "user name must not start with a separator")]
+    LeadingSeparator,
+    /// User name ends with `-`, `_` or `.` character.
+    #[display(fmt = "user name must not end with a separator")]
+    TrailingSeparator,
+    /// User name contains `-`, `_` or `.` characters next to each other.
+    #[display(
+        fmt = "user name contains consecutive separators at position {}",
+        position
+    )]
+    ConsecutiveSeparators {
+        /// Position of the second separator in a row.
+        position: usize,
+    },
 }
 
 /// Filters for user name of the backend.
@@ -66,6 +150,9 @@ pub enum NameError {
 #[builder(field_defaults(default, setter(into, strip_option)))]
 pub struct NameFilters<'a> {
     /// Equality user name filter.
+    ///
+    /// Equality, inequality, in and not in filters compare
+    /// [canonical forms](Name::canonical) of user names.
     pub eq: Option<Equal<Cow<'a, Name>>>,
     /// Inequality user name filter.
     pub ne: Option<NotEqual<Cow<'a, Name>>>,
@@ -90,17 +177,34 @@ where
             regex,
         } = self;
         let input = input.borrow();
-        eq.satisfies(Cow::Borrowed(input))
-            && ne.satisfies(Cow::Borrowed(input))
-            && r#in.as_ref().map(In::as_deref).satisfies(input)
-            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+        let canonical = input.canonical();
+        let canonical_all = |names: &[Name]| names.iter().map(Name::canonical).collect::<Vec<_>>();
+        eq.as_ref()
+            .map(|Equal(name)| Equal(name.canonical()))
+            .satisfies(&canonical)
+            && ne
+                .as_ref()
+                .map(|NotEqual(name)| NotEqual(name.canonical()))
+                .satisfies(&canonical)
+            && r#in
+                .as_ref()
+                .map(|In(names)| In(canonical_all(names)))
+                .satisfies(&canonical)
+            && nin
+                .as_ref()
+                .map(|NotIn(names)| NotIn(canonical_all(names)))
+                .satisfies(&canonical)
             && regex.satisfies(input.as_str())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Name, NameError};
+    use std::borrow::Cow;
+
+    use fp_filter::{Equal, Filter, In};
+
+    use super::{Name, NameError, NameFilters};
 
     #[test]
     fn valid_ones() {
@@ -120,15 +224,21 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
     fn too_short() {
-        let Name(_) = Name::new("hey").unwrap();
+        let error = Name::new("hey").unwrap_err();
+        assert_eq!(error, NameError::TooShort { min: 4, actual: 3 });
     }
 
     #[test]
-    #[should_panic]
     fn too_long() {
-        let Name(_) = Name::new("too-many-characters-in-one-username").unwrap();
+        let error = Name::new("too-many-characters-in-one-username").unwrap_err();
+        assert_eq!(
+            error,
+            NameError::TooLong {
+                max: 32,
+                actual: 35
+            }
+        );
     }
 
     #[test]
@@ -139,6 +249,22 @@ mod test {
         let _: NameError = Name::new("another-username-").unwrap_err();
     }
 
+    #[test]
+    fn detailed_errors() {
+        let error = Name::new("_tugushev_timur").unwrap_err();
+        assert_eq!(error, NameError::LeadingSeparator);
+        let error = Name::new("tugushev_timur_").unwrap_err();
+        assert_eq!(error, NameError::TrailingSeparator);
+        let error = Name::new("tugushev__timur").unwrap_err();
+        assert_eq!(error, NameError::ConsecutiveSeparators { position: 9 });
+        let error = Name::new("tugushev timur").unwrap_err();
+        let expected = NameError::InvalidCharacter {
+            ch: ' ',
+            position: 8,
+        };
+        assert_eq!(error, expected);
+    }
+
     #[test]
     fn too_many_special_in_row() {
         let _: NameError = Name::new("tugushev__timur").unwrap_err();
@@ -152,4 +278,22 @@ mod test {
         let _: NameError = Name::new("привет_мир").unwrap_err();
         let _: NameError = Name::new("асу").unwrap_err();
     }
+
+    #[test]
+    fn case_insensitive_filters() {
+        let name = Name::new("TuguzT").unwrap();
+        assert_eq!(name.canonical(), "tuguzt");
+        assert_eq!(name.as_str(), "TuguzT");
+
+        let other = Name::new("tuguzt").unwrap();
+        let filters = NameFilters::builder()
+            .eq(Equal(Cow::Borrowed(&other)))
+            .build();
+        assert!(filters.satisfies(&name));
+        let names = [Name::new("TUGUZT").unwrap()];
+        let filters = NameFilters::builder()
+            .r#in(In(names.as_slice().into()))
+            .build();
+        assert!(filters.satisfies(&name));
+    }
 }
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::{id::UserId, name::Name};
+
+/// Record of the user name change which releases former name of the user.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct NameChange {
+    /// Identifier of the user which changed its name.
+    pub user_id: UserId,
+    /// Former name of the user.
+    pub name: Name,
+    /// Time when the name was changed.
+    pub changed_at: DateTime<Utc>,
+}
+
+impl NameChange {
+    /// Checks if former name is still reserved for the user at provided time.
+    ///
+    /// Reserved name cannot be claimed by other users
+    /// and is resolved to the user which released it.
+    pub fn is_reserved(&self, cooldown: Duration, now: DateTime<Utc>) -> bool {
+        now < self.changed_at + cooldown
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+
+    use super::NameChange;
+    use crate::model::{Name, UserId};
+
+    #[test]
+    fn reservation() {
+        let now = Utc::now();
+        let change = NameChange {
+            user_id: UserId::new("user"),
+            name: Name::new("tanabe").unwrap(),
+            changed_at: now,
+        };
+        let cooldown = Duration::days(30);
+        assert!(change.is_reserved(cooldown, now));
+        assert!(change.is_reserved(cooldown, now + Duration::days(29)));
+        assert!(!change.is_reserved(cooldown, now + Duration::days(30)));
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Debug;
+
+use derive_more::{Display, Error};
+use fancy_regex::Regex as FancyRegex;
+use once_cell::sync::Lazy;
+
+/// Password of the user in the system with strong requirements about its content.
+///
+/// These requirements are:
+/// - must be from 8 to 128 characters in length;
+/// - must contain at least one letter;
+/// - must contain at least one digit.
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct Password(String);
+
+impl Password {
+    /// Creates new user password from input string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if input string does not match user password requirements.
+    pub fn new(password: impl Into<String>) -> Result<Self, PasswordError> {
+        static REGEX: Lazy<FancyRegex> = Lazy::new(|| {
+            FancyRegex::new(r"^(?=.*?\p{L})(?=.*?\p{Nd}).{8,128}$")
+                .expect("regex pattern should be parsed")
+        });
+
+        let password = password.into();
+        let is_valid = REGEX
+            .is_match(&password)
+            .expect("input password matching should be successful");
+        if !is_valid {
+            return Err(PasswordError::Invalid);
+        }
+        Ok(Self(password))
+    }
+
+    /// Extracts string slice from a user password.
+    pub fn as_str(&self) -> &str {
+        let Self(password) = self;
+        password.as_str()
+    }
+
+    /// Converts user password into a string.
+    pub fn into_inner(self) -> String {
+        let Self(password) = self;
+        password
+    }
+}
+
+/// Password content is never printed to not leak it into logs.
+impl Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Password").field(&"<redacted>").finish()
+    }
+}
+
+/// Type of error which is returned when input does not meet user password requirements.
+#[derive(Debug, Display, Clone, Copy, Error)]
+pub enum PasswordError {
+    /// User password does not meet requirements.
+    #[display(fmt = "user password does not meet requirements")]
+    Invalid,
+}
+
+/// Hash of the user password which is safe to be stored.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PasswordHash(String);
+
+impl PasswordHash {
+    /// Creates new user password hash from input string.
+    pub fn new(hash: impl Into<String>) -> Self {
+        let hash = hash.into();
+        Self(hash)
+    }
+
+    /// Extracts string slice from a user password hash.
+    pub fn as_str(&self) -> &str {
+        let Self(hash) = self;
+        hash.as_str()
+    }
+
+    /// Converts user password hash into a string.
+    pub fn into_inner(self) -> String {
+        let Self(hash) = self;
+        hash
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Password, PasswordError};
+
+    #[test]
+    fn valid_ones() {
+        let Password(_) = Password::new("correct horse battery staple 1").unwrap();
+        let Password(_) = Password::new("Catcat12").unwrap();
+        let Password(_) = Password::new("пароль123").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        let Password(_) = Password::new("").unwrap();
+    }
+
+    #[test]
+    fn too_short() {
+        let _: PasswordError = Password::new("cat1").unwrap_err();
+    }
+
+    #[test]
+    fn no_letters_or_digits() {
+        let _: PasswordError = Password::new("0123456789").unwrap_err();
+        let _: PasswordError = Password::new("password").unwrap_err();
+    }
+
+    #[test]
+    fn redacted() {
+        let password = Password::new("Catcat12").unwrap();
+        let debug = format!("{password:?}");
+        assert!(!debug.contains("Catcat12"));
+    }
+}
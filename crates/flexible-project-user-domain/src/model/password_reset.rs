@@ -0,0 +1,113 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+
+use super::id::UserId;
+
+/// Secret single-use token which allows the user to reset its password.
+///
+/// Token is sent to the user and never stored as is: only its [hash](PasswordResetTokenHash) is stored.
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct PasswordResetToken(String);
+
+impl PasswordResetToken {
+    /// Creates new password reset token from input string.
+    pub fn new(token: impl Into<String>) -> Self {
+        let token = token.into();
+        Self(token)
+    }
+
+    /// Extracts string slice from a password reset token.
+    pub fn as_str(&self) -> &str {
+        let Self(token) = self;
+        token.as_str()
+    }
+
+    /// Converts password reset token into a string.
+    pub fn into_inner(self) -> String {
+        let Self(token) = self;
+        token
+    }
+}
+
+/// Token content is never printed to not leak it into logs.
+impl Debug for PasswordResetToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PasswordResetToken")
+            .field(&"<redacted>")
+            .finish()
+    }
+}
+
+/// Hash of the password reset token which is safe to be stored.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PasswordResetTokenHash(String);
+
+impl PasswordResetTokenHash {
+    /// Creates new password reset token hash from input string.
+    pub fn new(hash: impl Into<String>) -> Self {
+        let hash = hash.into();
+        Self(hash)
+    }
+
+    /// Extracts string slice from a password reset token hash.
+    pub fn as_str(&self) -> &str {
+        let Self(hash) = self;
+        hash.as_str()
+    }
+
+    /// Converts password reset token hash into a string.
+    pub fn into_inner(self) -> String {
+        let Self(hash) = self;
+        hash
+    }
+}
+
+/// Pending password reset of the user in the system.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PasswordReset {
+    /// Hash of the token which was sent to the user.
+    pub token_hash: PasswordResetTokenHash,
+    /// Identifier of the user which requested password reset.
+    pub user_id: UserId,
+    /// Time when password reset was requested.
+    pub created_at: DateTime<Utc>,
+    /// Time after which password reset token cannot be used.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PasswordReset {
+    /// Checks if password reset token cannot be used at provided time.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+
+    use super::{PasswordReset, PasswordResetToken, PasswordResetTokenHash};
+    use crate::model::UserId;
+
+    #[test]
+    fn expiration() {
+        let now = Utc::now();
+        let reset = PasswordReset {
+            token_hash: PasswordResetTokenHash::new("hash"),
+            user_id: UserId::new("user"),
+            created_at: now,
+            expires_at: now + Duration::minutes(30),
+        };
+        assert!(!reset.is_expired(now));
+        assert!(!reset.is_expired(now + Duration::minutes(29)));
+        assert!(reset.is_expired(now + Duration::minutes(30)));
+    }
+
+    #[test]
+    fn redacted() {
+        let token = PasswordResetToken::new("secret");
+        let debug = format!("{token:?}");
+        assert!(!debug.contains("secret"));
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+use super::{name_history::NameChange, user::User};
+
+/// All personal data which the system holds about the user.
+///
+/// Secrets such as password hash are never included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalData {
+    /// The user itself with all of its data.
+    pub user: User,
+    /// Time of the last password change, if the user has a password.
+    pub password_updated_at: Option<DateTime<Utc>>,
+    /// Former names of the user, from newest to oldest.
+    pub name_history: Vec<NameChange>,
+    /// Personal data of the user which is held by other services of the system.
+    pub external: Vec<PersonalDataEntry>,
+}
+
+/// Personal data of the user which is held by other service of the system,
+/// such as workspace memberships or notifications.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PersonalDataEntry {
+    /// Name of the data, such as `memberships` or `notifications`.
+    pub name: String,
+    /// Content of the data in a format chosen by its source.
+    pub content: Vec<u8>,
+}
@@ -0,0 +1,174 @@
+use std::borrow::{Borrow, Cow};
+
+use derive_more::{Display, Error};
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
+use typed_builder::TypedBuilder;
+
+/// Pronouns of the user in the system, such as `they/them`.
+///
+/// These requirements are:
+/// - must not be empty;
+/// - must not be larger than 32 characters in length;
+/// - must contain letters, spaces or `/`, `-`, `'` characters only.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pronouns(String);
+
+impl Pronouns {
+    /// Creates new user pronouns from input string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if input string does not match user pronouns requirements.
+    pub fn new(pronouns: impl Into<String>) -> Result<Self, PronounsError> {
+        const MIN_LENGTH: usize = 1;
+        const MAX_LENGTH: usize = 32;
+
+        let pronouns = pronouns.into();
+        let length = pronouns.chars().count();
+        if length < MIN_LENGTH {
+            let error = PronounsError::TooShort {
+                min: MIN_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if length > MAX_LENGTH {
+            let error = PronounsError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+
+        let invalid_character = pronouns
+            .chars()
+            .enumerate()
+            .find(|&(_, ch)| !ch.is_alphabetic() && !matches!(ch, ' ' | '/' | '-' | '\''));
+        if let Some((position, ch)) = invalid_character {
+            return Err(PronounsError::InvalidCharacter { ch, position });
+        }
+        Ok(Self(pronouns))
+    }
+
+    /// Extracts string slice from a user pronouns.
+    pub fn as_str(&self) -> &str {
+        let Self(pronouns) = self;
+        pronouns.as_str()
+    }
+
+    /// Converts user pronouns into a string.
+    pub fn into_inner(self) -> String {
+        let Self(pronouns) = self;
+        pronouns
+    }
+}
+
+/// Type of error which is returned when input does not meet user pronouns requirements.
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PronounsError {
+    /// User pronouns have fewer characters than required.
+    #[display(
+        fmt = "user pronouns must have at least {} characters, got {}",
+        min,
+        actual
+    )]
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User pronouns have more characters than allowed.
+    #[display(
+        fmt = "user pronouns must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// User pronouns contain character which is not allowed.
+    #[display(
+        fmt = "user pronouns contain invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+}
+
+/// Filters for optional user pronouns of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionPronounsFilters<'a> {
+    /// Equality user pronouns filter.
+    pub eq: Option<Equal<Cow<'a, Option<Pronouns>>>>,
+    /// Inequality user pronouns filter.
+    pub ne: Option<NotEqual<Cow<'a, Option<Pronouns>>>>,
+    /// In user pronouns filter.
+    pub r#in: Option<In<Cow<'a, [Option<Pronouns>]>>>,
+    /// Not in user pronouns filter.
+    pub nin: Option<NotIn<Cow<'a, [Option<Pronouns>]>>>,
+    /// Regex user pronouns filter.
+    ///
+    /// Absent user pronouns never match the regex.
+    pub regex: Option<Regex<Cow<'a, str>>>,
+}
+
+impl<Input> Filter<Input> for OptionPronounsFilters<'_>
+where
+    Input: Borrow<Option<Pronouns>>,
+{
+    fn satisfies(&self, input: Input) -> bool {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = self;
+        let input = input.borrow();
+        eq.satisfies(Cow::Borrowed(input))
+            && ne.satisfies(Cow::Borrowed(input))
+            && r#in.as_ref().map(In::as_deref).satisfies(input)
+            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+            && match input {
+                Some(input) => regex.satisfies(input.as_str()),
+                None => regex.is_none(),
+            }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Pronouns, PronounsError};
+
+    #[test]
+    fn valid_ones() {
+        let Pronouns(_) = Pronouns::new("they/them").unwrap();
+        let Pronouns(_) = Pronouns::new("he/him").unwrap();
+        let Pronouns(_) = Pronouns::new("она/её").unwrap();
+    }
+
+    #[test]
+    fn invalid() {
+        let error = Pronouns::new("").unwrap_err();
+        assert_eq!(error, PronounsError::TooShort { min: 1, actual: 0 });
+        let error = Pronouns::new("<b>he</b>").unwrap_err();
+        let expected = PronounsError::InvalidCharacter {
+            ch: '<',
+            position: 0,
+        };
+        assert_eq!(error, expected);
+    }
+}
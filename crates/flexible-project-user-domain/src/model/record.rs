@@ -0,0 +1,169 @@
+use derive_more::{Display, Error, From};
+
+use super::{
+    avatar::{Avatar, AvatarError},
+    bio::{Bio, BioError},
+    display_name::{DisplayName, DisplayNameError},
+    email::{Email, EmailError},
+    locale::{Locale, LocaleError},
+    name::{Name, NameError},
+    pronouns::{Pronouns, PronounsError},
+    role::Role,
+    status::AccountStatus,
+    time_zone::{TimeZone, TimeZoneError},
+    user::UserData,
+};
+
+/// Unvalidated record of the user which is imported into or exported from the system in bulk.
+///
+/// Role and account status of the user are not part of the record,
+/// so imported users are always ordinary active users.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub struct UserRecord {
+    /// Unique name of the user.
+    pub name: String,
+    /// Display name of the user, if present.
+    ///
+    /// Name of the user is used as display name if absent,
+    /// so the name must meet display name requirements as well.
+    pub display_name: Option<String>,
+    /// Unique email of the user, if present.
+    pub email: Option<String>,
+    /// Avatar URL of the user, if present.
+    pub avatar: Option<String>,
+    /// Short Markdown biography of the user, if present.
+    pub bio: Option<String>,
+    /// Preferred locale of the user, if present.
+    pub locale: Option<String>,
+    /// Time zone of the user, if present.
+    pub time_zone: Option<String>,
+    /// Pronouns of the user, if present.
+    pub pronouns: Option<String>,
+}
+
+impl From<UserData> for UserRecord {
+    fn from(data: UserData) -> Self {
+        let UserData {
+            name,
+            display_name,
+            role: _,
+            email,
+            avatar,
+            status: _,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = data;
+        Self {
+            name: name.into_inner(),
+            display_name: Some(display_name.into_inner()),
+            email: email.map(Email::into_inner),
+            avatar: avatar.map(Avatar::into_inner),
+            bio: bio.map(Bio::into_inner),
+            locale: locale.map(Locale::into_inner),
+            time_zone: time_zone.map(TimeZone::into_inner),
+            pronouns: pronouns.map(Pronouns::into_inner),
+        }
+    }
+}
+
+impl TryFrom<UserRecord> for UserData {
+    type Error = UserRecordError;
+
+    fn try_from(record: UserRecord) -> Result<Self, Self::Error> {
+        let UserRecord {
+            name,
+            display_name,
+            email,
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = record;
+        let name = Name::new(name)?;
+        let display_name = match display_name {
+            Some(display_name) => DisplayName::new(display_name)?,
+            None => DisplayName::new(name.as_str())?,
+        };
+        let data = Self {
+            name,
+            display_name,
+            role: Role::User,
+            email: email.map(Email::new).transpose()?,
+            avatar: avatar.map(Avatar::new).transpose()?,
+            status: AccountStatus::Active,
+            bio: bio.map(Bio::new).transpose()?,
+            locale: locale.map(Locale::new).transpose()?,
+            time_zone: time_zone.map(TimeZone::new).transpose()?,
+            pronouns: pronouns.map(Pronouns::new).transpose()?,
+        };
+        Ok(data)
+    }
+}
+
+/// Type of error which is returned when user record does not meet domain requirements.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, From, Error)]
+pub enum UserRecordError {
+    /// Name does not meet domain requirements.
+    Name(NameError),
+    /// Display name does not meet domain requirements.
+    DisplayName(DisplayNameError),
+    /// Email does not meet domain requirements.
+    Email(EmailError),
+    /// Avatar does not meet domain requirements.
+    Avatar(AvatarError),
+    /// Bio does not meet domain requirements.
+    Bio(BioError),
+    /// Locale does not meet domain requirements.
+    Locale(LocaleError),
+    /// Time zone does not meet domain requirements.
+    TimeZone(TimeZoneError),
+    /// Pronouns do not meet domain requirements.
+    Pronouns(PronounsError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{UserRecord, UserRecordError};
+    use crate::model::{DisplayNameError, EmailError, Role, UserData};
+
+    #[test]
+    fn valid_ones() {
+        let record = UserRecord {
+            name: "tuguzT".to_owned(),
+            email: Some("tuguzT@example.com".to_owned()),
+            locale: Some("ru-ru".to_owned()),
+            ..Default::default()
+        };
+        let data = UserData::try_from(record).unwrap();
+        assert_eq!(data.display_name.as_str(), "tuguzT");
+        assert_eq!(data.role, Role::User);
+
+        let record = UserRecord::from(data);
+        assert_eq!(record.display_name.as_deref(), Some("tuguzT"));
+        assert_eq!(record.locale.as_deref(), Some("ru-RU"));
+    }
+
+    #[test]
+    fn invalid() {
+        let record = UserRecord {
+            name: "tuguzT".to_owned(),
+            email: Some("example.com".to_owned()),
+            ..Default::default()
+        };
+        let error = UserData::try_from(record).unwrap_err();
+        assert_eq!(error, UserRecordError::Email(EmailError::MissingSeparator));
+
+        let record = UserRecord {
+            name: "1234".to_owned(),
+            ..Default::default()
+        };
+        let error = UserData::try_from(record).unwrap_err();
+        assert_eq!(
+            error,
+            UserRecordError::DisplayName(DisplayNameError::NoLetter)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::borrow::{Borrow, Cow};
+
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn};
+use typed_builder::TypedBuilder;
+
+/// Status of the user account in the system.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Default)]
+pub enum AccountStatus {
+    /// Account is active and has no restrictions.
+    #[default]
+    Active,
+    /// Account is suspended until some point in time.
+    Suspended {
+        /// Time when suspension ends.
+        until: DateTime<Utc>,
+        /// Reason of the suspension.
+        reason: String,
+    },
+    /// Account is banned permanently.
+    Banned {
+        /// Reason of the ban.
+        reason: String,
+    },
+}
+
+impl AccountStatus {
+    /// Returns kind of the account status.
+    pub fn kind(&self) -> AccountStatusKind {
+        match self {
+            Self::Active => AccountStatusKind::Active,
+            Self::Suspended { .. } => AccountStatusKind::Suspended,
+            Self::Banned { .. } => AccountStatusKind::Banned,
+        }
+    }
+
+    /// Returns kind of the account status at provided time.
+    ///
+    /// Suspended account becomes active again when its suspension ends.
+    pub fn kind_at(&self, now: DateTime<Utc>) -> AccountStatusKind {
+        match self {
+            Self::Suspended { until, .. } if *until <= now => AccountStatusKind::Active,
+            status => status.kind(),
+        }
+    }
+
+    /// Checks if the account has no restrictions at provided time.
+    ///
+    /// Suspended account becomes active again when its suspension ends.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.kind_at(now) == AccountStatusKind::Active
+    }
+}
+
+/// Kind of the [account status](AccountStatus) without any data.
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum AccountStatusKind {
+    /// Account is active.
+    #[default]
+    Active,
+    /// Account is suspended.
+    Suspended,
+    /// Account is banned.
+    Banned,
+}
+
+/// Filters for user account status of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct AccountStatusFilters<'a> {
+    /// Equality account status kind filter.
+    pub eq: Option<Equal<Cow<'a, AccountStatusKind>>>,
+    /// Inequality account status kind filter.
+    pub ne: Option<NotEqual<Cow<'a, AccountStatusKind>>>,
+    /// In account status kind filter.
+    pub r#in: Option<In<Cow<'a, [AccountStatusKind]>>>,
+    /// Not in account status kind filter.
+    pub nin: Option<NotIn<Cow<'a, [AccountStatusKind]>>>,
+    /// Time at which [kind](AccountStatus::kind_at) of the account status is evaluated,
+    /// so that suspensions which ended before it are considered active.
+    ///
+    /// If `None`, all suspensions are considered ongoing.
+    /// Use cases which filter users set it to the current time.
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl<Input> Filter<Input> for AccountStatusFilters<'_>
+where
+    Input: Borrow<AccountStatus>,
+{
+    fn satisfies(&self, input: Input) -> bool {
+        let Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            at,
+        } = self;
+        let status = input.borrow();
+        let kind = match at {
+            Some(at) => status.kind_at(*at),
+            None => status.kind(),
+        };
+        eq.satisfies(Cow::Borrowed(&kind))
+            && ne.satisfies(Cow::Borrowed(&kind))
+            && r#in.as_ref().map(In::as_deref).satisfies(&kind)
+            && nin.as_ref().map(NotIn::as_deref).satisfies(&kind)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use chrono::{Duration, Utc};
+    use fp_filter::Filter;
+
+    use super::{AccountStatus, AccountStatusFilters, AccountStatusKind};
+
+    #[test]
+    fn activity() {
+        let now = Utc::now();
+        assert!(AccountStatus::Active.is_active(now));
+
+        let suspended = AccountStatus::Suspended {
+            until: now + Duration::days(1),
+            reason: "spam".into(),
+        };
+        assert!(!suspended.is_active(now));
+        assert!(suspended.is_active(now + Duration::days(1)));
+
+        let banned = AccountStatus::Banned {
+            reason: "spam".into(),
+        };
+        assert!(!banned.is_active(now));
+        assert!(!banned.is_active(now + Duration::days(365)));
+    }
+
+    #[test]
+    fn expired_suspension() {
+        let now = Utc::now();
+        let suspended = AccountStatus::Suspended {
+            until: now,
+            reason: "spam".into(),
+        };
+        assert_eq!(suspended.kind(), AccountStatusKind::Suspended);
+        assert_eq!(
+            suspended.kind_at(now - Duration::seconds(1)),
+            AccountStatusKind::Suspended,
+        );
+        assert_eq!(suspended.kind_at(now), AccountStatusKind::Active);
+
+        let filter = AccountStatusFilters::builder()
+            .eq(Cow::Owned(AccountStatusKind::Active))
+            .at(now)
+            .build();
+        assert!(filter.satisfies(&suspended));
+        assert!(filter.satisfies(AccountStatus::Active));
+        let filter = AccountStatusFilters::builder()
+            .eq(Cow::Owned(AccountStatusKind::Suspended))
+            .at(now - Duration::seconds(1))
+            .build();
+        assert!(filter.satisfies(&suspended));
+        let filter = AccountStatusFilters {
+            at: Some(now),
+            ..filter
+        };
+        assert!(!filter.satisfies(&suspended));
+    }
+
+    #[test]
+    fn filters() {
+        let banned = AccountStatus::Banned {
+            reason: "spam".into(),
+        };
+        let filter = AccountStatusFilters::builder()
+            .eq(Cow::Owned(AccountStatusKind::Banned))
+            .build();
+        assert!(filter.satisfies(&banned));
+        assert!(!filter.satisfies(AccountStatus::Active));
+
+        let filter = AccountStatusFilters::builder()
+            .ne(Cow::Owned(AccountStatusKind::Active))
+            .build();
+        assert!(filter.satisfies(&banned));
+        assert!(!filter.satisfies(AccountStatus::Active));
+    }
+}
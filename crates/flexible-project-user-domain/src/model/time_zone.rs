@@ -0,0 +1,116 @@
+use std::{
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+};
+
+use chrono_tz::Tz;
+use derive_more::{Display, Error};
+use fp_filter::{Equal, Filter, In, NotEqual, NotIn};
+use typed_builder::TypedBuilder;
+
+/// Time zone of the user in the system from [IANA time zone database](https://www.iana.org/time-zones),
+/// such as `Europe/Moscow` or `UTC`.
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq)]
+#[display(fmt = "{}", "_0.name()")]
+pub struct TimeZone(Tz);
+
+impl TimeZone {
+    /// Creates new user time zone from input string.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error
+    /// if input string is not a name of known IANA time zone.
+    pub fn new(time_zone: impl Into<String>) -> Result<Self, TimeZoneError> {
+        let time_zone = time_zone.into();
+        let time_zone = time_zone.parse().map_err(|_| TimeZoneError::Unknown)?;
+        Ok(Self(time_zone))
+    }
+
+    /// Extracts string slice from a user time zone.
+    pub fn as_str(&self) -> &'static str {
+        let Self(time_zone) = self;
+        time_zone.name()
+    }
+
+    /// Converts user time zone into a string.
+    pub fn into_inner(self) -> String {
+        self.as_str().to_owned()
+    }
+
+    /// Returns time zone which could be used to convert date and time
+    /// into local date and time of the user.
+    pub fn tz(&self) -> Tz {
+        let Self(time_zone) = self;
+        *time_zone
+    }
+}
+
+impl PartialOrd for TimeZone {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimeZone {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+/// Type of error which is returned when input does not meet user time zone requirements.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum TimeZoneError {
+    /// User time zone is not known.
+    #[display(fmt = "user time zone is not a known IANA time zone")]
+    Unknown,
+}
+
+/// Filters for optional user time zone of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionTimeZoneFilters<'a> {
+    /// Equality user time zone filter.
+    pub eq: Option<Equal<Cow<'a, Option<TimeZone>>>>,
+    /// Inequality user time zone filter.
+    pub ne: Option<NotEqual<Cow<'a, Option<TimeZone>>>>,
+    /// In user time zone filter.
+    pub r#in: Option<In<Cow<'a, [Option<TimeZone>]>>>,
+    /// Not in user time zone filter.
+    pub nin: Option<NotIn<Cow<'a, [Option<TimeZone>]>>>,
+}
+
+impl<Input> Filter<Input> for OptionTimeZoneFilters<'_>
+where
+    Input: Borrow<Option<TimeZone>>,
+{
+    fn satisfies(&self, input: Input) -> bool {
+        let Self { eq, ne, r#in, nin } = self;
+        let input = input.borrow();
+        eq.satisfies(Cow::Borrowed(input))
+            && ne.satisfies(Cow::Borrowed(input))
+            && r#in.as_ref().map(In::as_deref).satisfies(input)
+            && nin.as_ref().map(NotIn::as_deref).satisfies(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TimeZone, TimeZoneError};
+
+    #[test]
+    fn valid_ones() {
+        let time_zone = TimeZone::new("Europe/Moscow").unwrap();
+        assert_eq!(time_zone.as_str(), "Europe/Moscow");
+        let TimeZone(_) = TimeZone::new("UTC").unwrap();
+        let TimeZone(_) = TimeZone::new("America/Argentina/Buenos_Aires").unwrap();
+    }
+
+    #[test]
+    fn invalid() {
+        let error = TimeZone::new("Mars/Olympus_Mons").unwrap_err();
+        assert_eq!(error, TimeZoneError::Unknown);
+        let error = TimeZone::new("").unwrap_err();
+        assert_eq!(error, TimeZoneError::Unknown);
+    }
+}
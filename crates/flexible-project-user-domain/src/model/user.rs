@@ -3,16 +3,23 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use chrono::{DateTime, Utc};
 use fp_filter::Filter;
 use typed_builder::TypedBuilder;
 
 use super::{
+    actor::{Actor, UserAction},
     avatar::{Avatar, OptionAvatarFilters},
+    bio::{Bio, OptionBioFilters},
     display_name::{DisplayName, DisplayNameFilters},
     email::{Email, OptionEmailFilters},
     id::{UserId, UserIdFilters},
+    locale::{Locale, OptionLocaleFilters},
     name::{Name, NameFilters},
+    pronouns::{OptionPronounsFilters, Pronouns},
     role::{Role, RoleFilters},
+    status::{AccountStatus, AccountStatusFilters},
+    time_zone::{OptionTimeZoneFilters, TimeZone},
 };
 
 /// Model of user in the system.
@@ -38,6 +45,41 @@ impl Hash for User {
     }
 }
 
+/// User of the system as it is visible to the actor which reads it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VisibleUser {
+    /// All data of the user, including its email, account status and role.
+    Private(User),
+    /// Public data of the user only, so its email, account status and role must not be exposed.
+    ///
+    /// Email of the user is already cleared.
+    Public(User),
+}
+
+impl VisibleUser {
+    /// Hides private data of the user unless the reader is allowed
+    /// to [read](UserAction::Read) it, e.g. the user itself or an administrator.
+    ///
+    /// Reader is `None` if the user is read by an anonymous client.
+    pub fn new(user: User, reader: Option<&Actor>) -> Self {
+        match reader {
+            Some(reader) if reader.is_allowed(UserAction::Read, &user.id) => Self::Private(user),
+            _ => {
+                let mut user = user;
+                user.data.email = None;
+                Self::Public(user)
+            }
+        }
+    }
+
+    /// Returns the user regardless of its visibility.
+    pub fn into_user(self) -> User {
+        match self {
+            Self::Private(user) | Self::Public(user) => user,
+        }
+    }
+}
+
 /// Data of the user in the system.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct UserData {
@@ -51,6 +93,80 @@ pub struct UserData {
     pub email: Option<Email>,
     /// Avatar URL of the user, if present.
     pub avatar: Option<Avatar>,
+    /// Status of the user account.
+    pub status: AccountStatus,
+    /// Short Markdown biography of the user, if present.
+    pub bio: Option<Bio>,
+    /// Preferred locale of the user, if present.
+    pub locale: Option<Locale>,
+    /// Time zone of the user, if present.
+    pub time_zone: Option<TimeZone>,
+    /// Pronouns of the user, if present.
+    pub pronouns: Option<Pronouns>,
+}
+
+/// Partial change of the [user data](UserData).
+///
+/// Fields which are absent are left unchanged.
+/// Optional fields could be cleared by setting them to `None`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, TypedBuilder)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct UserPatch {
+    /// New unique name of the user.
+    pub name: Option<Name>,
+    /// New display name of the user.
+    pub display_name: Option<DisplayName>,
+    /// New role of the user.
+    pub role: Option<Role>,
+    /// New unique email of the user or `None` to remove it.
+    pub email: Option<Option<Email>>,
+    /// New avatar URL of the user or `None` to remove it.
+    pub avatar: Option<Option<Avatar>>,
+    /// New status of the user account.
+    pub status: Option<AccountStatus>,
+    /// New biography of the user or `None` to remove it.
+    pub bio: Option<Option<Bio>>,
+    /// New preferred locale of the user or `None` to remove it.
+    pub locale: Option<Option<Locale>>,
+    /// New time zone of the user or `None` to remove it.
+    pub time_zone: Option<Option<TimeZone>>,
+    /// New pronouns of the user or `None` to remove it.
+    pub pronouns: Option<Option<Pronouns>>,
+}
+
+impl UserPatch {
+    /// Checks if the patch does not change any field.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Applies the patch to provided user data.
+    pub fn apply(self, data: UserData) -> UserData {
+        let Self {
+            name,
+            display_name,
+            role,
+            email,
+            avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = self;
+        UserData {
+            name: name.unwrap_or(data.name),
+            display_name: display_name.unwrap_or(data.display_name),
+            role: role.unwrap_or(data.role),
+            email: email.unwrap_or(data.email),
+            avatar: avatar.unwrap_or(data.avatar),
+            status: status.unwrap_or(data.status),
+            bio: bio.unwrap_or(data.bio),
+            locale: locale.unwrap_or(data.locale),
+            time_zone: time_zone.unwrap_or(data.time_zone),
+            pronouns: pronouns.unwrap_or(data.pronouns),
+        }
+    }
 }
 
 /// Filters for user of the backend.
@@ -63,6 +179,26 @@ pub struct UserFilters<'a> {
     pub data: Option<UserDataFilters<'a>>,
 }
 
+impl UserFilters<'_> {
+    /// Checks if users are filtered by their private data: email, account status or role.
+    pub fn is_private(&self) -> bool {
+        let Some(data) = &self.data else {
+            return false;
+        };
+        data.email.is_some() || data.status.is_some() || data.role.is_some()
+    }
+
+    /// Evaluates account status filter of the user data (if any) at provided time,
+    /// so that suspensions which ended before it are considered active.
+    pub fn with_status_at(mut self, now: DateTime<Utc>) -> Self {
+        let status = self.data.as_mut().and_then(|data| data.status.as_mut());
+        if let Some(status) = status {
+            status.at = Some(now);
+        }
+        self
+    }
+}
+
 impl<Input> Filter<Input> for UserFilters<'_>
 where
     Input: Borrow<User>,
@@ -91,6 +227,16 @@ pub struct UserDataFilters<'a> {
     pub email: Option<OptionEmailFilters<'a>>,
     /// User avatar filters.
     pub avatar: Option<OptionAvatarFilters<'a>>,
+    /// User account status filters.
+    pub status: Option<AccountStatusFilters<'a>>,
+    /// User bio filters.
+    pub bio: Option<OptionBioFilters<'a>>,
+    /// User locale filters.
+    pub locale: Option<OptionLocaleFilters<'a>>,
+    /// User time zone filters.
+    pub time_zone: Option<OptionTimeZoneFilters<'a>>,
+    /// User pronouns filters.
+    pub pronouns: Option<OptionPronounsFilters<'a>>,
 }
 
 impl<Input> Filter<Input> for UserDataFilters<'_>
@@ -104,6 +250,11 @@ where
             role: role_filter,
             email: email_filter,
             avatar: avatar_filter,
+            status: status_filter,
+            bio: bio_filter,
+            locale: locale_filter,
+            time_zone: time_zone_filter,
+            pronouns: pronouns_filter,
         } = self;
         let UserData {
             name,
@@ -111,11 +262,55 @@ where
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = input.borrow();
         name_filter.satisfies(name)
             && display_name_filter.satisfies(display_name)
             && role_filter.satisfies(role)
             && email_filter.satisfies(email)
             && avatar_filter.satisfies(avatar)
+            && status_filter.satisfies(status)
+            && bio_filter.satisfies(bio)
+            && locale_filter.satisfies(locale)
+            && time_zone_filter.satisfies(time_zone)
+            && pronouns_filter.satisfies(pronouns)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{UserData, UserPatch};
+    use crate::model::{DisplayName, Email, Name, Role};
+
+    #[test]
+    fn apply_patch() {
+        let data = UserData {
+            name: Name::new("tanabe").unwrap(),
+            display_name: DisplayName::new("Tanabe").unwrap(),
+            role: Role::User,
+            email: Some(Email::new("tanabe@example.com").unwrap()),
+            avatar: None,
+            status: Default::default(),
+            bio: None,
+            locale: None,
+            time_zone: None,
+            pronouns: None,
+        };
+        assert!(UserPatch::default().is_empty());
+        assert_eq!(UserPatch::default().apply(data.clone()), data);
+
+        let patch = UserPatch::builder()
+            .role(Role::Moderator)
+            .email(None)
+            .build();
+        assert!(!patch.is_empty());
+        let patched = patch.apply(data.clone());
+        assert_eq!(patched.role, Role::Moderator);
+        assert_eq!(patched.email, None);
+        assert_eq!(patched.name, data.name);
     }
 }
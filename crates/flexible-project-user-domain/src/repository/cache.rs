@@ -0,0 +1,457 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    num::NonZeroUsize,
+    pin::pin,
+    sync::{Mutex, MutexGuard, PoisonError},
+    vec,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use futures::{
+    future::Either,
+    stream::{self, Iter},
+    TryStreamExt,
+};
+use lru::LruCache;
+use typed_builder::TypedBuilder;
+
+use crate::model::{Role, User, UserData, UserFilters, UserId, UserIdFilters, UserPatch};
+
+use super::{Clock, UserDatabase};
+
+/// Configuration of the [user cache](CachedUserDatabase).
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct UserCacheConfig {
+    /// Maximal count of users in the cache.
+    ///
+    /// Least recently used users are evicted when the cache is full.
+    #[builder(default = NonZeroUsize::new(1024).unwrap())]
+    pub capacity: NonZeroUsize,
+    /// Duration after which cached user is considered stale and is read again.
+    #[builder(default = Duration::minutes(5))]
+    pub ttl: Duration,
+}
+
+impl Default for UserCacheConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Snapshot of the [user cache](CachedUserDatabase) metrics.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct UserCacheMetrics {
+    /// Count of users which were read from the cache.
+    pub hits: u64,
+    /// Count of users which were requested by identifier but were not found in the cache.
+    pub misses: u64,
+}
+
+/// Read-through cache of users around any [user database](UserDatabase).
+///
+/// Only reads by user identifiers (filters with nothing but `eq` or `in` identifier filter)
+/// are served from the cache, other reads are passed to the inner database as is.
+/// Cached user is invalidated after it is updated, patched or deleted through the cache,
+/// so reads which race with the change cannot keep the previous user in the cache:
+/// each invalidation advances the epoch of the cache, and users read from the inner database
+/// are not cached if they were invalidated after the read started.
+///
+/// Users read by identifiers are returned in the order of requested identifiers.
+pub struct CachedUserDatabase<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    inner: Database,
+    clock: CurrentTime,
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+struct CacheState {
+    users: LruCache<UserId, CachedUser>,
+    metrics: UserCacheMetrics,
+    /// Count of invalidations of the cache.
+    epoch: u64,
+    /// Epochs of the latest invalidations of recently invalidated users.
+    invalidations: LruCache<UserId, u64>,
+    /// Latest epoch of invalidation which was evicted from the recent invalidations,
+    /// which is assumed for users without recent invalidation.
+    evicted_epoch: u64,
+}
+
+struct CachedUser {
+    data: UserData,
+    expires_at: DateTime<Utc>,
+}
+
+impl<Database, CurrentTime> CachedUserDatabase<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new cache around provided user database.
+    pub fn new(inner: Database, clock: CurrentTime, config: UserCacheConfig) -> Self {
+        let UserCacheConfig { capacity, ttl } = config;
+        let state = CacheState {
+            users: LruCache::new(capacity),
+            metrics: UserCacheMetrics::default(),
+            epoch: 0,
+            invalidations: LruCache::new(capacity),
+            evicted_epoch: 0,
+        };
+        Self {
+            inner,
+            clock,
+            ttl,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Returns current metrics of the cache.
+    pub fn metrics(&self) -> UserCacheMetrics {
+        self.lock().metrics
+    }
+
+    /// Removes user by provided identifier from the cache,
+    /// e.g. if it was changed bypassing the cache.
+    pub fn invalidate(&self, id: &UserId) {
+        let mut state = self.lock();
+        state.epoch += 1;
+        let epoch = state.epoch;
+        state.users.pop(id);
+        if let Some((evicted_id, evicted)) = state.invalidations.push(id.clone(), epoch) {
+            if &evicted_id != id {
+                state.evicted_epoch = state.evicted_epoch.max(evicted);
+            }
+        }
+    }
+
+    /// Removes all users from the cache.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.epoch += 1;
+        state.evicted_epoch = state.epoch;
+        state.invalidations.clear();
+        state.users.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn epoch(&self) -> u64 {
+        self.lock().epoch
+    }
+
+    /// Stores the user in the cache unless it was invalidated after provided epoch.
+    fn store(&self, user: &User, epoch: u64) {
+        let User { id, data } = user;
+        let cached = CachedUser {
+            data: data.clone(),
+            expires_at: self.clock.now() + self.ttl,
+        };
+        let mut state = self.lock();
+        let invalidated_at = match state.invalidations.peek(id) {
+            Some(&invalidated_at) => invalidated_at,
+            None => state.evicted_epoch,
+        };
+        if invalidated_at <= epoch {
+            state.users.put(id.clone(), cached);
+        }
+    }
+
+    /// Splits provided identifiers into users found in the cache and identifiers of missing users.
+    ///
+    /// Also returns current epoch of the cache, after which missing users should not be invalidated
+    /// to be stored in the cache.
+    fn lookup(&self, ids: &[UserId]) -> (HashMap<UserId, User>, Vec<UserId>, u64) {
+        let now = self.clock.now();
+        let mut state = self.lock();
+        let CacheState {
+            users,
+            metrics,
+            epoch,
+            ..
+        } = &mut *state;
+
+        let mut found = HashMap::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        for id in ids {
+            match users.get(id) {
+                Some(CachedUser { data, expires_at }) if now < *expires_at => {
+                    let user = User {
+                        id: id.clone(),
+                        data: data.clone(),
+                    };
+                    found.insert(id.clone(), user);
+                }
+                Some(_) => {
+                    users.pop(id);
+                    missing.push(id.clone());
+                }
+                None => missing.push(id.clone()),
+            }
+        }
+        metrics.hits += found.len() as u64;
+        metrics.misses += missing.len() as u64;
+        (found, missing, *epoch)
+    }
+}
+
+/// Returns unique identifiers of the filter if it filters users by identifiers only.
+fn requested_ids(filter: &UserFilters<'_>) -> Option<Vec<UserId>> {
+    let UserFilters {
+        id: Some(id),
+        data: None,
+    } = filter
+    else {
+        return None;
+    };
+    let UserIdFilters {
+        eq, ne, r#in, nin, ..
+    } = id;
+    let ids = match (eq, ne, r#in, nin) {
+        (Some(eq), None, None, None) => vec![eq.0.as_ref().clone()],
+        (None, None, Some(r#in), None) => r#in.0.to_vec(),
+        _ => return None,
+    };
+    let mut unique = HashSet::with_capacity(ids.len());
+    let ids = ids.into_iter().filter(|id| unique.insert(id.clone()));
+    Some(ids.collect())
+}
+
+/// Stream of filtered users from the [user cache](CachedUserDatabase).
+pub type CachedUsers<Users, Error> = Either<Iter<vec::IntoIter<Result<User, Error>>>, Users>;
+
+#[async_trait(?Send)]
+impl<Database, CurrentTime> UserDatabase for CachedUserDatabase<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    type Error = Database::Error;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let epoch = self.epoch();
+        let user = self.inner.create(id, data).await?;
+        self.store(&user, epoch);
+        Ok(user)
+    }
+
+    type Users = CachedUsers<Database::Users, Database::Error>;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let Some(ids) = requested_ids(&filter) else {
+            let users = self.inner.read(filter).await?;
+            return Ok(Either::Right(users));
+        };
+
+        let (mut found, missing, epoch) = self.lookup(&ids);
+        if !missing.is_empty() {
+            let filter = {
+                let id = UserIdFilters::builder().r#in(Cow::Owned(missing)).build();
+                UserFilters::builder().id(id).build()
+            };
+            let users = self.inner.read(filter).await?;
+            let mut users = pin!(users);
+            while let Some(user) = users.try_next().await? {
+                self.store(&user, epoch);
+                found.insert(user.id.clone(), user);
+            }
+        }
+        let users: Vec<_> = ids
+            .into_iter()
+            .filter_map(|id| found.remove(&id))
+            .map(Ok)
+            .collect();
+        Ok(Either::Left(stream::iter(users)))
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        self.inner.count(filter).await
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        self.inner.count_by_role(filter).await
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let user = self.inner.update(id.clone(), data).await;
+        self.invalidate(&id);
+        user
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let user = self.inner.patch(id.clone(), patch).await;
+        self.invalidate(&id);
+        user
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        let user = self.inner.delete(id.clone()).await;
+        self.invalidate(&id);
+        user
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, num::NonZeroUsize, pin::pin, slice};
+
+    use chrono::Duration;
+    use futures::{executor::block_on, join, TryStreamExt};
+
+    use super::{CachedUserDatabase, UserCacheConfig, UserCacheMetrics};
+    use crate::{
+        model::{Name, Role, User, UserFilters, UserId, UserIdFilters, UserPatch},
+        repository::{Clock, UserDatabase},
+        use_case::fixture::{database, user_data, ManualClock, YieldingDatabase},
+    };
+
+    async fn read_ids_async<Database>(database: &Database, ids: &[&str]) -> Vec<User>
+    where
+        Database: UserDatabase,
+        Database::Error: std::fmt::Debug,
+    {
+        let ids: Vec<_> = ids.iter().copied().map(UserId::new).collect();
+        let filter = {
+            let id = UserIdFilters::builder().r#in(Cow::Owned(ids)).build();
+            UserFilters::builder().id(id).build()
+        };
+        let users = database.read(filter).await.unwrap();
+        let users = pin!(users);
+        users.try_collect().await.unwrap()
+    }
+
+    fn read_ids<Database>(database: &Database, ids: &[&str]) -> Vec<User>
+    where
+        Database: UserDatabase,
+        Database::Error: std::fmt::Debug,
+    {
+        let ids: Vec<_> = ids.iter().copied().map(UserId::new).collect();
+        let filter = {
+            let id = UserIdFilters::builder().r#in(Cow::Owned(ids)).build();
+            UserFilters::builder().id(id).build()
+        };
+        block_on(async {
+            let users = database.read(filter).await?;
+            let users = pin!(users);
+            users.try_collect().await
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn read_through() {
+        let inner = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let clock = ManualClock::default();
+        let config = UserCacheConfig::builder().ttl(Duration::minutes(1)).build();
+        let cache = CachedUserDatabase::new(inner.clone(), &clock, config);
+
+        assert_eq!(read_ids(&cache, &["tanabe", "unknown"]).len(), 1);
+        assert_eq!(cache.metrics(), UserCacheMetrics { hits: 0, misses: 2 });
+        assert_eq!(read_ids(&cache, &["tanabe", "kotlinist"]).len(), 2);
+        assert_eq!(cache.metrics(), UserCacheMetrics { hits: 1, misses: 3 });
+
+        let id = UserId::new("tanabe");
+        block_on(inner.update(id.clone(), user_data("flexible", Role::User))).unwrap();
+        let users = read_ids(&cache, &["tanabe"]);
+        assert_eq!(users[0].data.name.as_str(), "tanabe");
+
+        clock.0.set(clock.now() + Duration::minutes(1));
+        let users = read_ids(&cache, &["tanabe"]);
+        assert_eq!(users[0].data.name.as_str(), "flexible");
+        assert_eq!(cache.metrics(), UserCacheMetrics { hits: 2, misses: 4 });
+
+        block_on(cache.update(id, user_data("renamed", Role::User))).unwrap();
+        let users = read_ids(&cache, &["tanabe"]);
+        assert_eq!(users[0].data.name.as_str(), "renamed");
+        assert_eq!(cache.metrics(), UserCacheMetrics { hits: 2, misses: 5 });
+    }
+
+    #[test]
+    fn invalidate_after_change() {
+        let inner = YieldingDatabase(database([("tanabe", Role::User)]));
+        let clock = ManualClock::default();
+        let cache = CachedUserDatabase::new(inner, &clock, UserCacheConfig::default());
+        let id = UserId::new("tanabe");
+
+        let (user, users) = block_on(async {
+            join!(
+                cache.update(id.clone(), user_data("flexible", Role::User)),
+                read_ids_async(&cache, &["tanabe"]),
+            )
+        });
+        assert_eq!(user.unwrap().data.name.as_str(), "flexible");
+        assert_eq!(users[0].data.name.as_str(), "tanabe");
+        let users = read_ids(&cache, &["tanabe"]);
+        assert_eq!(users[0].data.name.as_str(), "flexible");
+
+        let patch = UserPatch::builder()
+            .name(Name::new("renamed").unwrap())
+            .build();
+        let (user, _) = block_on(async {
+            join!(
+                cache.patch(id.clone(), patch),
+                read_ids_async(&cache, &["tanabe"]),
+            )
+        });
+        assert!(user.unwrap().is_some());
+        let users = read_ids(&cache, &["tanabe"]);
+        assert_eq!(users[0].data.name.as_str(), "renamed");
+
+        let (user, _) =
+            block_on(async { join!(cache.delete(id), read_ids_async(&cache, &["tanabe"])) });
+        assert!(user.is_ok());
+        assert!(read_ids(&cache, &["tanabe"]).is_empty());
+    }
+
+    #[test]
+    fn request_order() {
+        let inner = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let clock = ManualClock::default();
+        let cache = CachedUserDatabase::new(inner, &clock, UserCacheConfig::default());
+
+        assert_eq!(read_ids(&cache, &["kotlinist"]).len(), 1);
+        let users = read_ids(&cache, &["tanabe", "unknown", "kotlinist", "tanabe"]);
+        let names: Vec<_> = users.iter().map(|user| user.data.name.as_str()).collect();
+        assert_eq!(names, ["tanabe", "kotlinist"]);
+        assert_eq!(cache.metrics(), UserCacheMetrics { hits: 1, misses: 3 });
+    }
+
+    #[test]
+    fn store_after_invalidation() {
+        let inner = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let clock = ManualClock::default();
+        let config = UserCacheConfig::builder()
+            .capacity(NonZeroUsize::new(1).unwrap())
+            .build();
+        let cache = CachedUserDatabase::new(inner, &clock, config);
+        let (tanabe, kotlinist) = (UserId::new("tanabe"), UserId::new("kotlinist"));
+        let user = User {
+            id: tanabe.clone(),
+            data: user_data("tanabe", Role::User),
+        };
+        let is_cached = |id: &UserId| !cache.lookup(slice::from_ref(id)).0.is_empty();
+
+        let (_, _, epoch) = cache.lookup(slice::from_ref(&tanabe));
+        cache.invalidate(&tanabe);
+        cache.store(&user, epoch);
+        assert!(!is_cached(&tanabe));
+
+        let (_, _, epoch) = cache.lookup(slice::from_ref(&tanabe));
+        cache.store(&user, epoch);
+        assert!(is_cached(&tanabe));
+
+        let (_, _, epoch) = cache.lookup(slice::from_ref(&tanabe));
+        cache.invalidate(&tanabe);
+        cache.invalidate(&kotlinist);
+        cache.store(&user, epoch);
+        assert!(!is_cached(&tanabe));
+    }
+}
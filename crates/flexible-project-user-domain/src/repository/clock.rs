@@ -0,0 +1,9 @@
+use auto_impl::auto_impl;
+use chrono::{DateTime, Utc};
+
+/// Source of the current time of the system.
+#[auto_impl(&, &mut, Box, Rc, Arc)]
+pub trait Clock {
+    /// Returns current time of the system.
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::{UserCredentials, UserId};
+
+/// Database of user credentials.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait CredentialsDatabase {
+    /// The type returned when a repository fails to apply an operation.
+    type Error;
+
+    /// Finds credentials of the user by provided identifier.
+    ///
+    /// Returns `None` if the user has no credentials yet.
+    async fn read(&self, id: UserId) -> Result<Option<UserCredentials>, Self::Error>;
+
+    /// Creates or replaces credentials of the user by provided identifier.
+    ///
+    /// Returns new credentials of the user.
+    async fn upsert(
+        &self,
+        id: UserId,
+        credentials: UserCredentials,
+    ) -> Result<UserCredentials, Self::Error>;
+
+    /// Deletes credentials of the user by provided identifier.
+    ///
+    /// Returns deleted credentials or `None` if the user had no credentials.
+    async fn delete(&self, id: UserId) -> Result<Option<UserCredentials>, Self::Error>;
+}
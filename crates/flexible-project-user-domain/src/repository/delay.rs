@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use chrono::Duration;
+
+/// Asynchronous delay of the current task.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait Delay {
+    /// Completes after provided duration has elapsed.
+    async fn delay(&self, duration: Duration);
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::UserEvent;
+
+/// Publisher of user events which are consumed by other services of the system.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait PublishUserEvent {
+    /// Type of error which is returned when publisher fails to publish an event.
+    type Error;
+
+    /// Publishes provided event, so other services could consume it.
+    async fn publish(&self, event: UserEvent) -> Result<(), Self::Error>;
+}
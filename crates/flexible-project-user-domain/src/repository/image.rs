@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::ImageFormat;
+
+/// Processor of the images uploaded into the system.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait ResizeImage {
+    /// Type of error which is returned when image cannot be processed.
+    type Error;
+
+    /// Crops the image to the square from its center and resizes it to provided size.
+    ///
+    /// Returns content of the resized image in PNG format
+    /// or an error if the image cannot be decoded.
+    async fn resize_square(
+        &self,
+        content: Arc<[u8]>,
+        format: ImageFormat,
+        size: u32,
+    ) -> Result<Vec<u8>, Self::Error>;
+}
@@ -0,0 +1,223 @@
+use std::{collections::BTreeMap, future::Future, time::Instant};
+
+use async_trait::async_trait;
+use fp_core::metrics::RepositoryMetrics;
+use tracing::Instrument;
+
+use crate::model::{Role, User, UserData, UserDataFilters, UserFilters, UserId, UserPatch};
+
+use super::UserDatabase;
+
+/// Decorator of any [user database](UserDatabase) which traces its operations
+/// and records their [metrics](RepositoryMetrics).
+///
+/// Each operation is traced in its own span with operation name,
+/// identifier of the user or summary of the filter.
+/// Summary of the filter contains only names of filtered fields, but never their values.
+///
+/// Errors which are produced by the stream of read users are not recorded.
+pub struct InstrumentedUserDatabase<Database>
+where
+    Database: UserDatabase,
+{
+    inner: Database,
+    metrics: RepositoryMetrics,
+}
+
+impl<Database> InstrumentedUserDatabase<Database>
+where
+    Database: UserDatabase,
+{
+    /// Creates new decorator around provided user database
+    /// which records metrics into provided repository metrics.
+    pub fn new(inner: Database, metrics: RepositoryMetrics) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Returns metrics which operations of the database are recorded into.
+    pub fn metrics(&self) -> &RepositoryMetrics {
+        &self.metrics
+    }
+
+    async fn observe<T>(
+        &self,
+        operation: &'static str,
+        future: impl Future<Output = Result<T, Database::Error>>,
+    ) -> Result<T, Database::Error> {
+        let start = Instant::now();
+        let result = future.await;
+        let latency = start.elapsed();
+        self.metrics.record(operation, latency, result.is_err());
+        match &result {
+            Ok(_) => tracing::debug!(?latency, "user database operation succeeded"),
+            Err(_) => tracing::warn!(?latency, "user database operation failed"),
+        }
+        result
+    }
+}
+
+/// Summarizes which fields of the user are filtered without revealing filter values.
+fn filter_summary(filter: &UserFilters<'_>) -> String {
+    let UserFilters { id, data } = filter;
+    let mut fields = Vec::new();
+    if let Some(id) = id {
+        match &id.r#in {
+            Some(ids) => fields.push(format!("id[{}]", ids.0.len())),
+            None => fields.push("id".to_owned()),
+        }
+    }
+    if let Some(data) = data {
+        let UserDataFilters {
+            name,
+            display_name,
+            role,
+            email,
+            avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+        } = data;
+        let data_fields = [
+            ("name", name.is_some()),
+            ("display_name", display_name.is_some()),
+            ("role", role.is_some()),
+            ("email", email.is_some()),
+            ("avatar", avatar.is_some()),
+            ("status", status.is_some()),
+            ("bio", bio.is_some()),
+            ("locale", locale.is_some()),
+            ("time_zone", time_zone.is_some()),
+            ("pronouns", pronouns.is_some()),
+        ];
+        let data_fields = data_fields
+            .into_iter()
+            .filter(|&(_, is_filtered)| is_filtered)
+            .map(|(field, _)| format!("data.{field}"));
+        fields.extend(data_fields);
+    }
+    if fields.is_empty() {
+        return "*".to_owned();
+    }
+    fields.join(",")
+}
+
+#[async_trait(?Send)]
+impl<Database> UserDatabase for InstrumentedUserDatabase<Database>
+where
+    Database: UserDatabase,
+{
+    type Error = Database::Error;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let span = tracing::info_span!("user_database", operation = "create", user_id = %id);
+        self.observe("create", self.inner.create(id, data))
+            .instrument(span)
+            .await
+    }
+
+    type Users = Database::Users;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let summary = filter_summary(&filter);
+        let span = tracing::info_span!("user_database", operation = "read", filter = %summary);
+        self.observe("read", self.inner.read(filter))
+            .instrument(span)
+            .await
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        let summary = filter_summary(&filter);
+        let span = tracing::info_span!("user_database", operation = "count", filter = %summary);
+        self.observe("count", self.inner.count(filter))
+            .instrument(span)
+            .await
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        let summary = filter_summary(&filter);
+        let span = tracing::info_span!(
+            "user_database",
+            operation = "count_by_role",
+            filter = %summary,
+        );
+        self.observe("count_by_role", self.inner.count_by_role(filter))
+            .instrument(span)
+            .await
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let span = tracing::info_span!("user_database", operation = "update", user_id = %id);
+        self.observe("update", self.inner.update(id, data))
+            .instrument(span)
+            .await
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let span = tracing::info_span!("user_database", operation = "patch", user_id = %id);
+        self.observe("patch", self.inner.patch(id, patch))
+            .instrument(span)
+            .await
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        let span = tracing::info_span!("user_database", operation = "delete", user_id = %id);
+        self.observe("delete", self.inner.delete(id))
+            .instrument(span)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use fp_core::metrics::RepositoryMetrics;
+    use futures::executor::block_on;
+
+    use super::{filter_summary, InstrumentedUserDatabase};
+    use crate::{
+        model::{Name, NameFilters, Role, UserDataFilters, UserFilters, UserId, UserIdFilters},
+        repository::UserDatabase,
+        use_case::fixture::{database, user_data},
+    };
+
+    #[test]
+    fn summary() {
+        assert_eq!(filter_summary(&UserFilters::default()), "*");
+
+        let ids = vec![UserId::new("tanabe"), UserId::new("kotlinist")];
+        let id = UserIdFilters::builder().r#in(Cow::Owned(ids)).build();
+        let name = Name::new("tanabe").unwrap();
+        let name = NameFilters::builder().eq(Cow::Owned(name)).build();
+        let data = UserDataFilters::builder().name(name).build();
+        let filter = UserFilters::builder().id(id).data(data).build();
+        let summary = filter_summary(&filter);
+        assert_eq!(summary, "id[2],data.name");
+        assert!(!summary.contains("tanabe"));
+    }
+
+    #[test]
+    fn metrics() {
+        let inner = database([("tanabe", Role::User)]);
+        let database = InstrumentedUserDatabase::new(inner, RepositoryMetrics::new());
+
+        let id = UserId::new("tanabe");
+        assert!(block_on(database.update(id.clone(), user_data("flexible", Role::User))).is_ok());
+        assert!(block_on(database.delete(id.clone())).is_ok());
+        assert!(block_on(database.delete(id)).is_err());
+        assert!(block_on(database.count(UserFilters::default())).is_ok());
+
+        let metrics = database.metrics().snapshot();
+        assert_eq!(metrics["update"].calls, 1);
+        assert_eq!(metrics["update"].errors, 0);
+        assert_eq!(metrics["delete"].calls, 2);
+        assert_eq!(metrics["delete"].errors, 1);
+        assert_eq!(metrics["delete"].latency.count(), 2);
+        assert_eq!(metrics["count"].calls, 1);
+        assert!(!metrics.contains_key("read"));
+    }
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::Mail;
+
+/// Sender of mail from the system to its users.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait Mailer {
+    /// Type of error which is returned when mailer fails to send mail.
+    type Error;
+
+    /// Sends provided mail to its recipient.
+    async fn send(&self, mail: Mail) -> Result<(), Self::Error>;
+}
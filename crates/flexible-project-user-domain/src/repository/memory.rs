@@ -0,0 +1,275 @@
+//! In-memory implementations of the user microservice databases.
+//!
+//! Intended for tests and for running the user service without MongoDB.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    convert::Infallible,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use derive_more::{Display, Error};
+use fp_filter::Filter;
+use futures::stream::{self, Iter};
+use indexmap::IndexMap;
+
+use crate::model::{Email, Name, NameChange, Role, User, UserData, UserFilters, UserId, UserPatch};
+
+use super::{NameHistoryDatabase, UserConflict, UserDatabase, UserDatabaseError};
+
+type Users = IndexMap<UserId, UserData>;
+
+/// In-memory database of user data.
+///
+/// Clones of the database share the same users.
+/// Users are read in order of their creation.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryUserDatabase {
+    users: Arc<Mutex<Users>>,
+}
+
+impl MemoryUserDatabase {
+    /// Creates new empty in-memory user database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new in-memory user database with provided users.
+    ///
+    /// Returns an error if provided users violate uniqueness
+    /// of user identifiers, names or emails.
+    pub fn with_users<I>(users: I) -> Result<Self, MemoryUserDatabaseError>
+    where
+        I: IntoIterator<Item = User>,
+    {
+        let database = Self::new();
+        {
+            let mut state = database.lock();
+            for User { id, data } in users {
+                insert(&mut state, id, data)?;
+            }
+        }
+        Ok(database)
+    }
+
+    /// Returns all users of the database in order of their creation.
+    pub fn users(&self) -> Vec<User> {
+        let state = self.lock();
+        state
+            .iter()
+            .map(|(id, data)| User {
+                id: id.clone(),
+                data: data.clone(),
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Users> {
+        self.users.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Checks if provided user data does not conflict with data of other users
+/// the same way as unique indexes of the local database do.
+fn check_conflict(users: &Users, id: &UserId, data: &UserData) -> Result<(), UserConflict> {
+    let name = data.name.canonical();
+    let email = data.email.as_ref().map(Email::canonical);
+    let others = users.iter().filter(|&(other_id, _)| other_id != id);
+    for (_, other) in others {
+        if other.name.canonical() == name {
+            return Err(UserConflict::Name);
+        }
+        if email.is_some() && other.email.as_ref().map(Email::canonical) == email {
+            return Err(UserConflict::Email);
+        }
+    }
+    Ok(())
+}
+
+fn insert(users: &mut Users, id: UserId, data: UserData) -> Result<User, MemoryUserDatabaseError> {
+    if users.contains_key(&id) {
+        return Err(MemoryUserDatabaseError::AlreadyExists(id));
+    }
+    check_conflict(users, &id, &data)?;
+    users.insert(id.clone(), data.clone());
+    Ok(User { id, data })
+}
+
+fn filter_users<'a>(
+    users: &'a Users,
+    filter: &'a UserFilters<'_>,
+) -> impl Iterator<Item = User> + 'a {
+    users
+        .iter()
+        .map(|(id, data)| User {
+            id: id.clone(),
+            data: data.clone(),
+        })
+        .filter(|user| filter.satisfies(user))
+}
+
+#[async_trait(?Send)]
+impl UserDatabase for MemoryUserDatabase {
+    type Error = MemoryUserDatabaseError;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let mut state = self.lock();
+        insert(&mut state, id, data)
+    }
+
+    type Users = MemoryUsers;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let state = self.lock();
+        let users: Vec<_> = filter_users(&state, &filter).map(Ok).collect();
+        Ok(stream::iter(users))
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        let state = self.lock();
+        let count = filter_users(&state, &filter).count();
+        Ok(count as u64)
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        let state = self.lock();
+        let mut counts = BTreeMap::new();
+        for user in filter_users(&state, &filter) {
+            *counts.entry(user.data.role).or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let mut state = self.lock();
+        if !state.contains_key(&id) {
+            return Err(MemoryUserDatabaseError::NoUser(id));
+        }
+        check_conflict(&state, &id, &data)?;
+        state.insert(id.clone(), data.clone());
+        Ok(User { id, data })
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let mut state = self.lock();
+        let Some(data) = state.get(&id) else {
+            return Ok(None);
+        };
+        let data = patch.apply(data.clone());
+        check_conflict(&state, &id, &data)?;
+        state.insert(id.clone(), data.clone());
+        Ok(Some(User { id, data }))
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        let mut state = self.lock();
+        match state.shift_remove(&id) {
+            Some(data) => Ok(User { id, data }),
+            None => Err(MemoryUserDatabaseError::NoUser(id)),
+        }
+    }
+}
+
+/// Stream of filtered user data from in-memory database.
+pub type MemoryUsers = Iter<std::vec::IntoIter<Result<User, MemoryUserDatabaseError>>>;
+
+/// Type of error which is returned on in-memory user database failure.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+pub enum MemoryUserDatabaseError {
+    /// User with provided identifier already exists.
+    #[display(fmt = r#"user already exists by identifier "{}""#, _0)]
+    AlreadyExists(#[error(not(source))] UserId),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    NoUser(#[error(not(source))] UserId),
+    /// User with the same name or email already exists.
+    #[display(fmt = "{}", _0)]
+    Conflict(#[error(not(source))] UserConflict),
+}
+
+impl From<UserConflict> for MemoryUserDatabaseError {
+    fn from(conflict: UserConflict) -> Self {
+        Self::Conflict(conflict)
+    }
+}
+
+impl UserDatabaseError for MemoryUserDatabaseError {
+    fn conflict(&self) -> Option<UserConflict> {
+        match self {
+            Self::Conflict(conflict) => Some(*conflict),
+            _ => None,
+        }
+    }
+}
+
+/// In-memory database of user name changes.
+///
+/// Clones of the database share the same name changes.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryNameHistoryDatabase {
+    changes: Arc<Mutex<Vec<NameChange>>>,
+}
+
+impl MemoryNameHistoryDatabase {
+    /// Creates new empty in-memory name history database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<NameChange>> {
+        self.changes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait(?Send)]
+impl NameHistoryDatabase for MemoryNameHistoryDatabase {
+    type Error = Infallible;
+
+    async fn create(&self, change: NameChange) -> Result<NameChange, Self::Error> {
+        let mut state = self.lock();
+        state.push(change.clone());
+        Ok(change)
+    }
+
+    async fn find_latest_by_name(&self, name: &Name) -> Result<Option<NameChange>, Self::Error> {
+        let state = self.lock();
+        let name = name.canonical();
+        let change = state
+            .iter()
+            .filter(|change| change.name.canonical() == name)
+            .max_by_key(|change| change.changed_at)
+            .cloned();
+        Ok(change)
+    }
+
+    async fn read_by_user(&self, user_id: UserId) -> Result<Vec<NameChange>, Self::Error> {
+        let state = self.lock();
+        let mut changes: Vec<_> = state
+            .iter()
+            .filter(|change| change.user_id == user_id)
+            .cloned()
+            .collect();
+        changes.sort_by_key(|change| Reverse(change.changed_at));
+        Ok(changes)
+    }
+
+    async fn delete(&self, change: NameChange) -> Result<bool, Self::Error> {
+        let mut state = self.lock();
+        let index = state.iter().position(|other| other == &change);
+        if let Some(index) = index {
+            state.remove(index);
+        }
+        Ok(index.is_some())
+    }
+
+    async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error> {
+        let mut state = self.lock();
+        let count = state.len();
+        state.retain(|change| change.user_id != user_id);
+        Ok((count - state.len()) as u64)
+    }
+}
@@ -1,6 +1,42 @@
 //! Definitions and utilities for objects which have access to the outer environment.
 
-pub use self::{id::GenerateUserId, user::UserDatabase};
+pub use self::{
+    cache::{CachedUserDatabase, CachedUsers, UserCacheConfig, UserCacheMetrics},
+    clock::Clock,
+    credentials::CredentialsDatabase,
+    delay::Delay,
+    event::PublishUserEvent,
+    id::GenerateUserId,
+    image::ResizeImage,
+    instrument::InstrumentedUserDatabase,
+    mailer::Mailer,
+    name_history::NameHistoryDatabase,
+    object_store::ObjectStore,
+    password::HashPassword,
+    password_reset::{GeneratePasswordResetToken, PasswordResetDatabase},
+    personal_data::PersonalDataSource,
+    record::UserRecordFormat,
+    resilient::{ResilienceConfig, ResilientError, ResilientUserDatabase, ResilientUsers},
+    user::{UserConflict, UserDatabase, UserDatabaseError},
+};
 
+#[cfg(any(test, feature = "memory"))]
+pub mod memory;
+
+mod cache;
+mod clock;
+mod credentials;
+mod delay;
+mod event;
 mod id;
+mod image;
+mod instrument;
+mod mailer;
+mod name_history;
+mod object_store;
+mod password;
+mod password_reset;
+mod personal_data;
+mod record;
+mod resilient;
 mod user;
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::{Name, NameChange, UserId};
+
+/// Database of user name changes.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait NameHistoryDatabase {
+    /// The type returned when a repository fails to apply an operation.
+    type Error;
+
+    /// Stores new user name change.
+    async fn create(&self, change: NameChange) -> Result<NameChange, Self::Error>;
+
+    /// Finds the latest change which released provided name, if any.
+    async fn find_latest_by_name(&self, name: &Name) -> Result<Option<NameChange>, Self::Error>;
+
+    /// Returns all name changes of the user by provided identifier, from newest to oldest.
+    async fn read_by_user(&self, user_id: UserId) -> Result<Vec<NameChange>, Self::Error>;
+
+    /// Deletes provided name change, releasing the name if it was not changed again since.
+    ///
+    /// Returns `true` if the name change was deleted.
+    async fn delete(&self, change: NameChange) -> Result<bool, Self::Error>;
+
+    /// Deletes all name changes of the user by provided identifier,
+    /// releasing all former names of the user.
+    ///
+    /// Returns count of deleted name changes.
+    async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error>;
+}
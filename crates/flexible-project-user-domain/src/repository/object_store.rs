@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use url::Url;
+
+/// Storage of binary objects which are served to the clients by URL.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait ObjectStore {
+    /// The type returned when a storage fails to apply an operation.
+    type Error;
+
+    /// Stores the object by provided key, replacing existing object with the same key.
+    ///
+    /// Returns URL by which stored object is served.
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<Url, Self::Error>;
+
+    /// Lists keys of all stored objects which keys start with provided prefix.
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, Self::Error>;
+
+    /// Deletes the object by provided key, if any.
+    async fn delete(&self, key: &str) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,20 @@
+use auto_impl::auto_impl;
+
+use crate::model::{Password, PasswordHash};
+
+/// Hasher of user passwords.
+#[auto_impl(&, &mut, Box, Rc, Arc)]
+pub trait HashPassword {
+    /// Type of error which is returned when hasher fails to process a password.
+    type Error;
+
+    /// Hashes provided password, so it can be safely stored.
+    fn hash_password(&self, password: &Password) -> Result<PasswordHash, Self::Error>;
+
+    /// Checks if provided password matches provided hash.
+    fn verify_password(
+        &self,
+        password: &Password,
+        hash: &PasswordHash,
+    ) -> Result<bool, Self::Error>;
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use chrono::{DateTime, Utc};
+
+use crate::model::{PasswordReset, PasswordResetToken, PasswordResetTokenHash, UserId};
+
+/// Database of pending password resets of users.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait PasswordResetDatabase {
+    /// The type returned when a repository fails to apply an operation.
+    type Error;
+
+    /// Stores new pending password reset.
+    ///
+    /// Returns stored password reset or an error if reset with the same token hash already exists.
+    async fn create(&self, reset: PasswordReset) -> Result<PasswordReset, Self::Error>;
+
+    /// Atomically finds and takes pending password reset by provided token hash,
+    /// so the same token cannot be taken twice even by concurrent requests.
+    ///
+    /// Taken password reset may be kept by the database,
+    /// so it is still [counted](PasswordResetDatabase::count_created_since) by the rate limit.
+    async fn take_by_token_hash(
+        &self,
+        token_hash: &PasswordResetTokenHash,
+    ) -> Result<Option<PasswordReset>, Self::Error>;
+
+    /// Counts password resets requested by the user since provided time.
+    async fn count_created_since(
+        &self,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<u64, Self::Error>;
+
+    /// Deletes all pending password resets of the user.
+    ///
+    /// Returns count of deleted password resets.
+    async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error>;
+}
+
+/// Generator of secret password reset tokens.
+#[auto_impl(&, &mut, Box, Rc, Arc)]
+pub trait GeneratePasswordResetToken {
+    /// Type of error which is returned when generator fails to generate new token.
+    type Error;
+
+    /// Generates new unpredictable password reset token.
+    fn generate_token(&self) -> Result<PasswordResetToken, Self::Error>;
+
+    /// Hashes provided token, so it can be safely stored and looked up later.
+    ///
+    /// Hash of the same token must always be the same.
+    fn hash_token(&self, token: &PasswordResetToken) -> PasswordResetTokenHash;
+}
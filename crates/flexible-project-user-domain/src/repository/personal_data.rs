@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::model::{PersonalDataEntry, UserId};
+
+/// Source of personal data of the user which is held outside of the user microservice,
+/// such as workspace memberships or notifications.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait PersonalDataSource {
+    /// Type of error which is returned when source fails to collect the data.
+    type Error;
+
+    /// Collects all personal data of the user by provided identifier.
+    async fn collect(&self, user_id: UserId) -> Result<Vec<PersonalDataEntry>, Self::Error>;
+}
@@ -0,0 +1,25 @@
+use auto_impl::auto_impl;
+
+use crate::model::UserRecord;
+
+/// Format of the user records which are imported into or exported from the system in bulk,
+/// such as JSON Lines or CSV.
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait UserRecordFormat {
+    /// Type of error which is returned when record cannot be decoded or encoded.
+    type Error;
+
+    /// Iterator over decoded records of the content.
+    type Records: Iterator<Item = Result<UserRecord, Self::Error>>;
+
+    /// Decodes user records from provided content lazily.
+    ///
+    /// Each malformed record is reported separately, so other records could still be decoded.
+    fn decode(&self, content: Vec<u8>) -> Self::Records;
+
+    /// Encodes user record and appends it to the end of provided output.
+    ///
+    /// Output is empty before the first record is encoded,
+    /// so the format could write its header if needed.
+    fn encode(&self, record: &UserRecord, output: &mut Vec<u8>) -> Result<(), Self::Error>;
+}
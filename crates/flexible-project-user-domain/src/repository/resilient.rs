@@ -0,0 +1,633 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    future::Future,
+    pin::pin,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use derive_more::{Display, Error};
+use futures::{stream::MapErr, TryStreamExt};
+use rand::Rng;
+use typed_builder::TypedBuilder;
+
+use crate::model::{Role, User, UserData, UserFilters, UserId, UserIdFilters, UserPatch};
+
+use super::{Clock, Delay, UserConflict, UserDatabase, UserDatabaseError};
+
+/// Configuration of the [resilient user database](ResilientUserDatabase).
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct ResilienceConfig {
+    /// Maximal count of attempts of idempotent operation, including the first one.
+    #[builder(default = 3)]
+    pub max_attempts: u32,
+    /// Delay before the second attempt, which is doubled for each next attempt.
+    #[builder(default = Duration::milliseconds(50))]
+    pub base_delay: Duration,
+    /// Maximal delay between attempts.
+    #[builder(default = Duration::seconds(1))]
+    pub max_delay: Duration,
+    /// Count of consecutive transient failures after which the circuit is opened.
+    #[builder(default = 5)]
+    pub failure_threshold: u32,
+    /// Duration during which operations fail fast after the circuit was opened.
+    #[builder(default = Duration::seconds(30))]
+    pub open_duration: Duration,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Decorator of any [user database](UserDatabase) which retries idempotent operations
+/// on [transient](UserDatabaseError::is_transient) failures and stops calling the database
+/// for a while after repeated transient failures (opens the circuit).
+///
+/// Retried operations are `read`, `count` and `count_by_role`, which do not modify the database.
+/// Operation `update` is retried only when the user was not changed by anyone:
+/// the user is read before the first attempt and again after each transient failure,
+/// so update which was applied is not repeated, and update which was not applied is repeated
+/// only if the user still has the data read before the first attempt.
+/// Operations `create`, `patch` and `delete` are never retried:
+/// write which failed transiently could still be applied, and repeating it later
+/// could overwrite concurrent write of other client.
+///
+/// Delay between attempts grows exponentially and is jittered
+/// to not retry simultaneously with other clients.
+///
+/// When the open duration ends, the circuit becomes half-open:
+/// the next operation is attempted once as a probe while other operations still fail fast.
+/// The circuit is closed if the probe succeeds and opened again if it fails transiently.
+pub struct ResilientUserDatabase<Database, Sleep, CurrentTime>
+where
+    Database: UserDatabase,
+    Sleep: Delay,
+    CurrentTime: Clock,
+{
+    inner: Database,
+    sleep: Sleep,
+    clock: CurrentTime,
+    config: ResilienceConfig,
+    circuit: Mutex<Circuit>,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    failures: u32,
+    open_until: Option<DateTime<Utc>>,
+    is_probing: bool,
+}
+
+impl<Database, Sleep, CurrentTime> ResilientUserDatabase<Database, Sleep, CurrentTime>
+where
+    Database: UserDatabase,
+    Sleep: Delay,
+    CurrentTime: Clock,
+{
+    /// Creates new decorator around provided user database.
+    pub fn new(
+        inner: Database,
+        sleep: Sleep,
+        clock: CurrentTime,
+        config: ResilienceConfig,
+    ) -> Self {
+        Self {
+            inner,
+            sleep,
+            clock,
+            config,
+            circuit: Mutex::default(),
+        }
+    }
+
+    /// Checks if the circuit is open, so operations fail fast without calling the database.
+    pub fn is_open(&self) -> bool {
+        let now = self.clock.now();
+        matches!(self.lock().open_until, Some(open_until) if now < open_until)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Circuit> {
+        self.circuit.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn on_success(&self) {
+        let mut circuit = self.lock();
+        *circuit = Circuit::default();
+    }
+
+    fn on_transient_failure(&self) {
+        let Self { clock, config, .. } = self;
+        let mut circuit = self.lock();
+        circuit.failures += 1;
+        if circuit.is_probing || circuit.failures >= config.failure_threshold {
+            circuit.open_until = Some(clock.now() + config.open_duration);
+            circuit.is_probing = false;
+            tracing::warn!(
+                failures = circuit.failures,
+                "circuit of user database is opened",
+            );
+        }
+    }
+
+    /// Checks if the operation could call the database.
+    ///
+    /// Returns `true` if the operation is a probe of the half-open circuit.
+    /// The probe extends the open duration, so other operations fail fast until it completes,
+    /// and the next probe is allowed if this one never completes.
+    fn acquire(&self) -> Result<bool, ResilientError<Database::Error>> {
+        let Self { clock, config, .. } = self;
+        let now = clock.now();
+        let mut circuit = self.lock();
+        match circuit.open_until {
+            None => Ok(false),
+            Some(open_until) if now < open_until => Err(ResilientError::CircuitOpen),
+            Some(_) => {
+                circuit.open_until = Some(now + config.open_duration);
+                circuit.is_probing = true;
+                tracing::info!("circuit of user database is half-opened");
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns jittered delay before the next attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ResilienceConfig {
+            base_delay,
+            max_delay,
+            ..
+        } = &self.config;
+        let factor = 2_i32.saturating_pow(attempt.saturating_sub(1));
+        let delay = (*base_delay * factor).min(*max_delay);
+        let millis = delay.num_milliseconds().max(0);
+        Duration::milliseconds(rand::thread_rng().gen_range(0..=millis))
+    }
+
+    async fn call<T, Operation, Output>(
+        &self,
+        is_idempotent: bool,
+        mut operation: Operation,
+    ) -> Result<T, ResilientError<Database::Error>>
+    where
+        Operation: FnMut() -> Output,
+        Output: Future<Output = Result<T, Database::Error>>,
+    {
+        let is_probe = self.acquire()?;
+        let max_attempts = if is_idempotent && !is_probe {
+            self.config.max_attempts.max(1)
+        } else {
+            1
+        };
+        let mut attempt = 1;
+        loop {
+            if attempt > 1 && self.lock().open_until.is_some() {
+                return Err(ResilientError::CircuitOpen);
+            }
+            let error = match operation().await {
+                Ok(value) => {
+                    self.on_success();
+                    return Ok(value);
+                }
+                Err(error) if !error.is_transient() => {
+                    self.on_success();
+                    return Err(ResilientError::Database(error));
+                }
+                Err(error) => error,
+            };
+            self.on_transient_failure();
+            if attempt >= max_attempts {
+                return Err(ResilientError::Database(error));
+            }
+            let delay = self.backoff(attempt);
+            tracing::debug!(attempt, ?delay, "retrying user database operation");
+            self.sleep.delay(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Reads data of the user by its identifier, retrying on transient failures.
+    async fn read_data(
+        &self,
+        id: &UserId,
+    ) -> Result<Option<UserData>, ResilientError<Database::Error>> {
+        let filter = {
+            let id = UserIdFilters::builder().eq(Cow::Borrowed(id)).build();
+            UserFilters::builder().id(id).build()
+        };
+        let users = self.call(true, || self.inner.read(filter.clone())).await?;
+        let mut users = pin!(users);
+        let user = users.try_next().await.map_err(ResilientError::Database)?;
+        Ok(user.map(|User { data, .. }| data))
+    }
+}
+
+/// Stream of filtered users from the [resilient user database](ResilientUserDatabase).
+pub type ResilientUsers<Users, Error> = MapErr<Users, fn(Error) -> ResilientError<Error>>;
+
+#[async_trait(?Send)]
+impl<Database, Sleep, CurrentTime> UserDatabase
+    for ResilientUserDatabase<Database, Sleep, CurrentTime>
+where
+    Database: UserDatabase,
+    Sleep: Delay,
+    CurrentTime: Clock,
+{
+    type Error = ResilientError<Database::Error>;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let (mut id, mut data) = (Some(id), Some(data));
+        self.call(false, || {
+            let (id, data) = (id.take(), data.take());
+            let (id, data) = id.zip(data).expect("operation should be called only once");
+            self.inner.create(id, data)
+        })
+        .await
+    }
+
+    type Users = ResilientUsers<Database::Users, Database::Error>;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        let users = self.call(true, || self.inner.read(filter.clone())).await?;
+        Ok(users.map_err(ResilientError::Database as fn(_) -> _))
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        self.call(true, || self.inner.count(filter.clone())).await
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        self.call(true, || self.inner.count_by_role(filter.clone()))
+            .await
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        let max_attempts = self.config.max_attempts.max(1);
+        let expected = match max_attempts {
+            1 => None,
+            _ => self.read_data(&id).await?,
+        };
+        let mut attempt = 1;
+        loop {
+            let result = self
+                .call(false, || self.inner.update(id.clone(), data.clone()))
+                .await;
+            let error = match (result, &expected) {
+                (Err(ResilientError::Database(error)), Some(_))
+                    if error.is_transient() && attempt < max_attempts =>
+                {
+                    error
+                }
+                (result, _) => return result,
+            };
+            let delay = self.backoff(attempt);
+            self.sleep.delay(delay).await;
+            let current = self.read_data(&id).await?;
+            if current.as_ref() == Some(&data) {
+                return Ok(User { id, data });
+            }
+            if current != expected {
+                tracing::warn!(user_id = %id, "user was changed concurrently, update is not retried");
+                return Err(ResilientError::Database(error));
+            }
+            tracing::debug!(attempt, ?delay, "retrying user database update");
+            attempt += 1;
+        }
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        let (mut id, mut patch) = (Some(id), Some(patch));
+        self.call(false, || {
+            let (id, patch) = (id.take(), patch.take());
+            let (id, patch) = id.zip(patch).expect("operation should be called only once");
+            self.inner.patch(id, patch)
+        })
+        .await
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        let mut id = Some(id);
+        self.call(false, || {
+            let id = id.take().expect("operation should be called only once");
+            self.inner.delete(id)
+        })
+        .await
+    }
+}
+
+/// Error type of the [resilient user database](ResilientUserDatabase).
+#[derive(Debug, Display, Error)]
+pub enum ResilientError<Error> {
+    /// Circuit is open after repeated transient failures, so the database was not called.
+    #[display(fmt = "user database is unavailable after repeated failures")]
+    CircuitOpen,
+    /// Database error.
+    #[display(fmt = "{}", _0)]
+    Database(Error),
+}
+
+impl<Error> UserDatabaseError for ResilientError<Error>
+where
+    Error: UserDatabaseError,
+{
+    fn conflict(&self) -> Option<UserConflict> {
+        match self {
+            Self::CircuitOpen => None,
+            Self::Database(error) => error.conflict(),
+        }
+    }
+
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::CircuitOpen => true,
+            Self::Database(error) => error.is_transient(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::{BTreeMap, VecDeque},
+        vec,
+    };
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use derive_more::{Display, Error};
+    use futures::{executor::block_on, stream::Iter};
+
+    use super::{ResilienceConfig, ResilientError, ResilientUserDatabase};
+    use crate::{
+        model::{Role, User, UserData, UserFilters, UserId, UserPatch},
+        repository::{Clock, Delay, UserConflict, UserDatabase, UserDatabaseError},
+        use_case::fixture::{user_data, ManualClock},
+    };
+
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "transient failure")]
+    struct TransientError;
+
+    impl UserDatabaseError for TransientError {
+        fn conflict(&self) -> Option<UserConflict> {
+            None
+        }
+
+        fn is_transient(&self) -> bool {
+            true
+        }
+    }
+
+    /// Database which fails provided count of times, then always counts zero users
+    /// and patches no users. Other operations always fail.
+    #[derive(Default)]
+    struct FlakyDatabase {
+        failures: Cell<u32>,
+        calls: Cell<u32>,
+    }
+
+    impl FlakyDatabase {
+        fn call(&self) -> Result<(), TransientError> {
+            self.calls.set(self.calls.get() + 1);
+            let failures = self.failures.get();
+            if failures == 0 {
+                return Ok(());
+            }
+            self.failures.set(failures - 1);
+            Err(TransientError)
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl UserDatabase for FlakyDatabase {
+        type Error = TransientError;
+
+        async fn create(&self, _: UserId, _: UserData) -> Result<User, Self::Error> {
+            Err(TransientError)
+        }
+
+        type Users = futures::stream::Empty<Result<User, Self::Error>>;
+        async fn read(&self, _: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn count(&self, _: UserFilters<'_>) -> Result<u64, Self::Error> {
+            self.call().map(|()| 0)
+        }
+
+        async fn count_by_role(
+            &self,
+            _: UserFilters<'_>,
+        ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn update(&self, _: UserId, _: UserData) -> Result<User, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn patch(&self, _: UserId, _: UserPatch) -> Result<Option<User>, Self::Error> {
+            self.call().map(|()| None)
+        }
+
+        async fn delete(&self, _: UserId) -> Result<User, Self::Error> {
+            Err(TransientError)
+        }
+    }
+
+    /// Database of users which fails updates transiently as planned,
+    /// optionally storing provided data before the failure is reported.
+    #[derive(Default)]
+    struct LossyDatabase {
+        users: RefCell<BTreeMap<UserId, UserData>>,
+        failures: RefCell<VecDeque<Option<UserData>>>,
+        updates: Cell<u32>,
+    }
+
+    #[async_trait(?Send)]
+    impl UserDatabase for LossyDatabase {
+        type Error = TransientError;
+
+        async fn create(&self, _: UserId, _: UserData) -> Result<User, Self::Error> {
+            Err(TransientError)
+        }
+
+        type Users = Iter<vec::IntoIter<Result<User, Self::Error>>>;
+        async fn read(&self, _: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+            let users = self.users.borrow();
+            let users = users.iter().map(|(id, data)| {
+                let user = User {
+                    id: id.clone(),
+                    data: data.clone(),
+                };
+                Ok(user)
+            });
+            Ok(futures::stream::iter(users.collect::<Vec<_>>()))
+        }
+
+        async fn count(&self, _: UserFilters<'_>) -> Result<u64, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn count_by_role(
+            &self,
+            _: UserFilters<'_>,
+        ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+            self.updates.set(self.updates.get() + 1);
+            if let Some(stored) = self.failures.borrow_mut().pop_front() {
+                if let Some(stored) = stored {
+                    self.users.borrow_mut().insert(id, stored);
+                }
+                return Err(TransientError);
+            }
+            self.users.borrow_mut().insert(id.clone(), data.clone());
+            Ok(User { id, data })
+        }
+
+        async fn patch(&self, _: UserId, _: UserPatch) -> Result<Option<User>, Self::Error> {
+            Err(TransientError)
+        }
+
+        async fn delete(&self, _: UserId) -> Result<User, Self::Error> {
+            Err(TransientError)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordedDelay(RefCell<Vec<Duration>>);
+
+    #[async_trait(?Send)]
+    impl Delay for RecordedDelay {
+        async fn delay(&self, duration: Duration) {
+            self.0.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn retry_and_circuit() {
+        let inner = FlakyDatabase::default();
+        let delay = RecordedDelay::default();
+        let clock = ManualClock::default();
+        let config = ResilienceConfig::builder()
+            .max_attempts(3)
+            .failure_threshold(4)
+            .build();
+        let database = ResilientUserDatabase::new(&inner, &delay, &clock, config.clone());
+        let count = || block_on(database.count(UserFilters::default()));
+
+        inner.failures.set(2);
+        assert_eq!(count().unwrap(), 0);
+        assert_eq!(inner.calls.get(), 3);
+        let delays = delay.0.borrow().clone();
+        assert_eq!(delays.len(), 2);
+        assert!(delays[0] <= config.base_delay && delays[1] <= config.base_delay * 2);
+
+        inner.failures.set(4);
+        assert!(matches!(count(), Err(ResilientError::Database(_))));
+        assert!(!database.is_open());
+        assert!(matches!(count(), Err(ResilientError::CircuitOpen)));
+        assert!(database.is_open());
+        assert_eq!(inner.calls.get(), 7);
+        assert!(matches!(count(), Err(ResilientError::CircuitOpen)));
+        assert_eq!(inner.calls.get(), 7);
+
+        clock.0.set(clock.now() + config.open_duration);
+        assert_eq!(count().unwrap(), 0);
+        assert!(!database.is_open());
+    }
+
+    #[test]
+    fn writes_are_not_retried() {
+        let inner = FlakyDatabase::default();
+        let delay = RecordedDelay::default();
+        let clock = ManualClock::default();
+        let database =
+            ResilientUserDatabase::new(&inner, &delay, &clock, ResilienceConfig::default());
+        let patch = || {
+            let patch = UserPatch::builder().build();
+            block_on(database.patch(UserId::new("user"), patch))
+        };
+
+        inner.failures.set(1);
+        assert!(matches!(patch(), Err(ResilientError::Database(_))));
+        assert_eq!(inner.calls.get(), 1);
+        assert!(delay.0.borrow().is_empty());
+        assert!(patch().unwrap().is_none());
+        assert_eq!(inner.calls.get(), 2);
+    }
+
+    #[test]
+    fn half_open_probe() {
+        let inner = FlakyDatabase::default();
+        let delay = RecordedDelay::default();
+        let clock = ManualClock::default();
+        let config = ResilienceConfig::builder()
+            .max_attempts(3)
+            .failure_threshold(3)
+            .build();
+        let database = ResilientUserDatabase::new(&inner, &delay, &clock, config.clone());
+        let count = || block_on(database.count(UserFilters::default()));
+
+        inner.failures.set(3);
+        assert!(matches!(count(), Err(ResilientError::Database(_))));
+        assert!(database.is_open());
+        assert_eq!(inner.calls.get(), 3);
+
+        clock.0.set(clock.now() + config.open_duration);
+        inner.failures.set(2);
+        assert!(matches!(count(), Err(ResilientError::Database(_))));
+        assert_eq!(inner.calls.get(), 4);
+        assert!(database.is_open());
+        assert!(matches!(count(), Err(ResilientError::CircuitOpen)));
+
+        clock.0.set(clock.now() + config.open_duration);
+        assert!(matches!(count(), Err(ResilientError::Database(_))));
+        assert_eq!(inner.calls.get(), 5);
+        clock.0.set(clock.now() + config.open_duration);
+        assert_eq!(count().unwrap(), 0);
+        assert!(!database.is_open());
+        assert_eq!(inner.calls.get(), 6);
+    }
+
+    #[test]
+    fn guarded_update_retry() {
+        let inner = LossyDatabase::default();
+        let delay = RecordedDelay::default();
+        let clock = ManualClock::default();
+        let database =
+            ResilientUserDatabase::new(&inner, &delay, &clock, ResilienceConfig::default());
+        let id = UserId::new("tanabe");
+        let update = |name| block_on(database.update(id.clone(), user_data(name, Role::User)));
+        let stored = || inner.users.borrow()[&id].name.as_str().to_string();
+        inner
+            .users
+            .borrow_mut()
+            .insert(id.clone(), user_data("tanabe", Role::User));
+
+        inner.failures.borrow_mut().push_back(None);
+        assert_eq!(update("flexible").unwrap().data.name.as_str(), "flexible");
+        assert_eq!(inner.updates.get(), 2);
+        assert_eq!(stored(), "flexible");
+
+        let applied = user_data("renamed", Role::User);
+        inner.failures.borrow_mut().push_back(Some(applied));
+        assert_eq!(update("renamed").unwrap().data.name.as_str(), "renamed");
+        assert_eq!(inner.updates.get(), 3);
+
+        let concurrent = user_data("kotlinist", Role::User);
+        inner.failures.borrow_mut().push_back(Some(concurrent));
+        assert!(matches!(update("tanabe"), Err(ResilientError::Database(_))));
+        assert_eq!(inner.updates.get(), 4);
+        assert_eq!(stored(), "kotlinist");
+    }
+}
@@ -1,19 +1,24 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use auto_impl::auto_impl;
+use derive_more::Display;
 use futures::Stream;
 
-use crate::model::{User, UserData, UserFilters, UserId};
+use crate::model::{Role, User, UserData, UserFilters, UserId, UserPatch};
 
 /// Database of user microservice data.
 #[async_trait(?Send)]
 #[auto_impl(&, Box, Rc, Arc)]
 pub trait UserDatabase {
     /// The type returned when a repository fails to apply an operation.
-    type Error;
+    type Error: UserDatabaseError;
 
     /// Creates new user from provided identifier and user data.
     ///
     /// Returns new user or an error if user with such identifier already exists.
+    /// Error must report [name](UserConflict::Name) or [email](UserConflict::Email) conflict
+    /// if user with the same name or email already exists.
     async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error>;
 
     /// Type of stream which produces filtered repository data.
@@ -21,13 +26,58 @@ pub trait UserDatabase {
     /// Filters users by provided filter object.
     async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error>;
 
+    /// Counts users which satisfy provided filter object.
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error>;
+
+    /// Counts users which satisfy provided filter object grouped by their role.
+    ///
+    /// Roles which are not held by any user satisfying the filter may be absent from the result.
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error>;
+
     /// Updates user by provided identifier with provided data.
     ///
     /// Returns updated user or an error if user with such identifier does not exist.
+    /// Error must report [name](UserConflict::Name) or [email](UserConflict::Email) conflict
+    /// if other user with the same name or email already exists.
     async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error>;
 
+    /// Changes only those fields of the user by provided identifier which are present in the patch.
+    ///
+    /// Returns patched user or `None` if user with such identifier does not exist.
+    /// Error must report [name](UserConflict::Name) or [email](UserConflict::Email) conflict
+    /// if other user with the same name or email already exists.
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error>;
+
     /// Deletes user from the repository by provided identifier.
     ///
     /// Returns deleted user or an error if user with such identifier does not exist.
     async fn delete(&self, id: UserId) -> Result<User, Self::Error>;
 }
+
+/// Error of the [user database](UserDatabase) which could be caused by uniqueness violation
+/// or by transient failure of the database.
+pub trait UserDatabaseError {
+    /// Returns the kind of conflict if the error was caused by violation
+    /// of user name or email uniqueness.
+    fn conflict(&self) -> Option<UserConflict>;
+
+    /// Checks if the error is caused by transient failure of the database
+    /// (e.g. network failure or primary election), so the operation could be retried.
+    fn is_transient(&self) -> bool {
+        false
+    }
+}
+
+/// Kind of uniqueness conflict which is reported by the [user database](UserDatabase).
+#[derive(Debug, Display, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum UserConflict {
+    /// User with the same name already exists.
+    #[display(fmt = "user name is already taken")]
+    Name,
+    /// User with the same email already exists.
+    #[display(fmt = "user email is already taken")]
+    Email,
+}
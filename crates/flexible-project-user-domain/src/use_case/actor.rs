@@ -0,0 +1,32 @@
+use crate::{
+    model::{Actor, User, UserData},
+    repository::{Clock, UserDatabase},
+    use_case::find_one::find_one_by_id,
+};
+
+/// Checks if the actor is allowed to mutate data of the system right now.
+///
+/// Suspended, banned or deleted users are not active, while the system is always active.
+pub async fn is_actor_active<Database, CurrentTime>(
+    database: Database,
+    clock: CurrentTime,
+    actor: &Actor,
+) -> Result<bool, Database::Error>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    let id = match actor {
+        Actor::User { id, .. } => id,
+        Actor::System => return Ok(true),
+    };
+    let user = find_one_by_id(database, id).await?;
+    let is_active = match user {
+        Some(User {
+            data: UserData { status, .. },
+            ..
+        }) => status.is_active(clock.now()),
+        None => false,
+    };
+    Ok(is_active)
+}
@@ -0,0 +1,88 @@
+use std::pin::pin;
+
+use derive_more::{Display, Error};
+use futures::TryStreamExt;
+
+use crate::{
+    model::{Actor, UserAction, UserFilters, UserRecord},
+    repository::{Clock, UserDatabase, UserRecordFormat},
+    use_case::actor::is_actor_active,
+};
+
+/// Error type of export users use case.
+#[derive(Debug, Display, Error)]
+pub enum ExportUsersError<DatabaseError, FormatError> {
+    /// Actor is not allowed to export users.
+    #[display(fmt = "{} is not allowed to export users", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Record format error.
+    #[display(fmt = "record format error: {}", _0)]
+    Format(FormatError),
+}
+
+/// Export users interactor.
+pub struct ExportUsers<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> ExportUsers<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new export users interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Exports users filtered by provided filter object into the content
+    /// which is encoded by provided format.
+    ///
+    /// Users are encoded one by one as they are read from the database.
+    pub async fn export_users<Format>(
+        &self,
+        actor: Actor,
+        format: Format,
+        filter: UserFilters<'_>,
+    ) -> Result<Vec<u8>, ExportUsersError<Database::Error, Format::Error>>
+    where
+        Format: UserRecordFormat,
+    {
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed_in_bulk(UserAction::Export) {
+            return Err(ExportUsersError::Forbidden(actor));
+        }
+        let is_actor_active = is_actor_active(database, clock, &actor)
+            .await
+            .map_err(ExportUsersError::Database)?;
+        if !is_actor_active {
+            return Err(ExportUsersError::Inactive(actor));
+        }
+
+        let users = database
+            .read(filter)
+            .await
+            .map_err(ExportUsersError::Database)?;
+        let mut users = pin!(users);
+        let mut content = Vec::new();
+        while let Some(user) = users.try_next().await.map_err(ExportUsersError::Database)? {
+            let record = UserRecord::from(user.data);
+            format
+                .encode(&record, &mut content)
+                .map_err(ExportUsersError::Format)?;
+        }
+        Ok(content)
+    }
+}
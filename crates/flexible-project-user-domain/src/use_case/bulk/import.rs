@@ -0,0 +1,497 @@
+use std::collections::HashSet;
+
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{
+        Actor, Avatar, AvatarError, AvatarPolicy, DisplayName, Email, EmailDomainError,
+        EmailPolicy, Name, User, UserAction, UserData, UserRecord, UserRecordError,
+    },
+    repository::{
+        Clock, GenerateUserId, NameHistoryDatabase, UserConflict, UserDatabase, UserDatabaseError,
+        UserRecordFormat,
+    },
+    use_case::{
+        actor::is_actor_active,
+        find_one::{find_one_by_email, find_one_by_name},
+        name::{is_reserved_for_other, NameHistoryConfig},
+        update::{is_confusable_with_staff, DisplayNameConfig},
+    },
+};
+
+/// Error type of import users use case.
+#[derive(Debug, Display, Error)]
+pub enum ImportUsersError<DatabaseError, HistoryError, GenerateIdError> {
+    /// Actor is not allowed to import users.
+    #[display(fmt = "{} is not allowed to import users", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    NameHistory(HistoryError),
+    /// Identifier generation error.
+    #[display(fmt = "identifier generation error: {}", _0)]
+    GenerateId(GenerateIdError),
+}
+
+/// Error of the users import which was aborted.
+#[derive(Debug, Display, Error)]
+#[display(
+    fmt = "import was aborted after {} records: {}",
+    "report.imported.len() + report.failures.len()",
+    error
+)]
+pub struct ImportAborted<FormatError, DatabaseError, HistoryError, GenerateIdError> {
+    /// Report of the records which were processed before the import was aborted.
+    ///
+    /// Users of this report were already stored (unless in dry run mode).
+    pub report: ImportReport<FormatError>,
+    /// Error which aborted the import.
+    #[error(source)]
+    pub error: ImportUsersError<DatabaseError, HistoryError, GenerateIdError>,
+}
+
+/// Reason why the record of the user was not imported.
+#[derive(Debug, Display, Error)]
+pub enum ImportRecordError<FormatError> {
+    /// Record is malformed and cannot be decoded.
+    #[display(fmt = "record is malformed: {}", _0)]
+    Malformed(FormatError),
+    /// Record does not meet domain requirements.
+    #[display(fmt = "record is invalid: {}", _0)]
+    Invalid(UserRecordError),
+    /// User with provided name already exists, was imported earlier
+    /// or the name was released recently.
+    #[display(fmt = r#"user name "{}" is already taken"#, _0)]
+    NameAlreadyTaken(#[error(not(source))] Name),
+    /// User with provided email already exists or was imported earlier.
+    #[display(fmt = r#"user email "{}" is already taken"#, _0)]
+    EmailAlreadyTaken(#[error(not(source))] Email),
+    /// Display name of the user could be confused with display name
+    /// of administrator or moderator.
+    #[display(
+        fmt = r#"user display name "{}" could be confused with display name of staff member"#,
+        _0
+    )]
+    ConfusableDisplayName(#[error(not(source))] DisplayName),
+    /// Domain of provided email does not conform to the email policy.
+    #[display(fmt = r#"user email "{}" is rejected: {}"#, _0, _1)]
+    EmailNotAllowed(
+        #[error(not(source))] Email,
+        #[error(not(source))] EmailDomainError,
+    ),
+    /// Avatar of the user does not conform to the avatar policy.
+    #[display(fmt = r#"user avatar "{}" is rejected: {}"#, _0, _1)]
+    AvatarNotAllowed(
+        #[error(not(source))] Avatar,
+        #[error(not(source))] AvatarError,
+    ),
+}
+
+/// Record of the user which was not imported.
+#[derive(Debug)]
+pub struct ImportFailure<FormatError> {
+    /// Number of the record in the content, starting from one.
+    pub record: usize,
+    /// Reason why the record was not imported.
+    pub error: ImportRecordError<FormatError>,
+}
+
+/// Report of the users import.
+#[derive(Debug)]
+pub struct ImportReport<FormatError> {
+    /// Users which were imported.
+    ///
+    /// In dry run mode, these users would be imported, but they were not stored,
+    /// so their identifiers were generated but not claimed.
+    pub imported: Vec<User>,
+    /// Records which were not imported.
+    pub failures: Vec<ImportFailure<FormatError>>,
+}
+
+/// Import users interactor.
+pub struct ImportUsers<Database, History, GenerateId, CurrentTime>
+where
+    Database: UserDatabase,
+    History: NameHistoryDatabase,
+    GenerateId: GenerateUserId,
+    CurrentTime: Clock,
+{
+    database: Database,
+    history: History,
+    generate_id: GenerateId,
+    clock: CurrentTime,
+    config: NameHistoryConfig,
+    display_name_config: DisplayNameConfig,
+    email_policy: EmailPolicy,
+    avatar_policy: AvatarPolicy,
+}
+
+impl<Database, History, GenerateId, CurrentTime>
+    ImportUsers<Database, History, GenerateId, CurrentTime>
+where
+    Database: UserDatabase,
+    History: NameHistoryDatabase,
+    GenerateId: GenerateUserId,
+    CurrentTime: Clock,
+{
+    /// Creates new import users interactor.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        database: Database,
+        history: History,
+        generate_id: GenerateId,
+        clock: CurrentTime,
+        config: NameHistoryConfig,
+        display_name_config: DisplayNameConfig,
+        email_policy: EmailPolicy,
+        avatar_policy: AvatarPolicy,
+    ) -> Self {
+        Self {
+            database,
+            history,
+            generate_id,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
+            avatar_policy,
+        }
+    }
+
+    /// Imports users from the content which is decoded by provided format.
+    ///
+    /// Each record is validated separately, so invalid records are reported
+    /// and skipped without aborting the import. Records which names or emails
+    /// are already taken by existing users or by earlier records are skipped too,
+    /// as well as records which display names could be confused with display names
+    /// of administrators or moderators.
+    ///
+    /// If the import is aborted, records processed before the error are reported
+    /// along with it, because users imported by them are already stored.
+    ///
+    /// If dry run mode is enabled, records are validated but users are not stored.
+    #[allow(clippy::type_complexity)]
+    pub async fn import_users<Format>(
+        &self,
+        actor: Actor,
+        format: Format,
+        content: Vec<u8>,
+        dry_run: bool,
+    ) -> Result<
+        ImportReport<Format::Error>,
+        ImportAborted<Format::Error, Database::Error, History::Error, GenerateId::Error>,
+    >
+    where
+        Format: UserRecordFormat,
+    {
+        let Self {
+            database, clock, ..
+        } = self;
+
+        let mut report = ImportReport {
+            imported: Vec::new(),
+            failures: Vec::new(),
+        };
+        if !actor.is_allowed_in_bulk(UserAction::Import) {
+            let error = ImportUsersError::Forbidden(actor);
+            return Err(ImportAborted { report, error });
+        }
+        let is_actor_active = match is_actor_active(database, clock, &actor).await {
+            Ok(is_actor_active) => is_actor_active,
+            Err(error) => {
+                let error = ImportUsersError::Database(error);
+                return Err(ImportAborted { report, error });
+            }
+        };
+        if !is_actor_active {
+            let error = ImportUsersError::Inactive(actor);
+            return Err(ImportAborted { report, error });
+        }
+
+        let mut taken = Taken::default();
+        for (index, record) in format.decode(content).enumerate() {
+            let user = match record {
+                Ok(record) => match self.import_record(record, &mut taken, dry_run).await {
+                    Ok(user) => user,
+                    Err(error) => return Err(ImportAborted { report, error }),
+                },
+                Err(error) => Err(ImportRecordError::Malformed(error)),
+            };
+            match user {
+                Ok(user) => report.imported.push(user),
+                Err(error) => {
+                    let failure = ImportFailure {
+                        record: index + 1,
+                        error,
+                    };
+                    report.failures.push(failure);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Validates and imports one record of the user.
+    ///
+    /// Returns an error of the record if it was not imported,
+    /// or an error of the import if it must be aborted.
+    #[allow(clippy::type_complexity)]
+    async fn import_record<FormatError>(
+        &self,
+        record: UserRecord,
+        taken: &mut Taken,
+        dry_run: bool,
+    ) -> Result<
+        Result<User, ImportRecordError<FormatError>>,
+        ImportUsersError<Database::Error, History::Error, GenerateId::Error>,
+    > {
+        let Self {
+            database,
+            history,
+            generate_id,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
+            avatar_policy,
+        } = self;
+
+        let data = match UserData::try_from(record) {
+            Ok(data) => data,
+            Err(error) => return Ok(Err(ImportRecordError::Invalid(error))),
+        };
+        let UserData {
+            name,
+            display_name,
+            email,
+            avatar,
+            ..
+        } = &data;
+        let is_confusable =
+            is_confusable_with_staff(database, display_name_config, display_name, None)
+                .await
+                .map_err(ImportUsersError::Database)?;
+        if is_confusable {
+            return Ok(Err(ImportRecordError::ConfusableDisplayName(
+                display_name.clone(),
+            )));
+        }
+        if let Some(email) = email {
+            if let Err(error) = email_policy.check(email) {
+                return Ok(Err(ImportRecordError::EmailNotAllowed(
+                    email.clone(),
+                    error,
+                )));
+            }
+        }
+        if let Some(avatar) = avatar {
+            if let Err(error) = avatar_policy.check(avatar) {
+                return Ok(Err(ImportRecordError::AvatarNotAllowed(
+                    avatar.clone(),
+                    error,
+                )));
+            }
+        }
+
+        let is_name_taken = taken.names.contains(&name.canonical())
+            || is_reserved_for_other(history, clock, config, name, None)
+                .await
+                .map_err(ImportUsersError::NameHistory)?
+            || find_one_by_name(database, name)
+                .await
+                .map_err(ImportUsersError::Database)?
+                .is_some();
+        if is_name_taken {
+            return Ok(Err(ImportRecordError::NameAlreadyTaken(name.clone())));
+        }
+        if let Some(email) = email {
+            let is_email_taken = taken.emails.contains(&email.canonical())
+                || find_one_by_email(database, &data.email)
+                    .await
+                    .map_err(ImportUsersError::Database)?
+                    .is_some();
+            if is_email_taken {
+                return Ok(Err(ImportRecordError::EmailAlreadyTaken(email.clone())));
+            }
+        }
+
+        let id = generate_id
+            .generate_id()
+            .map_err(ImportUsersError::GenerateId)?;
+        let user = if dry_run {
+            User { id, data }
+        } else {
+            let UserData { name, email, .. } = data.clone();
+            let user = database.create(id, data).await;
+            match (user, email) {
+                (Ok(user), _) => user,
+                (Err(error), email) => {
+                    let error = match (error.conflict(), email) {
+                        (Some(UserConflict::Name), _) => ImportRecordError::NameAlreadyTaken(name),
+                        (Some(UserConflict::Email), Some(email)) => {
+                            ImportRecordError::EmailAlreadyTaken(email)
+                        }
+                        _ => return Err(ImportUsersError::Database(error)),
+                    };
+                    return Ok(Err(error));
+                }
+            }
+        };
+
+        let UserData { name, email, .. } = &user.data;
+        taken.names.insert(name.canonical());
+        if let Some(email) = email {
+            taken.emails.insert(email.canonical());
+        }
+        Ok(Ok(user))
+    }
+}
+
+/// Canonical names and emails which were taken by the records imported earlier.
+#[derive(Debug, Default)]
+struct Taken {
+    names: HashSet<String>,
+    emails: HashSet<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, convert::Infallible, vec};
+
+    use fp_core::id::GenerateId;
+    use futures::executor::block_on;
+
+    use super::{ImportAborted, ImportRecordError, ImportUsers, ImportUsersError};
+    use crate::{
+        model::{Actor, AvatarPolicy, EmailPolicy, Role, User, UserId, UserRecord},
+        repository::{memory::MemoryNameHistoryDatabase, UserRecordFormat},
+        use_case::{
+            fixture::{database, FixedClock, SequentialIds},
+            DisplayNameConfig, NameHistoryConfig,
+        },
+    };
+
+    /// Format where each line is a name of the user,
+    /// optionally followed by its display name and avatar after commas.
+    struct Lines;
+
+    impl UserRecordFormat for Lines {
+        type Error = Infallible;
+
+        type Records = vec::IntoIter<Result<UserRecord, Self::Error>>;
+
+        fn decode(&self, content: Vec<u8>) -> Self::Records {
+            let content = String::from_utf8(content).unwrap();
+            let records: Vec<_> = content
+                .lines()
+                .map(|line| {
+                    let mut fields = line.splitn(3, ',');
+                    let name = fields.next().unwrap_or_default();
+                    let mut field = || fields.next().map(ToOwned::to_owned);
+                    let record = UserRecord {
+                        name: name.to_owned(),
+                        display_name: field(),
+                        avatar: field(),
+                        ..Default::default()
+                    };
+                    Ok(record)
+                })
+                .collect();
+            records.into_iter()
+        }
+
+        fn encode(&self, _: &UserRecord, _: &mut Vec<u8>) -> Result<(), Self::Error> {
+            unimplemented!("records are only decoded in tests")
+        }
+    }
+
+    /// Generator which fails after provided count of identifiers.
+    struct LimitedIds(Cell<u32>);
+
+    impl GenerateId<User> for LimitedIds {
+        type Error = &'static str;
+
+        fn generate_id(&self) -> Result<UserId, Self::Error> {
+            let Self(left) = self;
+            let id = left.get().checked_sub(1).ok_or("no identifiers left")?;
+            left.set(id);
+            Ok(UserId::new(format!("limited-{id}")))
+        }
+    }
+
+    #[test]
+    fn import() {
+        let database = database([("moderator", Role::Moderator), ("tanabe", Role::User)]);
+        let interactor = ImportUsers::new(
+            database,
+            MemoryNameHistoryDatabase::new(),
+            SequentialIds::default(),
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+            DisplayNameConfig::default(),
+            EmailPolicy::default(),
+            AvatarPolicy::default(),
+        );
+        let content = concat!(
+            "kotlinist\nflexible,Rnoderator\ntanabe\nKotlinist\n1234\n",
+            "project,Project,http://127.0.0.1/avatar.png",
+        );
+        let content = content.as_bytes();
+
+        let report =
+            block_on(interactor.import_users(Actor::System, Lines, content.to_vec(), false))
+                .unwrap();
+        let imported: Vec<_> = report
+            .imported
+            .iter()
+            .map(|user| user.data.name.as_str())
+            .collect();
+        assert_eq!(imported, ["kotlinist"]);
+        let failures: Vec<_> = report
+            .failures
+            .iter()
+            .map(|failure| (failure.record, &failure.error))
+            .collect();
+        assert!(matches!(
+            failures[..],
+            [
+                (2, ImportRecordError::ConfusableDisplayName(_)),
+                (3, ImportRecordError::NameAlreadyTaken(_)),
+                (4, ImportRecordError::NameAlreadyTaken(_)),
+                (5, ImportRecordError::Invalid(_)),
+                (6, ImportRecordError::AvatarNotAllowed(_, _)),
+            ],
+        ));
+    }
+
+    #[test]
+    fn aborted() {
+        let database = database([("tanabe", Role::User)]);
+        let interactor = ImportUsers::new(
+            &database,
+            MemoryNameHistoryDatabase::new(),
+            LimitedIds(Cell::new(1)),
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+            DisplayNameConfig::default(),
+            EmailPolicy::default(),
+            AvatarPolicy::default(),
+        );
+        let content = "kotlinist\ntanabe\nflexible\nproject".as_bytes();
+
+        let ImportAborted { report, error } =
+            block_on(interactor.import_users(Actor::System, Lines, content.to_vec(), false))
+                .unwrap_err();
+        assert!(matches!(error, ImportUsersError::GenerateId(_)));
+        assert_eq!(report.imported.len(), 1);
+        assert_eq!(report.imported[0].data.name.as_str(), "kotlinist");
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].record, 2);
+    }
+}
@@ -0,0 +1,10 @@
+pub use self::{
+    export::{ExportUsers, ExportUsersError},
+    import::{
+        ImportAborted, ImportFailure, ImportRecordError, ImportReport, ImportUsers,
+        ImportUsersError,
+    },
+};
+
+mod export;
+mod import;
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    model::{Actor, Role, UserFilters},
+    repository::{Clock, UserDatabase},
+};
+
+use super::read::{check_filter, ReadUsersError};
+
+/// Count users interactor.
+pub struct CountUsers<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> CountUsers<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new count users interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Counts users which satisfy provided filter object.
+    ///
+    /// Account status filter is evaluated at the current time.
+    /// Any actor, including anonymous one (if `None`), is allowed to count users,
+    /// but only the system and administrators are allowed to count them by private data.
+    pub async fn count_users(
+        &self,
+        actor: Option<Actor>,
+        filter: UserFilters<'_>,
+    ) -> Result<u64, ReadUsersError<Database::Error>> {
+        let Self { database, clock } = self;
+        check_filter(actor.as_ref(), &filter)?;
+        let filter = filter.with_status_at(clock.now());
+        let count = database.count(filter).await?;
+        Ok(count)
+    }
+
+    /// Counts users which satisfy provided filter object grouped by their role.
+    ///
+    /// Every role is present in the result, even if no user satisfying the filter has it.
+    /// Role of the user is private, so only the system and administrators are allowed to do it.
+    pub async fn count_users_by_role(
+        &self,
+        actor: Option<Actor>,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, ReadUsersError<Database::Error>> {
+        let Self { database, clock } = self;
+        if !actor.is_some_and(|actor| actor.is_allowed_to_read_all()) {
+            return Err(ReadUsersError::Forbidden);
+        }
+        let filter = filter.with_status_at(clock.now());
+        let mut counts = database.count_by_role(filter).await?;
+        for role in [Role::User, Role::Moderator, Role::Administrator] {
+            counts.entry(role).or_default();
+        }
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use chrono::Duration;
+    use futures::executor::block_on;
+
+    use super::{CountUsers, ReadUsersError};
+    use crate::{
+        model::{
+            AccountStatus, AccountStatusFilters, AccountStatusKind, Actor, Role, RoleFilters,
+            UserDataFilters, UserFilters, UserId, UserPatch,
+        },
+        repository::UserDatabase,
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn count() {
+        let database = database([
+            ("tanabe", Role::User),
+            ("kotlinist", Role::User),
+            ("flexible", Role::Administrator),
+        ]);
+        let interactor = CountUsers::new(database, FixedClock::default());
+
+        let count = block_on(interactor.count_users(None, UserFilters::default())).unwrap();
+        assert_eq!(count, 3);
+        let filter = {
+            let role = RoleFilters::builder().ne(Cow::Owned(Role::User)).build();
+            let data = UserDataFilters::builder().role(role).build();
+            UserFilters::builder().data(data).build()
+        };
+        let count = block_on(interactor.count_users(Some(Actor::System), filter)).unwrap();
+        assert_eq!(count, 1);
+
+        let result = block_on(interactor.count_users_by_role(None, UserFilters::default()));
+        assert!(matches!(result, Err(ReadUsersError::Forbidden)));
+        let moderator = actor("kotlinist", Role::Moderator);
+        let result =
+            block_on(interactor.count_users_by_role(Some(moderator), UserFilters::default()));
+        assert!(matches!(result, Err(ReadUsersError::Forbidden)));
+
+        let administrator = actor("flexible", Role::Administrator);
+        let counts =
+            block_on(interactor.count_users_by_role(Some(administrator), UserFilters::default()))
+                .unwrap();
+        let counts: Vec<_> = counts.into_iter().collect();
+        assert_eq!(
+            counts,
+            [
+                (Role::User, 2),
+                (Role::Moderator, 0),
+                (Role::Administrator, 1),
+            ],
+        );
+    }
+
+    #[test]
+    fn expired_suspension() {
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let clock = FixedClock::default();
+        let suspend = |id, until| {
+            let status = AccountStatus::Suspended {
+                until,
+                reason: "spam".into(),
+            };
+            let patch = UserPatch::builder().status(status).build();
+            block_on(database.patch(UserId::new(id), patch)).unwrap();
+        };
+        suspend("tanabe", clock.0 - Duration::days(1));
+        suspend("kotlinist", clock.0 + Duration::days(1));
+        let interactor = CountUsers::new(&database, clock);
+        let count = |kind| {
+            let filter = {
+                let status = AccountStatusFilters::builder().eq(Cow::Owned(kind)).build();
+                let data = UserDataFilters::builder().status(status).build();
+                UserFilters::builder().data(data).build()
+            };
+            block_on(interactor.count_users(Some(Actor::System), filter)).unwrap()
+        };
+
+        assert_eq!(count(AccountStatusKind::Active), 1);
+        assert_eq!(count(AccountStatusKind::Suspended), 1);
+        assert_eq!(count(AccountStatusKind::Banned), 0);
+    }
+}
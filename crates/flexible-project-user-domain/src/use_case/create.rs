@@ -1,86 +1,259 @@
 use derive_more::{Display, Error};
 
 use crate::{
-    model::{DisplayName, Name, Role, User, UserData},
-    repository::{GenerateUserId, UserDatabase},
+    model::{
+        AccountStatus, Actor, DisplayName, DisplayNameError, Email, EmailDomainError, EmailPolicy,
+        Name, Role, User, UserData,
+    },
+    repository::{
+        Clock, GenerateUserId, NameHistoryDatabase, UserConflict, UserDatabase, UserDatabaseError,
+    },
 };
 
-use super::find_one::find_one_by_name;
+use super::{
+    actor::is_actor_active,
+    name::{is_reserved_for_other, NameHistoryConfig},
+    update::{is_confusable_with_staff, DisplayNameConfig},
+};
 
 /// Error type of create user use case.
 #[derive(Debug, Display, Error)]
-pub enum CreateUserError<DatabaseError, GenerateIdError> {
-    /// User with provided name already exists.
+pub enum CreateUserError<DatabaseError, HistoryError, GenerateIdError> {
+    /// Actor is not allowed to create new users.
+    #[display(fmt = "{} is not allowed to create user", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// User with provided name already exists or the name was released recently.
     #[display(fmt = r#"user name "{}" is already taken"#, _0)]
     NameAlreadyTaken(#[error(not(source))] Name),
+    /// User with provided email already exists.
+    #[display(fmt = r#"user email "{}" is already taken"#, _0)]
+    EmailAlreadyTaken(#[error(not(source))] Email),
+    /// Name of the new user cannot be used as its display name.
+    #[display(fmt = r#"user name "{}" cannot be used as display name: {}"#, _0, _1)]
+    InvalidDisplayName(
+        #[error(not(source))] Name,
+        #[error(not(source))] DisplayNameError,
+    ),
+    /// Display name of the new user could be confused with display name
+    /// of administrator or moderator.
+    #[display(
+        fmt = r#"user display name "{}" could be confused with display name of staff member"#,
+        _0
+    )]
+    ConfusableDisplayName(#[error(not(source))] DisplayName),
+    /// Domain of provided email does not conform to the email policy.
+    #[display(fmt = r#"user email "{}" is rejected: {}"#, _0, _1)]
+    EmailNotAllowed(
+        #[error(not(source))] Email,
+        #[error(not(source))] EmailDomainError,
+    ),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(DatabaseError),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    NameHistory(HistoryError),
     /// Identifier generation error.
     #[display(fmt = "identifier generation error: {}", _0)]
     GenerateId(GenerateIdError),
 }
 
 /// Create user interactor.
-pub struct CreateUser<Database, GenerateId>
+pub struct CreateUser<Database, History, GenerateId, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
     GenerateId: GenerateUserId,
+    CurrentTime: Clock,
 {
     database: Database,
+    history: History,
     generate_id: GenerateId,
+    clock: CurrentTime,
+    config: NameHistoryConfig,
+    display_name_config: DisplayNameConfig,
+    email_policy: EmailPolicy,
 }
 
-impl<Database, GenerateId> CreateUser<Database, GenerateId>
+impl<Database, History, GenerateId, CurrentTime>
+    CreateUser<Database, History, GenerateId, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
     GenerateId: GenerateUserId,
+    CurrentTime: Clock,
 {
     /// Creates new create user interactor.
-    pub fn new(database: Database, generate_id: GenerateId) -> Self {
+    pub fn new(
+        database: Database,
+        history: History,
+        generate_id: GenerateId,
+        clock: CurrentTime,
+        config: NameHistoryConfig,
+        display_name_config: DisplayNameConfig,
+        email_policy: EmailPolicy,
+    ) -> Self {
         Self {
             database,
+            history,
             generate_id,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
         }
     }
 
-    /// Creates new user from provided unique user name.
+    /// Creates new user from provided unique user name and optional unique email.
+    ///
+    /// Provided email must conform to the email policy of the interactor.
+    /// Display name of the new user (which is its name) must not be confusable
+    /// with display name of any administrator or moderator.
+    #[allow(clippy::type_complexity)]
     pub async fn create_user(
         &self,
+        actor: Actor,
         name: Name,
-    ) -> Result<User, CreateUserError<Database::Error, GenerateId::Error>> {
+        email: Option<Email>,
+    ) -> Result<User, CreateUserError<Database::Error, History::Error, GenerateId::Error>> {
         let Self {
             database,
+            history,
             generate_id,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
         } = self;
 
-        let id = generate_id
-            .generate_id()
-            .map_err(CreateUserError::GenerateId)?;
+        if !actor.is_allowed_to_create() {
+            return Err(CreateUserError::Forbidden(actor));
+        }
+        let is_actor_active = is_actor_active(database, clock, &actor)
+            .await
+            .map_err(CreateUserError::Database)?;
+        if !is_actor_active {
+            return Err(CreateUserError::Inactive(actor));
+        }
+        if let Some(email) = &email {
+            if let Err(error) = email_policy.check(email) {
+                return Err(CreateUserError::EmailNotAllowed(email.clone(), error));
+            }
+        }
 
-        let is_name_unique = {
-            let user_by_name = find_one_by_name(database, &name)
-                .await
-                .map_err(CreateUserError::Database)?;
-            user_by_name.is_none()
-        };
-        if !is_name_unique {
+        let is_reserved = is_reserved_for_other(history, clock, config, &name, None)
+            .await
+            .map_err(CreateUserError::NameHistory)?;
+        if is_reserved {
             return Err(CreateUserError::NameAlreadyTaken(name));
         }
 
-        let display_name = DisplayName::new(name.as_str())
-            .expect("provided name should match display name requirements");
+        let display_name = match DisplayName::new(name.as_str()) {
+            Ok(display_name) => display_name,
+            Err(error) => return Err(CreateUserError::InvalidDisplayName(name, error)),
+        };
+        let is_confusable =
+            is_confusable_with_staff(database, display_name_config, &display_name, None)
+                .await
+                .map_err(CreateUserError::Database)?;
+        if is_confusable {
+            return Err(CreateUserError::ConfusableDisplayName(display_name));
+        }
+        let id = generate_id
+            .generate_id()
+            .map_err(CreateUserError::GenerateId)?;
         let data = UserData {
             display_name,
-            name,
+            name: name.clone(),
             role: Role::User,
-            email: None,
+            email: email.clone(),
             avatar: None,
+            status: AccountStatus::Active,
+            bio: None,
+            locale: None,
+            time_zone: None,
+            pronouns: None,
         };
-        let user = database
-            .create(id, data)
-            .await
-            .map_err(CreateUserError::Database)?;
+        let user =
+            database
+                .create(id, data)
+                .await
+                .map_err(|error| match (error.conflict(), email) {
+                    (Some(UserConflict::Name), _) => CreateUserError::NameAlreadyTaken(name),
+                    (Some(UserConflict::Email), Some(email)) => {
+                        CreateUserError::EmailAlreadyTaken(email)
+                    }
+                    _ => CreateUserError::Database(error),
+                })?;
         Ok(user)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{CreateUser, CreateUserError};
+    use crate::{
+        model::{Actor, Email, EmailPolicy, Name, Role},
+        repository::memory::MemoryNameHistoryDatabase,
+        use_case::{
+            fixture::{actor, database, FixedClock, SequentialIds},
+            DisplayNameConfig, NameHistoryConfig,
+        },
+    };
+
+    #[test]
+    fn create() {
+        let database = database([
+            ("admin", Role::Administrator),
+            ("moderator", Role::Moderator),
+            ("tanabe", Role::User),
+        ]);
+        let interactor = CreateUser::new(
+            database,
+            MemoryNameHistoryDatabase::new(),
+            SequentialIds::default(),
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+            DisplayNameConfig::default(),
+            EmailPolicy::default(),
+        );
+        let create = |actor, name, email: Option<&str>| {
+            let name = Name::new(name).unwrap();
+            let email = email.map(|email| Email::new(email).unwrap());
+            block_on(interactor.create_user(actor, name, email))
+        };
+
+        let user = create(Actor::System, "kotlinist", Some("kotlinist@example.com")).unwrap();
+        assert_eq!(user.data.name.as_str(), "kotlinist");
+        assert_eq!(user.data.display_name.as_str(), "kotlinist");
+        assert_eq!(user.data.role, Role::User);
+
+        let admin = actor("admin", Role::Administrator);
+        assert!(create(admin.clone(), "flexible", None).is_ok());
+        assert!(matches!(
+            create(admin.clone(), "Tanabe", None),
+            Err(CreateUserError::NameAlreadyTaken(_)),
+        ));
+        assert!(matches!(
+            create(admin, "project", Some("KOTLINIST@example.com")),
+            Err(CreateUserError::EmailAlreadyTaken(_)),
+        ));
+        assert!(matches!(
+            create(actor("tanabe", Role::User), "other", None),
+            Err(CreateUserError::Forbidden(_)),
+        ));
+        assert!(matches!(
+            create(Actor::System, "1234", None),
+            Err(CreateUserError::InvalidDisplayName(_, _)),
+        ));
+        assert!(matches!(
+            create(Actor::System, "rnoderator", None),
+            Err(CreateUserError::ConfusableDisplayName(_)),
+        ));
+    }
+}
@@ -1,15 +1,23 @@
 use derive_more::{Display, Error, From};
 
 use crate::{
-    model::{User, UserId},
-    repository::UserDatabase,
+    model::{Actor, User, UserAction, UserId},
+    repository::{Clock, UserDatabase},
 };
 
-use super::find_one::find_one_by_id;
+use super::{actor::is_actor_active, find_one::find_one_by_id};
 
 /// Error type of delete user use case.
 #[derive(Debug, Display, From, Error)]
 pub enum DeleteUserError<Error> {
+    /// Actor is not allowed to delete user.
+    #[display(fmt = "{} is not allowed to delete user", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
@@ -20,28 +28,39 @@ pub enum DeleteUserError<Error> {
 }
 
 /// Delete user interactor.
-pub struct DeleteUser<Database>
+pub struct DeleteUser<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    clock: CurrentTime,
 }
 
-impl<Database> DeleteUser<Database>
+impl<Database, CurrentTime> DeleteUser<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new delete user interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
     }
 
     /// Deletes user by provided identifier.
     pub async fn delete_user(
         &self,
+        actor: Actor,
         current_id: UserId,
     ) -> Result<User, DeleteUserError<Database::Error>> {
-        let Self { database } = self;
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed(UserAction::Delete, &current_id) {
+            return Err(DeleteUserError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(DeleteUserError::Inactive(actor));
+        }
 
         let id_exists = {
             let user_by_id = find_one_by_id(database, &current_id).await?;
@@ -55,3 +74,41 @@ where
         Ok(user)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use futures::{executor::block_on, TryStreamExt};
+
+    use super::{DeleteUser, DeleteUserError};
+    use crate::{
+        model::{Role, UserFilters, UserId},
+        repository::UserDatabase,
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn delete() {
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let interactor = DeleteUser::new(database.clone(), FixedClock::default());
+        let delete = |actor, id| block_on(interactor.delete_user(actor, UserId::new(id)));
+
+        assert!(matches!(
+            delete(actor("tanabe", Role::User), "kotlinist"),
+            Err(DeleteUserError::Forbidden(_)),
+        ));
+        let user = delete(actor("tanabe", Role::User), "tanabe").unwrap();
+        assert_eq!(user.data.name.as_str(), "tanabe");
+        assert!(matches!(
+            delete(actor("tanabe", Role::User), "tanabe"),
+            Err(DeleteUserError::Inactive(_)),
+        ));
+
+        let users: Vec<_> = block_on(async {
+            let users = database.read(UserFilters::default()).await?;
+            users.try_collect().await
+        })
+        .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, UserId::new("kotlinist"));
+    }
+}
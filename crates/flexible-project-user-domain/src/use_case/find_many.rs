@@ -0,0 +1,131 @@
+use std::{borrow::Cow, collections::HashMap, pin::pin};
+
+use futures::TryStreamExt;
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    model::{Actor, UserFilters, UserId, UserIdFilters, VisibleUser},
+    repository::UserDatabase,
+};
+
+/// Users which were found by their identifiers.
+#[derive(Debug, Clone, Default)]
+pub struct FoundUsers {
+    /// Found users by their identifiers in the order of the request.
+    pub users: IndexMap<UserId, VisibleUser>,
+    /// Requested identifiers by which no user was found, in the order of the request.
+    pub missing: Vec<UserId>,
+}
+
+/// Find users by identifiers interactor.
+pub struct FindUsersByIds<Database>
+where
+    Database: UserDatabase,
+{
+    database: Database,
+}
+
+impl<Database> FindUsersByIds<Database>
+where
+    Database: UserDatabase,
+{
+    /// Creates new find users by identifiers interactor.
+    pub fn new(database: Database) -> Self {
+        Self { database }
+    }
+
+    /// Finds users by provided identifiers with one database query.
+    ///
+    /// Duplicate identifiers are found only once.
+    /// Any actor, including anonymous one (if `None`), is allowed to find users,
+    /// but private data is visible only to the user itself, administrators and the system.
+    pub async fn find_users_by_ids<Ids>(
+        &self,
+        actor: Option<Actor>,
+        ids: Ids,
+    ) -> Result<FoundUsers, Database::Error>
+    where
+        Ids: IntoIterator<Item = UserId>,
+    {
+        let Self { database } = self;
+        let ids: IndexSet<_> = ids.into_iter().collect();
+        if ids.is_empty() {
+            return Ok(FoundUsers::default());
+        }
+
+        let filter = {
+            let ids: Vec<_> = ids.iter().cloned().collect();
+            let id = UserIdFilters::builder().r#in(Cow::Owned(ids)).build();
+            UserFilters::builder().id(id).build()
+        };
+        let users = database.read(filter).await?;
+        let mut users = pin!(users);
+        let mut found = HashMap::with_capacity(ids.len());
+        while let Some(user) = users.try_next().await? {
+            found.insert(user.id.clone(), user);
+        }
+
+        let mut missing = Vec::new();
+        let users = ids
+            .into_iter()
+            .filter_map(|id| match found.remove(&id) {
+                Some(user) => Some((id, VisibleUser::new(user, actor.as_ref()))),
+                None => {
+                    missing.push(id);
+                    None
+                }
+            })
+            .collect();
+        Ok(FoundUsers { users, missing })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::FindUsersByIds;
+    use crate::{
+        model::{Role, UserId, VisibleUser},
+        use_case::fixture::{actor, database},
+    };
+
+    #[test]
+    fn order_and_missing() {
+        let database = database([
+            ("tanabe", Role::User),
+            ("kotlinist", Role::Moderator),
+            ("flexible", Role::Administrator),
+        ]);
+        let interactor = FindUsersByIds::new(database);
+        let ids = ["flexible", "unknown", "tanabe", "flexible", "missing"].map(UserId::new);
+
+        let found = block_on(interactor.find_users_by_ids(None, ids)).unwrap();
+        let users: Vec<_> = found.users.keys().map(UserId::as_str).collect();
+        assert_eq!(users, ["flexible", "tanabe"]);
+        let missing: Vec<_> = found.missing.iter().map(UserId::as_str).collect();
+        assert_eq!(missing, ["unknown", "missing"]);
+
+        let found = block_on(interactor.find_users_by_ids(None, [])).unwrap();
+        assert!(found.users.is_empty() && found.missing.is_empty());
+    }
+
+    #[test]
+    fn redaction() {
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let interactor = FindUsersByIds::new(database);
+        let ids = ["tanabe", "kotlinist"].map(UserId::new);
+
+        let actor = actor("tanabe", Role::User);
+        let found = block_on(interactor.find_users_by_ids(Some(actor), ids.clone())).unwrap();
+        let users: Vec<_> = found.users.into_values().collect();
+        assert!(matches!(
+            &users[..],
+            [VisibleUser::Private(_), VisibleUser::Public(_)]
+        ));
+
+        let found = block_on(interactor.find_users_by_ids(None, ids)).unwrap();
+        let is_public = |user| matches!(user, VisibleUser::Public(_));
+        assert!(found.users.into_values().all(is_public));
+    }
+}
@@ -0,0 +1,160 @@
+//! Fixtures which are shared by the tests of the use cases.
+
+use std::{cell::Cell, collections::BTreeMap, convert::Infallible, task::Poll};
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use fp_core::id::GenerateId;
+use futures::future::poll_fn;
+
+use crate::{
+    model::{
+        AccountStatus, Actor, DisplayName, Name, Role, User, UserData, UserFilters, UserId,
+        UserPatch,
+    },
+    repository::{
+        memory::{MemoryUserDatabase, MemoryUsers},
+        Clock, UserDatabase,
+    },
+};
+
+/// Clock which always returns the same time.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Default for FixedClock {
+    fn default() -> Self {
+        Self(Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap())
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Clock which returns the time set by the test,
+/// starting from the time of [fixed clock](FixedClock) by default.
+#[derive(Debug)]
+pub struct ManualClock(pub Cell<DateTime<Utc>>);
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        let FixedClock(now) = FixedClock::default();
+        Self(Cell::new(now))
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0.get()
+    }
+}
+
+/// Generator of sequential user identifiers.
+#[derive(Debug, Default)]
+pub struct SequentialIds(Cell<u32>);
+
+impl GenerateId<User> for SequentialIds {
+    type Error = Infallible;
+
+    fn generate_id(&self) -> Result<UserId, Self::Error> {
+        let Self(next) = self;
+        let id = next.get();
+        next.set(id + 1);
+        Ok(UserId::new(format!("generated-{id}")))
+    }
+}
+
+/// Creates active user data with provided name and role.
+pub fn user_data(name: &str, role: Role) -> UserData {
+    UserData {
+        name: Name::new(name).unwrap(),
+        display_name: DisplayName::new(name).unwrap(),
+        role,
+        email: None,
+        avatar: None,
+        status: AccountStatus::Active,
+        bio: None,
+        locale: None,
+        time_zone: None,
+        pronouns: None,
+    }
+}
+
+/// Creates database with users of provided names and roles,
+/// where identifier of each user is its name.
+pub fn database<const N: usize>(users: [(&str, Role); N]) -> MemoryUserDatabase {
+    let users = users.map(|(name, role)| User {
+        id: UserId::new(name),
+        data: user_data(name, role),
+    });
+    MemoryUserDatabase::with_users(users).unwrap()
+}
+
+/// Creates actor of the user by its name which is also its identifier.
+pub fn actor(name: &str, role: Role) -> Actor {
+    Actor::User {
+        id: UserId::new(name),
+        role,
+    }
+}
+
+/// Yields to other tasks once, so concurrently joined futures could interleave.
+pub async fn yield_now() {
+    let mut yielded = false;
+    poll_fn(|context| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        context.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Database which yields to other tasks before each change of the users.
+pub struct YieldingDatabase(pub MemoryUserDatabase);
+
+#[async_trait(?Send)]
+impl UserDatabase for YieldingDatabase {
+    type Error = <MemoryUserDatabase as UserDatabase>::Error;
+
+    async fn create(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        yield_now().await;
+        self.0.create(id, data).await
+    }
+
+    type Users = MemoryUsers;
+    async fn read(&self, filter: UserFilters<'_>) -> Result<Self::Users, Self::Error> {
+        self.0.read(filter).await
+    }
+
+    async fn count(&self, filter: UserFilters<'_>) -> Result<u64, Self::Error> {
+        self.0.count(filter).await
+    }
+
+    async fn count_by_role(
+        &self,
+        filter: UserFilters<'_>,
+    ) -> Result<BTreeMap<Role, u64>, Self::Error> {
+        self.0.count_by_role(filter).await
+    }
+
+    async fn update(&self, id: UserId, data: UserData) -> Result<User, Self::Error> {
+        yield_now().await;
+        self.0.update(id, data).await
+    }
+
+    async fn patch(&self, id: UserId, patch: UserPatch) -> Result<Option<User>, Self::Error> {
+        yield_now().await;
+        self.0.patch(id, patch).await
+    }
+
+    async fn delete(&self, id: UserId) -> Result<User, Self::Error> {
+        yield_now().await;
+        self.0.delete(id).await
+    }
+}
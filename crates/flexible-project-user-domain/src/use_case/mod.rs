@@ -1,14 +1,33 @@
 //! Use cases of the user microservice domain layer.
 
 pub use self::{
+    bulk::*,
+    count::CountUsers,
     create::{CreateUser, CreateUserError},
     delete::{DeleteUser, DeleteUserError},
-    read::FilterUsers,
+    find_many::{FindUsersByIds, FoundUsers},
+    moderation::*,
+    name::*,
+    password::*,
+    personal_data::*,
+    read::{FilterUsers, ReadUsersError, VisibleUsers},
+    sign_in::{SignIn, SignInError},
     update::*,
 };
 
+mod actor;
+mod bulk;
+mod count;
 mod create;
 mod delete;
+mod find_many;
 mod find_one;
+#[cfg(test)]
+pub(crate) mod fixture;
+mod moderation;
+mod name;
+mod password;
+mod personal_data;
 mod read;
+mod sign_in;
 mod update;
@@ -0,0 +1,80 @@
+use derive_more::{Display, Error, From};
+
+use crate::{
+    model::{AccountStatus, Actor, User, UserAction, UserId, UserPatch},
+    repository::{Clock, UserDatabase},
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+use super::can_moderate;
+
+/// Error type of ban user use case.
+#[derive(Debug, Display, From, Error)]
+pub enum BanUserError<Error> {
+    /// Actor is not allowed to ban the user.
+    #[display(fmt = "{} is not allowed to ban user", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    #[from(ignore)]
+    NoUser(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(Error),
+}
+
+/// Ban user interactor.
+pub struct BanUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> BanUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new ban user interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Bans user by its identifier permanently with provided reason.
+    pub async fn ban_user(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+        reason: String,
+    ) -> Result<User, BanUserError<Database::Error>> {
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed(UserAction::Ban, &current_id) {
+            return Err(BanUserError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(BanUserError::Inactive(actor));
+        }
+
+        let User { id, data } = {
+            let user_by_id = find_one_by_id(database, &current_id).await?;
+            user_by_id.ok_or_else(|| BanUserError::NoUser(current_id))?
+        };
+        if !can_moderate(&actor, data.role) {
+            return Err(BanUserError::Forbidden(actor));
+        }
+
+        let status = AccountStatus::Banned { reason };
+        let patch = UserPatch::builder().status(status).build();
+        let user = database.patch(id.clone(), patch).await?;
+        user.ok_or(BanUserError::NoUser(id))
+    }
+}
@@ -0,0 +1,22 @@
+pub use self::{
+    ban::{BanUser, BanUserError},
+    suspend::{SuspendUser, SuspendUserError},
+    unsuspend::{UnsuspendUser, UnsuspendUserError},
+};
+
+mod ban;
+mod suspend;
+mod unsuspend;
+
+use crate::model::{Actor, Role};
+
+/// Checks if the actor is allowed to moderate the user with provided role.
+///
+/// Moderators can moderate ordinary users only,
+/// while administrators and the system can moderate anyone.
+fn can_moderate(actor: &Actor, target_role: Role) -> bool {
+    match actor {
+        Actor::System => true,
+        Actor::User { role, .. } => *role == Role::Administrator || target_role == Role::User,
+    }
+}
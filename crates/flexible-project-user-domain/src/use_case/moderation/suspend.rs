@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use derive_more::{Display, Error, From};
+
+use crate::{
+    model::{AccountStatus, Actor, User, UserAction, UserId, UserPatch},
+    repository::{Clock, UserDatabase},
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+use super::can_moderate;
+
+/// Error type of suspend user use case.
+#[derive(Debug, Display, From, Error)]
+pub enum SuspendUserError<Error> {
+    /// Actor is not allowed to suspend the user.
+    #[display(fmt = "{} is not allowed to suspend user", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    #[from(ignore)]
+    NoUser(#[error(not(source))] UserId),
+    /// User is already banned, so it cannot be suspended.
+    #[display(fmt = r#"user "{}" is banned"#, _0)]
+    #[from(ignore)]
+    Banned(#[error(not(source))] UserId),
+    /// Suspension ends in the past.
+    #[display(fmt = "suspension end {} is in the past", _0)]
+    #[from(ignore)]
+    InvalidUntil(#[error(not(source))] DateTime<Utc>),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(Error),
+}
+
+/// Suspend user interactor.
+pub struct SuspendUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> SuspendUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new suspend user interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Suspends user by its identifier until provided time with provided reason.
+    ///
+    /// Existing suspension of the user is replaced with the new one.
+    pub async fn suspend_user(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+        until: DateTime<Utc>,
+        reason: String,
+    ) -> Result<User, SuspendUserError<Database::Error>> {
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed(UserAction::Suspend, &current_id) {
+            return Err(SuspendUserError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(SuspendUserError::Inactive(actor));
+        }
+        if until <= clock.now() {
+            return Err(SuspendUserError::InvalidUntil(until));
+        }
+
+        let User { id, data } = {
+            let user_by_id = find_one_by_id(database, &current_id).await?;
+            user_by_id.ok_or_else(|| SuspendUserError::NoUser(current_id))?
+        };
+        if !can_moderate(&actor, data.role) {
+            return Err(SuspendUserError::Forbidden(actor));
+        }
+        if let AccountStatus::Banned { .. } = data.status {
+            return Err(SuspendUserError::Banned(id));
+        }
+
+        let status = AccountStatus::Suspended { until, reason };
+        let patch = UserPatch::builder().status(status).build();
+        let user = database.patch(id.clone(), patch).await?;
+        user.ok_or(SuspendUserError::NoUser(id))
+    }
+}
@@ -0,0 +1,88 @@
+use derive_more::{Display, Error, From};
+
+use crate::{
+    model::{AccountStatus, Actor, User, UserAction, UserId, UserPatch},
+    repository::{Clock, UserDatabase},
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+use super::can_moderate;
+
+/// Error type of unsuspend user use case.
+#[derive(Debug, Display, From, Error)]
+pub enum UnsuspendUserError<Error> {
+    /// Actor is not allowed to unsuspend the user.
+    #[display(fmt = "{} is not allowed to unsuspend user", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    #[from(ignore)]
+    NoUser(#[error(not(source))] UserId),
+    /// User is not suspended.
+    #[display(fmt = r#"user "{}" is not suspended"#, _0)]
+    #[from(ignore)]
+    NotSuspended(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(Error),
+}
+
+/// Unsuspend user interactor.
+pub struct UnsuspendUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> UnsuspendUser<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new unsuspend user interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Lifts suspension of the user by its identifier.
+    ///
+    /// Bans are permanent and cannot be lifted.
+    pub async fn unsuspend_user(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+    ) -> Result<User, UnsuspendUserError<Database::Error>> {
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed(UserAction::Unsuspend, &current_id) {
+            return Err(UnsuspendUserError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UnsuspendUserError::Inactive(actor));
+        }
+
+        let User { id, data } = {
+            let user_by_id = find_one_by_id(database, &current_id).await?;
+            user_by_id.ok_or_else(|| UnsuspendUserError::NoUser(current_id))?
+        };
+        if !can_moderate(&actor, data.role) {
+            return Err(UnsuspendUserError::Forbidden(actor));
+        }
+        let AccountStatus::Suspended { .. } = data.status else {
+            return Err(UnsuspendUserError::NotSuspended(id));
+        };
+
+        let status = AccountStatus::Active;
+        let patch = UserPatch::builder().status(status).build();
+        let user = database.patch(id.clone(), patch).await?;
+        user.ok_or(UnsuspendUserError::NoUser(id))
+    }
+}
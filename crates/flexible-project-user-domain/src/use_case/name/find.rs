@@ -0,0 +1,119 @@
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{Actor, Name, NameChange, VisibleUser},
+    repository::{Clock, NameHistoryDatabase, UserDatabase},
+    use_case::find_one::{find_one_by_id, find_one_by_name},
+};
+
+use super::{find_reservation, NameHistoryConfig};
+
+/// Error type of find user by name use case.
+#[derive(Debug, Display, Error)]
+pub enum FindUserByNameError<DatabaseError, HistoryError> {
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    NameHistory(HistoryError),
+}
+
+/// User which was found by its current or former name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserByName {
+    /// User was found by its current name.
+    Current(VisibleUser),
+    /// User was found by its recent former name,
+    /// so the client should be redirected to the current name of the user.
+    Redirect {
+        /// Former name of the user which was used to find it.
+        former_name: Name,
+        /// User which owned the former name.
+        user: VisibleUser,
+    },
+}
+
+impl UserByName {
+    /// Returns found user regardless of the name which was used to find it.
+    pub fn into_user(self) -> VisibleUser {
+        match self {
+            Self::Current(user) | Self::Redirect { user, .. } => user,
+        }
+    }
+}
+
+/// Find user by name interactor.
+pub struct FindUserByName<Database, History, CurrentTime>
+where
+    Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    history: History,
+    clock: CurrentTime,
+    config: NameHistoryConfig,
+}
+
+impl<Database, History, CurrentTime> FindUserByName<Database, History, CurrentTime>
+where
+    Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new find user by name interactor.
+    pub fn new(
+        database: Database,
+        history: History,
+        clock: CurrentTime,
+        config: NameHistoryConfig,
+    ) -> Self {
+        Self {
+            database,
+            history,
+            clock,
+            config,
+        }
+    }
+
+    /// Finds user by its current name or by its recent former name.
+    ///
+    /// Any actor, including anonymous one (if `None`), is allowed to find user by name,
+    /// but private data is visible only to the user itself, administrators and the system.
+    pub async fn find_user_by_name(
+        &self,
+        actor: Option<Actor>,
+        name: Name,
+    ) -> Result<Option<UserByName>, FindUserByNameError<Database::Error, History::Error>> {
+        let Self {
+            database,
+            history,
+            clock,
+            config,
+        } = self;
+
+        let user = find_one_by_name(database, &name)
+            .await
+            .map_err(FindUserByNameError::Database)?;
+        if let Some(user) = user {
+            let user = VisibleUser::new(user, actor.as_ref());
+            return Ok(Some(UserByName::Current(user)));
+        }
+
+        let change = find_reservation(history, clock, config, &name)
+            .await
+            .map_err(FindUserByNameError::NameHistory)?;
+        let Some(NameChange { user_id, .. }) = change else {
+            return Ok(None);
+        };
+        let user = find_one_by_id(database, &user_id)
+            .await
+            .map_err(FindUserByNameError::Database)?;
+        let user = user.map(|user| UserByName::Redirect {
+            former_name: name,
+            user: VisibleUser::new(user, actor.as_ref()),
+        });
+        Ok(user)
+    }
+}
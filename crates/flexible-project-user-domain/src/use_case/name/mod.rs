@@ -0,0 +1,59 @@
+pub use self::find::{FindUserByName, FindUserByNameError, UserByName};
+
+mod find;
+
+use chrono::Duration;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    model::{Name, NameChange, UserId},
+    repository::{Clock, NameHistoryDatabase},
+};
+
+/// Configuration of user name history.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct NameHistoryConfig {
+    /// Duration after the name change during which former name of the user
+    /// cannot be claimed by other users and is resolved to its former owner.
+    #[builder(default = Duration::days(30))]
+    pub reuse_cooldown: Duration,
+}
+
+impl Default for NameHistoryConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Finds the latest change which released provided name if it is still reserved.
+pub(crate) async fn find_reservation<History, CurrentTime>(
+    history: History,
+    clock: CurrentTime,
+    config: &NameHistoryConfig,
+    name: &Name,
+) -> Result<Option<NameChange>, History::Error>
+where
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
+{
+    let change = history.find_latest_by_name(name).await?;
+    let change = change.filter(|change| change.is_reserved(config.reuse_cooldown, clock.now()));
+    Ok(change)
+}
+
+/// Checks if provided name is reserved for other user than provided one.
+pub(crate) async fn is_reserved_for_other<History, CurrentTime>(
+    history: History,
+    clock: CurrentTime,
+    config: &NameHistoryConfig,
+    name: &Name,
+    user_id: Option<&UserId>,
+) -> Result<bool, History::Error>
+where
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
+{
+    let change = find_reservation(history, clock, config, name).await?;
+    let is_reserved = change.is_some_and(|change| Some(&change.user_id) != user_id);
+    Ok(is_reserved)
+}
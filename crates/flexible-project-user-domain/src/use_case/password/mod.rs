@@ -0,0 +1,7 @@
+pub use self::{
+    request_reset::{PasswordResetConfig, RequestPasswordReset, RequestPasswordResetError},
+    reset::{ResetPassword, ResetPasswordError},
+};
+
+mod request_reset;
+mod reset;
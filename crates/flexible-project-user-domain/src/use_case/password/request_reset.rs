@@ -0,0 +1,163 @@
+use chrono::Duration;
+use derive_more::{Display, Error};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    model::{Email, Mail, PasswordReset, User},
+    repository::{Clock, GeneratePasswordResetToken, Mailer, PasswordResetDatabase, UserDatabase},
+    use_case::find_one::find_one_by_email,
+};
+
+/// Configuration of password reset use cases.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct PasswordResetConfig {
+    /// Duration after which password reset token cannot be used.
+    #[builder(default = Duration::hours(1))]
+    pub token_lifetime: Duration,
+    /// Maximal count of password resets which could be requested by one user
+    /// during [rate limit window](PasswordResetConfig::rate_limit_window).
+    #[builder(default = 3)]
+    pub max_requests: u64,
+    /// Duration of the window in which password reset requests of the user are limited.
+    #[builder(default = Duration::hours(1))]
+    pub rate_limit_window: Duration,
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Error type of request password reset use case.
+#[derive(Debug, Display, Error)]
+pub enum RequestPasswordResetError<DatabaseError, ResetDatabaseError, TokenError, MailerError> {
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Password reset database error.
+    #[display(fmt = "password reset database error: {}", _0)]
+    ResetDatabase(ResetDatabaseError),
+    /// Password reset token generation error.
+    #[display(fmt = "password reset token generation error: {}", _0)]
+    GenerateToken(TokenError),
+    /// Mailer error.
+    #[display(fmt = "mailer error: {}", _0)]
+    Mailer(MailerError),
+}
+
+/// Request password reset interactor.
+pub struct RequestPasswordReset<Database, ResetDatabase, GenerateToken, MailSender, CurrentTime>
+where
+    Database: UserDatabase,
+    ResetDatabase: PasswordResetDatabase,
+    GenerateToken: GeneratePasswordResetToken,
+    MailSender: Mailer,
+    CurrentTime: Clock,
+{
+    database: Database,
+    reset_database: ResetDatabase,
+    generate_token: GenerateToken,
+    mailer: MailSender,
+    clock: CurrentTime,
+    config: PasswordResetConfig,
+}
+
+impl<Database, ResetDatabase, GenerateToken, MailSender, CurrentTime>
+    RequestPasswordReset<Database, ResetDatabase, GenerateToken, MailSender, CurrentTime>
+where
+    Database: UserDatabase,
+    ResetDatabase: PasswordResetDatabase,
+    GenerateToken: GeneratePasswordResetToken,
+    MailSender: Mailer,
+    CurrentTime: Clock,
+{
+    /// Creates new request password reset interactor.
+    pub fn new(
+        database: Database,
+        reset_database: ResetDatabase,
+        generate_token: GenerateToken,
+        mailer: MailSender,
+        clock: CurrentTime,
+        config: PasswordResetConfig,
+    ) -> Self {
+        Self {
+            database,
+            reset_database,
+            generate_token,
+            mailer,
+            clock,
+            config,
+        }
+    }
+
+    /// Sends password reset token to the user with provided email.
+    ///
+    /// Nothing is sent (and no error is returned) if there is no user with such email
+    /// or if the user has reached the limit of password reset requests,
+    /// so this use case cannot be used to find out which emails are registered.
+    #[allow(clippy::type_complexity)]
+    pub async fn request_password_reset(
+        &self,
+        email: Email,
+    ) -> Result<
+        (),
+        RequestPasswordResetError<
+            Database::Error,
+            ResetDatabase::Error,
+            GenerateToken::Error,
+            MailSender::Error,
+        >,
+    > {
+        let Self {
+            database,
+            reset_database,
+            generate_token,
+            mailer,
+            clock,
+            config,
+        } = self;
+
+        let user_by_email = find_one_by_email(database, Some(email.clone()))
+            .await
+            .map_err(RequestPasswordResetError::Database)?;
+        let Some(User { id, data }) = user_by_email else {
+            return Ok(());
+        };
+
+        let now = clock.now();
+        let requests_count = reset_database
+            .count_created_since(id.clone(), now - config.rate_limit_window)
+            .await
+            .map_err(RequestPasswordResetError::ResetDatabase)?;
+        if requests_count >= config.max_requests {
+            return Ok(());
+        }
+
+        let reset_token = generate_token
+            .generate_token()
+            .map_err(RequestPasswordResetError::GenerateToken)?;
+        let reset = PasswordReset {
+            token_hash: generate_token.hash_token(&reset_token),
+            user_id: id,
+            created_at: now,
+            expires_at: now + config.token_lifetime,
+        };
+        let PasswordReset { expires_at, .. } = reset_database
+            .create(reset)
+            .await
+            .map_err(RequestPasswordResetError::ResetDatabase)?;
+
+        let mail = Mail::PasswordReset {
+            recipient: email,
+            name: data.name,
+            token: reset_token,
+            expires_at,
+        };
+        mailer
+            .send(mail)
+            .await
+            .map_err(RequestPasswordResetError::Mailer)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{Password, PasswordReset, PasswordResetToken, User, UserCredentials, UserId},
+    repository::{
+        Clock, CredentialsDatabase, GeneratePasswordResetToken, HashPassword,
+        PasswordResetDatabase, UserDatabase,
+    },
+    use_case::find_one::find_one_by_id,
+};
+
+/// Error type of reset password use case.
+#[derive(Debug, Display, Error)]
+pub enum ResetPasswordError<DatabaseError, CredentialsError, ResetDatabaseError, HashError> {
+    /// Password reset token is unknown, was already used or was invalidated by password change.
+    #[display(fmt = "password reset token is invalid")]
+    InvalidToken,
+    /// Password reset token has expired.
+    #[display(fmt = "password reset token has expired")]
+    ExpiredToken,
+    /// No user was found by identifier from password reset.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    NoUser(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Credentials database error.
+    #[display(fmt = "credentials database error: {}", _0)]
+    Credentials(CredentialsError),
+    /// Password reset database error.
+    #[display(fmt = "password reset database error: {}", _0)]
+    ResetDatabase(ResetDatabaseError),
+    /// Password hashing error.
+    #[display(fmt = "password hashing error: {}", _0)]
+    HashPassword(HashError),
+}
+
+/// Reset password interactor.
+pub struct ResetPassword<Database, Credentials, ResetDatabase, GenerateToken, Hasher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    ResetDatabase: PasswordResetDatabase,
+    GenerateToken: GeneratePasswordResetToken,
+    Hasher: HashPassword,
+    CurrentTime: Clock,
+{
+    database: Database,
+    credentials: Credentials,
+    reset_database: ResetDatabase,
+    generate_token: GenerateToken,
+    hasher: Hasher,
+    clock: CurrentTime,
+}
+
+impl<Database, Credentials, ResetDatabase, GenerateToken, Hasher, CurrentTime>
+    ResetPassword<Database, Credentials, ResetDatabase, GenerateToken, Hasher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    ResetDatabase: PasswordResetDatabase,
+    GenerateToken: GeneratePasswordResetToken,
+    Hasher: HashPassword,
+    CurrentTime: Clock,
+{
+    /// Creates new reset password interactor.
+    pub fn new(
+        database: Database,
+        credentials: Credentials,
+        reset_database: ResetDatabase,
+        generate_token: GenerateToken,
+        hasher: Hasher,
+        clock: CurrentTime,
+    ) -> Self {
+        Self {
+            database,
+            credentials,
+            reset_database,
+            generate_token,
+            hasher,
+            clock,
+        }
+    }
+
+    /// Sets new password of the user which requested password reset with provided token.
+    ///
+    /// Token can be used only once: it is consumed atomically before the password is changed,
+    /// so concurrent requests with the same token cannot both succeed,
+    /// and all other pending password resets of the user are invalidated afterwards.
+    /// Consumed token cannot be used again even if the password was not changed
+    /// because of an error, so the user must request new password reset.
+    #[allow(clippy::type_complexity)]
+    pub async fn reset_password(
+        &self,
+        token: PasswordResetToken,
+        password: Password,
+    ) -> Result<
+        User,
+        ResetPasswordError<
+            Database::Error,
+            Credentials::Error,
+            ResetDatabase::Error,
+            Hasher::Error,
+        >,
+    > {
+        let Self {
+            database,
+            credentials,
+            reset_database,
+            generate_token,
+            hasher,
+            clock,
+        } = self;
+
+        let reset = {
+            let token_hash = generate_token.hash_token(&token);
+            let reset = reset_database
+                .take_by_token_hash(&token_hash)
+                .await
+                .map_err(ResetPasswordError::ResetDatabase)?;
+            reset.ok_or(ResetPasswordError::InvalidToken)?
+        };
+        let now = clock.now();
+        if reset.is_expired(now) {
+            return Err(ResetPasswordError::ExpiredToken);
+        }
+        let PasswordReset {
+            user_id,
+            created_at,
+            ..
+        } = reset;
+
+        let current_credentials = credentials
+            .read(user_id.clone())
+            .await
+            .map_err(ResetPasswordError::Credentials)?;
+        if let Some(UserCredentials { updated_at, .. }) = current_credentials {
+            if updated_at >= created_at {
+                return Err(ResetPasswordError::InvalidToken);
+            }
+        }
+
+        let user = find_one_by_id(database, &user_id)
+            .await
+            .map_err(ResetPasswordError::Database)?;
+        let user = user.ok_or_else(|| ResetPasswordError::NoUser(user_id.clone()))?;
+
+        let password_hash = hasher
+            .hash_password(&password)
+            .map_err(ResetPasswordError::HashPassword)?;
+        let new_credentials = UserCredentials {
+            password_hash,
+            updated_at: now,
+        };
+        credentials
+            .upsert(user_id.clone(), new_credentials)
+            .await
+            .map_err(ResetPasswordError::Credentials)?;
+        reset_database
+            .delete_by_user(user_id)
+            .await
+            .map_err(ResetPasswordError::ResetDatabase)?;
+        Ok(user)
+    }
+}
@@ -0,0 +1,195 @@
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{Actor, User, UserAction, UserEvent, UserId},
+    repository::{
+        Clock, CredentialsDatabase, NameHistoryDatabase, ObjectStore, PasswordResetDatabase,
+        PublishUserEvent, UserDatabase,
+    },
+    use_case::{actor::is_actor_active, find_one::find_one_by_id, update::avatar_key_prefix},
+};
+
+/// Error type of erase user use case.
+#[derive(Debug, Display, Error)]
+pub enum EraseUserError<
+    DatabaseError,
+    CredentialsError,
+    HistoryError,
+    ResetError,
+    StoreError,
+    PublishError,
+> {
+    /// Actor is not allowed to erase the user.
+    #[display(fmt = "{} is not allowed to erase user", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    NoUser(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Credentials database error.
+    #[display(fmt = "credentials database error: {}", _0)]
+    Credentials(CredentialsError),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    NameHistory(HistoryError),
+    /// Password reset database error.
+    #[display(fmt = "password reset database error: {}", _0)]
+    PasswordReset(ResetError),
+    /// Object store error.
+    #[display(fmt = "object store error: {}", _0)]
+    ObjectStore(StoreError),
+    /// User event publisher error.
+    #[display(fmt = "user event publisher error: {}", _0)]
+    Publish(PublishError),
+}
+
+/// Erase user interactor.
+pub struct EraseUser<Database, Credentials, History, Resets, Store, Publisher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    History: NameHistoryDatabase,
+    Resets: PasswordResetDatabase,
+    Store: ObjectStore,
+    Publisher: PublishUserEvent,
+    CurrentTime: Clock,
+{
+    database: Database,
+    credentials: Credentials,
+    history: History,
+    resets: Resets,
+    store: Store,
+    publisher: Publisher,
+    clock: CurrentTime,
+}
+
+impl<Database, Credentials, History, Resets, Store, Publisher, CurrentTime>
+    EraseUser<Database, Credentials, History, Resets, Store, Publisher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    History: NameHistoryDatabase,
+    Resets: PasswordResetDatabase,
+    Store: ObjectStore,
+    Publisher: PublishUserEvent,
+    CurrentTime: Clock,
+{
+    /// Creates new erase user interactor.
+    pub fn new(
+        database: Database,
+        credentials: Credentials,
+        history: History,
+        resets: Resets,
+        store: Store,
+        publisher: Publisher,
+        clock: CurrentTime,
+    ) -> Self {
+        Self {
+            database,
+            credentials,
+            history,
+            resets,
+            store,
+            publisher,
+            clock,
+        }
+    }
+
+    /// Erases all data of the user by provided identifier from the user microservice:
+    /// its credentials, name history, pending password resets, uploaded avatar images
+    /// and the user itself.
+    ///
+    /// Former names of the user are released immediately.
+    /// [Erased](UserEvent::Erased) event is published before the user itself is deleted,
+    /// so other services could scrub their references to the user.
+    ///
+    /// Erasure is idempotent, so it can be resumed by retrying it if any step fails:
+    /// while the event is not published, the user still exists and can be erased again.
+    /// The event could be published more than once, so its consumers must be idempotent too.
+    #[allow(clippy::type_complexity)]
+    pub async fn erase_user(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+    ) -> Result<
+        User,
+        EraseUserError<
+            Database::Error,
+            Credentials::Error,
+            History::Error,
+            Resets::Error,
+            Store::Error,
+            Publisher::Error,
+        >,
+    > {
+        let Self {
+            database,
+            credentials,
+            history,
+            resets,
+            store,
+            publisher,
+            clock,
+        } = self;
+
+        if !actor.is_allowed(UserAction::Erase, &current_id) {
+            return Err(EraseUserError::Forbidden(actor));
+        }
+        let is_actor_active = is_actor_active(database, clock, &actor)
+            .await
+            .map_err(EraseUserError::Database)?;
+        if !is_actor_active {
+            return Err(EraseUserError::Inactive(actor));
+        }
+
+        let id_exists = find_one_by_id(database, &current_id)
+            .await
+            .map_err(EraseUserError::Database)?
+            .is_some();
+        if !id_exists {
+            return Err(EraseUserError::NoUser(current_id));
+        }
+
+        credentials
+            .delete(current_id.clone())
+            .await
+            .map_err(EraseUserError::Credentials)?;
+        history
+            .delete_by_user(current_id.clone())
+            .await
+            .map_err(EraseUserError::NameHistory)?;
+        resets
+            .delete_by_user(current_id.clone())
+            .await
+            .map_err(EraseUserError::PasswordReset)?;
+        let avatar_keys = store
+            .list(&avatar_key_prefix(&current_id))
+            .await
+            .map_err(EraseUserError::ObjectStore)?;
+        for key in avatar_keys {
+            store
+                .delete(&key)
+                .await
+                .map_err(EraseUserError::ObjectStore)?;
+        }
+
+        let event = UserEvent::Erased {
+            id: current_id.clone(),
+            erased_at: clock.now(),
+        };
+        publisher
+            .publish(event)
+            .await
+            .map_err(EraseUserError::Publish)?;
+        let user = database
+            .delete(current_id)
+            .await
+            .map_err(EraseUserError::Database)?;
+        Ok(user)
+    }
+}
@@ -0,0 +1,136 @@
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{Actor, PersonalData, UserAction, UserCredentials, UserId},
+    repository::{
+        Clock, CredentialsDatabase, NameHistoryDatabase, PersonalDataSource, UserDatabase,
+    },
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+/// Error type of export personal data use case.
+#[derive(Debug, Display, Error)]
+pub enum ExportPersonalDataError<DatabaseError, CredentialsError, HistoryError, SourceError> {
+    /// Actor is not allowed to export personal data of the user.
+    #[display(fmt = "{} is not allowed to export personal data", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    NoUser(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Credentials database error.
+    #[display(fmt = "credentials database error: {}", _0)]
+    Credentials(CredentialsError),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    NameHistory(HistoryError),
+    /// External personal data source error.
+    #[display(fmt = "personal data source error: {}", _0)]
+    Source(SourceError),
+}
+
+/// Export personal data interactor.
+pub struct ExportPersonalData<Database, Credentials, History, Source, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    History: NameHistoryDatabase,
+    Source: PersonalDataSource,
+    CurrentTime: Clock,
+{
+    database: Database,
+    credentials: Credentials,
+    history: History,
+    source: Source,
+    clock: CurrentTime,
+}
+
+impl<Database, Credentials, History, Source, CurrentTime>
+    ExportPersonalData<Database, Credentials, History, Source, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    History: NameHistoryDatabase,
+    Source: PersonalDataSource,
+    CurrentTime: Clock,
+{
+    /// Creates new export personal data interactor.
+    pub fn new(
+        database: Database,
+        credentials: Credentials,
+        history: History,
+        source: Source,
+        clock: CurrentTime,
+    ) -> Self {
+        Self {
+            database,
+            credentials,
+            history,
+            source,
+            clock,
+        }
+    }
+
+    /// Collects all personal data of the user by provided identifier,
+    /// including data which is held by other services of the system.
+    ///
+    /// Password hash of the user is never exported.
+    #[allow(clippy::type_complexity)]
+    pub async fn export_personal_data(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+    ) -> Result<
+        PersonalData,
+        ExportPersonalDataError<Database::Error, Credentials::Error, History::Error, Source::Error>,
+    > {
+        let Self {
+            database,
+            credentials,
+            history,
+            source,
+            clock,
+        } = self;
+
+        if !actor.is_allowed(UserAction::ExportPersonalData, &current_id) {
+            return Err(ExportPersonalDataError::Forbidden(actor));
+        }
+        let is_actor_active = is_actor_active(database, clock, &actor)
+            .await
+            .map_err(ExportPersonalDataError::Database)?;
+        if !is_actor_active {
+            return Err(ExportPersonalDataError::Inactive(actor));
+        }
+
+        let user = find_one_by_id(database, &current_id)
+            .await
+            .map_err(ExportPersonalDataError::Database)?
+            .ok_or_else(|| ExportPersonalDataError::NoUser(current_id))?;
+        let password_updated_at = credentials
+            .read(user.id.clone())
+            .await
+            .map_err(ExportPersonalDataError::Credentials)?
+            .map(|UserCredentials { updated_at, .. }| updated_at);
+        let name_history = history
+            .read_by_user(user.id.clone())
+            .await
+            .map_err(ExportPersonalDataError::NameHistory)?;
+        let external = source
+            .collect(user.id.clone())
+            .await
+            .map_err(ExportPersonalDataError::Source)?;
+
+        let personal_data = PersonalData {
+            user,
+            password_updated_at,
+            name_history,
+            external,
+        };
+        Ok(personal_data)
+    }
+}
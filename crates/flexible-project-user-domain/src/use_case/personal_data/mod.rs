@@ -0,0 +1,7 @@
+pub use self::{
+    erase::{EraseUser, EraseUserError},
+    export::{ExportPersonalData, ExportPersonalDataError},
+};
+
+mod erase;
+mod export;
@@ -1,28 +1,154 @@
-use crate::{model::UserFilters, repository::UserDatabase};
+use derive_more::{Display, Error, From};
+use futures::{
+    stream::{self, Map, Repeat, Zip},
+    StreamExt,
+};
+
+use crate::{
+    model::{Actor, User, UserFilters, VisibleUser},
+    repository::{Clock, UserDatabase},
+};
+
+/// Error type of filter and count users use cases.
+#[derive(Debug, Display, From, Error)]
+pub enum ReadUsersError<Error> {
+    /// Actor is not allowed to filter users by their private data.
+    #[display(fmt = "actor is not allowed to read private data of users")]
+    #[from(ignore)]
+    Forbidden,
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(Error),
+}
+
+/// Stream of filtered users which are visible to the actor which reads them.
+pub type VisibleUsers<Users, Error> = Map<
+    Zip<Users, Repeat<Option<Actor>>>,
+    fn((Result<User, Error>, Option<Actor>)) -> Result<VisibleUser, Error>,
+>;
 
 /// Filter users interactor.
-pub struct FilterUsers<Database>
+pub struct FilterUsers<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    clock: CurrentTime,
 }
 
-impl<Database> FilterUsers<Database>
+impl<Database, CurrentTime> FilterUsers<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new filter users interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
     }
 
     /// Filters users by provided filter object.
+    ///
+    /// Account status filter is evaluated at the current time.
+    /// Public data of users is visible to any actor, including anonymous one (if `None`),
+    /// but only the system and administrators are allowed to filter users by private data.
     pub async fn filter_users(
         &self,
+        actor: Option<Actor>,
         filter: UserFilters<'_>,
-    ) -> Result<Database::Users, Database::Error> {
-        let Self { database } = self;
-        database.read(filter).await
+    ) -> Result<VisibleUsers<Database::Users, Database::Error>, ReadUsersError<Database::Error>>
+    {
+        let Self { database, clock } = self;
+        check_filter(actor.as_ref(), &filter)?;
+        let filter = filter.with_status_at(clock.now());
+        let users = database.read(filter).await?;
+        let users = users
+            .zip(stream::repeat(actor))
+            .map(visible_user as fn(_) -> _);
+        Ok(users)
+    }
+}
+
+/// Checks if the actor is allowed to filter users by provided filter object.
+pub(super) fn check_filter<Error>(
+    actor: Option<&Actor>,
+    filter: &UserFilters<'_>,
+) -> Result<(), ReadUsersError<Error>> {
+    let is_allowed = actor.is_some_and(Actor::is_allowed_to_read_all);
+    if filter.is_private() && !is_allowed {
+        return Err(ReadUsersError::Forbidden);
+    }
+    Ok(())
+}
+
+fn visible_user<Error>(
+    (user, actor): (Result<User, Error>, Option<Actor>),
+) -> Result<VisibleUser, Error> {
+    let user = user?;
+    Ok(VisibleUser::new(user, actor.as_ref()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use futures::{executor::block_on, TryStreamExt};
+
+    use super::{FilterUsers, ReadUsersError};
+    use crate::{
+        model::{Actor, Role, RoleFilters, UserDataFilters, UserFilters, UserId, VisibleUser},
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn redaction() {
+        let database = database([("tanabe", Role::User), ("flexible", Role::Administrator)]);
+        let interactor = FilterUsers::new(database, FixedClock::default());
+        let private_users = |actor| {
+            let users = block_on(interactor.filter_users(actor, UserFilters::default())).unwrap();
+            let users: Vec<_> = block_on(users.try_collect()).unwrap();
+            let ids: Vec<_> = users
+                .into_iter()
+                .filter_map(|user| match user {
+                    VisibleUser::Private(user) => Some(user.id),
+                    VisibleUser::Public(user) => {
+                        assert_eq!(user.data.email, None);
+                        None
+                    }
+                })
+                .collect();
+            ids
+        };
+
+        assert!(private_users(None).is_empty());
+        let ids = private_users(Some(actor("tanabe", Role::User)));
+        assert_eq!(ids, [UserId::new("tanabe")]);
+        let ids = private_users(Some(actor("kotlinist", Role::Moderator)));
+        assert!(ids.is_empty());
+        let ids = private_users(Some(actor("flexible", Role::Administrator)));
+        assert_eq!(ids.len(), 2);
+        assert_eq!(private_users(Some(Actor::System)).len(), 2);
+    }
+
+    #[test]
+    fn private_filter() {
+        let database = database([("tanabe", Role::User), ("flexible", Role::Administrator)]);
+        let interactor = FilterUsers::new(database, FixedClock::default());
+        let filter = || {
+            let role = RoleFilters::builder()
+                .eq(Cow::Owned(Role::Administrator))
+                .build();
+            let data = UserDataFilters::builder().role(role).build();
+            UserFilters::builder().data(data).build()
+        };
+
+        let result = block_on(interactor.filter_users(None, filter()));
+        assert!(matches!(result, Err(ReadUsersError::Forbidden)));
+        let moderator = actor("kotlinist", Role::Moderator);
+        let result = block_on(interactor.filter_users(Some(moderator), filter()));
+        assert!(matches!(result, Err(ReadUsersError::Forbidden)));
+        let users = block_on(interactor.filter_users(Some(Actor::System), filter())).unwrap();
+        let users: Vec<_> = block_on(users.try_collect()).unwrap();
+        assert_eq!(users.len(), 1);
     }
 }
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use derive_more::{Display, Error};
+
+use crate::{
+    model::{AccountStatus, Name, Password, User, UserCredentials},
+    repository::{Clock, CredentialsDatabase, HashPassword, UserDatabase},
+    use_case::find_one::find_one_by_name,
+};
+
+/// Error type of sign in use case.
+#[derive(Debug, Display, Error)]
+pub enum SignInError<DatabaseError, CredentialsError, HashError> {
+    /// No user exists with provided name and password.
+    #[display(fmt = "user name or password is invalid")]
+    InvalidCredentials,
+    /// User account is suspended until provided time.
+    #[display(fmt = "user is suspended until {}", _0)]
+    Suspended(#[error(not(source))] DateTime<Utc>),
+    /// User account is banned.
+    #[display(fmt = "user is banned")]
+    Banned,
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Credentials database error.
+    #[display(fmt = "credentials database error: {}", _0)]
+    Credentials(CredentialsError),
+    /// Password hashing error.
+    #[display(fmt = "password hashing error: {}", _0)]
+    HashPassword(HashError),
+}
+
+/// Sign in interactor.
+pub struct SignIn<Database, Credentials, Hasher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    Hasher: HashPassword,
+    CurrentTime: Clock,
+{
+    database: Database,
+    credentials: Credentials,
+    hasher: Hasher,
+    clock: CurrentTime,
+}
+
+impl<Database, Credentials, Hasher, CurrentTime> SignIn<Database, Credentials, Hasher, CurrentTime>
+where
+    Database: UserDatabase,
+    Credentials: CredentialsDatabase,
+    Hasher: HashPassword,
+    CurrentTime: Clock,
+{
+    /// Creates new sign in interactor.
+    pub fn new(
+        database: Database,
+        credentials: Credentials,
+        hasher: Hasher,
+        clock: CurrentTime,
+    ) -> Self {
+        Self {
+            database,
+            credentials,
+            hasher,
+            clock,
+        }
+    }
+
+    /// Checks credentials of the user by its name and password.
+    ///
+    /// Suspended or banned users are not allowed to sign in.
+    #[allow(clippy::type_complexity)]
+    pub async fn sign_in(
+        &self,
+        name: Name,
+        password: Password,
+    ) -> Result<User, SignInError<Database::Error, Credentials::Error, Hasher::Error>> {
+        let Self {
+            database,
+            credentials,
+            hasher,
+            clock,
+        } = self;
+
+        let user = find_one_by_name(database, &name)
+            .await
+            .map_err(SignInError::Database)?;
+        let user = user.ok_or(SignInError::InvalidCredentials)?;
+
+        let UserCredentials { password_hash, .. } = {
+            let user_credentials = credentials
+                .read(user.id.clone())
+                .await
+                .map_err(SignInError::Credentials)?;
+            user_credentials.ok_or(SignInError::InvalidCredentials)?
+        };
+        let is_valid = hasher
+            .verify_password(&password, &password_hash)
+            .map_err(SignInError::HashPassword)?;
+        if !is_valid {
+            return Err(SignInError::InvalidCredentials);
+        }
+
+        let status = &user.data.status;
+        if !status.is_active(clock.now()) {
+            return match status {
+                AccountStatus::Suspended { until, .. } => Err(SignInError::Suspended(*until)),
+                _ => Err(SignInError::Banned),
+            };
+        }
+        Ok(user)
+    }
+}
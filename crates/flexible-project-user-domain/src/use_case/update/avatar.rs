@@ -1,54 +1,142 @@
 use derive_more::{Display, Error, From};
 
 use crate::{
-    model::{Avatar, User, UserData, UserId},
-    repository::UserDatabase,
-    use_case::find_one::find_one_by_id,
+    model::{Actor, Avatar, AvatarError, AvatarPolicy, User, UserAction, UserId, UserPatch},
+    repository::{Clock, UserDatabase},
+    use_case::actor::is_actor_active,
 };
 
 /// Error type of update user avatar use case.
 #[derive(Debug, Display, From, Error)]
 pub enum UpdateAvatarError<Error> {
+    /// Actor is not allowed to update user avatar.
+    #[display(fmt = "{} is not allowed to update user avatar", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
     NoUser(#[error(not(source))] UserId),
+    /// Provided avatar does not conform to the avatar policy.
+    #[display(fmt = r#"user avatar "{}" is rejected: {}"#, _0, _1)]
+    #[from(ignore)]
+    NotAllowed(
+        #[error(not(source))] Avatar,
+        #[error(not(source))] AvatarError,
+    ),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(Error),
 }
 
 /// Update avatar interactor.
-pub struct UpdateAvatar<Database>
+pub struct UpdateAvatar<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    clock: CurrentTime,
+    policy: AvatarPolicy,
 }
 
-impl<Database> UpdateAvatar<Database>
+impl<Database, CurrentTime> UpdateAvatar<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new update avatar interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(database: Database, clock: CurrentTime, policy: AvatarPolicy) -> Self {
+        Self {
+            database,
+            clock,
+            policy,
+        }
     }
 
     /// Updates avatar of the user by its identifier with provided avatar.
+    ///
+    /// Provided avatar must conform to the avatar policy of the interactor.
     pub async fn update_avatar(
         &self,
+        actor: Actor,
         current_id: UserId,
         avatar: Option<Avatar>,
     ) -> Result<User, UpdateAvatarError<Database::Error>> {
-        let Self { database } = self;
+        let Self {
+            database,
+            clock,
+            policy,
+        } = self;
 
-        let User { id, data } = {
-            let user_by_id = find_one_by_id(database, &current_id).await?;
-            user_by_id.ok_or_else(|| UpdateAvatarError::NoUser(current_id))?
+        if !actor.is_allowed(UserAction::UpdateAvatar, &current_id) {
+            return Err(UpdateAvatarError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UpdateAvatarError::Inactive(actor));
+        }
+        if let Some(avatar) = &avatar {
+            if let Err(error) = policy.check(avatar) {
+                return Err(UpdateAvatarError::NotAllowed(avatar.clone(), error));
+            }
+        }
+
+        let patch = UserPatch::builder().avatar(avatar).build();
+        let user = database.patch(current_id.clone(), patch).await?;
+        user.ok_or(UpdateAvatarError::NoUser(current_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{UpdateAvatar, UpdateAvatarError};
+    use crate::{
+        model::{Avatar, AvatarError, AvatarPolicy, Role, UserId},
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn update() {
+        let database = database([("moderator", Role::Moderator), ("tanabe", Role::User)]);
+        let interactor =
+            UpdateAvatar::new(database, FixedClock::default(), AvatarPolicy::default());
+        let update = |actor, id, avatar: Option<&str>| {
+            let avatar = avatar.map(|avatar| Avatar::new(avatar).unwrap());
+            block_on(interactor.update_avatar(actor, UserId::new(id), avatar))
         };
-        let data = UserData { avatar, ..data };
-        let user = database.update(id, data).await?;
-        Ok(user)
+        let tanabe = actor("tanabe", Role::User);
+
+        let url = "https://example.com/avatar.png";
+        let user = update(tanabe.clone(), "tanabe", Some(url)).unwrap();
+        assert_eq!(user.data.avatar.unwrap().as_str(), url);
+        let moderator = actor("moderator", Role::Moderator);
+        let user = update(moderator, "tanabe", None).unwrap();
+        assert_eq!(user.data.avatar, None);
+
+        assert!(matches!(
+            update(tanabe.clone(), "tanabe", Some("javascript:alert(1)")),
+            Err(UpdateAvatarError::NotAllowed(
+                _,
+                AvatarError::SchemeNotAllowed
+            )),
+        ));
+        assert!(matches!(
+            update(
+                tanabe.clone(),
+                "tanabe",
+                Some("https://localhost/avatar.png")
+            ),
+            Err(UpdateAvatarError::NotAllowed(_, AvatarError::PrivateHost)),
+        ));
+        assert!(matches!(
+            update(tanabe, "moderator", Some(url)),
+            Err(UpdateAvatarError::Forbidden(_)),
+        ));
     }
 }
@@ -0,0 +1,455 @@
+use std::{num::NonZeroU32, sync::Arc};
+
+use derive_more::{Display, Error};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    model::{
+        Actor, Avatar, AvatarError, AvatarPolicy, ImageFormat, User, UserAction, UserId, UserPatch,
+    },
+    repository::{Clock, ObjectStore, ResizeImage, UserDatabase},
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+/// Returns prefix of the keys of all uploaded avatar images of the user.
+pub(crate) fn avatar_key_prefix(user_id: &UserId) -> String {
+    format!("avatars/{user_id}/")
+}
+
+/// Checks if the key of uploaded avatar image belongs to the upload
+/// which is older than provided version.
+///
+/// Keys of uploaded images are `avatars/{id}/{version}/{size}.png`,
+/// keys without a version are left from the older uploads too.
+fn is_superseded(key: &str, prefix: &str, version: i64) -> bool {
+    let key_version = key
+        .strip_prefix(prefix)
+        .and_then(|key| key.split_once('/'))
+        .and_then(|(key_version, _)| key_version.parse::<i64>().ok());
+    key_version.is_none_or(|key_version| key_version < version)
+}
+
+/// Error which is returned when no avatar resolutions are provided.
+#[derive(Debug, Display, Error)]
+#[display(fmt = "at least one avatar resolution must be provided")]
+pub struct EmptyAvatarResolutions;
+
+/// Non-empty set of sizes of the square avatar images, sorted in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvatarResolutions(Vec<NonZeroU32>);
+
+impl AvatarResolutions {
+    /// Creates new set of avatar resolutions from provided sizes.
+    ///
+    /// Returns an error if no sizes are provided.
+    pub fn new(
+        resolutions: impl IntoIterator<Item = NonZeroU32>,
+    ) -> Result<Self, EmptyAvatarResolutions> {
+        let mut resolutions: Vec<_> = resolutions.into_iter().collect();
+        if resolutions.is_empty() {
+            return Err(EmptyAvatarResolutions);
+        }
+        resolutions.sort_unstable();
+        resolutions.dedup();
+        Ok(Self(resolutions))
+    }
+
+    /// Returns sizes of the avatar images in ascending order.
+    pub fn as_slice(&self) -> &[NonZeroU32] {
+        let Self(resolutions) = self;
+        resolutions
+    }
+}
+
+impl Default for AvatarResolutions {
+    fn default() -> Self {
+        let resolutions = [64, 128, 256].map(|size| NonZeroU32::new(size).unwrap());
+        Self(resolutions.to_vec())
+    }
+}
+
+/// Configuration of avatar upload use case.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct AvatarUploadConfig {
+    /// Maximal size of uploaded image in bytes.
+    #[builder(default = 5 * 1024 * 1024)]
+    pub max_size: usize,
+    /// Sizes of the square images which are produced from uploaded image.
+    ///
+    /// Avatar of the user is set to the URL of the largest image.
+    #[builder(default)]
+    pub resolutions: AvatarResolutions,
+    /// Policy which URL of the stored image should conform to.
+    #[builder(default)]
+    pub avatar_policy: AvatarPolicy,
+}
+
+impl Default for AvatarUploadConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Error type of upload avatar use case.
+#[derive(Debug, Display, Error)]
+pub enum UploadAvatarError<DatabaseError, StoreError, ImageError> {
+    /// Actor is not allowed to update user avatar.
+    #[display(fmt = "{} is not allowed to update user avatar", _0)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    Inactive(#[error(not(source))] Actor),
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    NoUser(#[error(not(source))] UserId),
+    /// Uploaded image is too large.
+    #[display(fmt = "image size {} exceeds maximal size {}", actual, max)]
+    TooLarge {
+        /// Maximal size of the image in bytes.
+        max: usize,
+        /// Actual size of the image in bytes.
+        actual: usize,
+    },
+    /// Format of uploaded image is not supported.
+    #[display(fmt = "image format is not supported")]
+    UnsupportedFormat,
+    /// Uploaded image cannot be processed.
+    #[display(fmt = "image processing error: {}", _0)]
+    Image(ImageError),
+    /// URL of stored image does not meet avatar requirements.
+    #[display(fmt = "stored avatar is invalid: {}", _0)]
+    Avatar(AvatarError),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+    /// Object store error.
+    #[display(fmt = "object store error: {}", _0)]
+    ObjectStore(StoreError),
+}
+
+/// Upload avatar interactor.
+pub struct UploadAvatar<Database, Store, Resizer, CurrentTime>
+where
+    Database: UserDatabase,
+    Store: ObjectStore,
+    Resizer: ResizeImage,
+    CurrentTime: Clock,
+{
+    database: Database,
+    store: Store,
+    resizer: Resizer,
+    clock: CurrentTime,
+    config: AvatarUploadConfig,
+}
+
+impl<Database, Store, Resizer, CurrentTime> UploadAvatar<Database, Store, Resizer, CurrentTime>
+where
+    Database: UserDatabase,
+    Store: ObjectStore,
+    Resizer: ResizeImage,
+    CurrentTime: Clock,
+{
+    /// Creates new upload avatar interactor.
+    pub fn new(
+        database: Database,
+        store: Store,
+        resizer: Resizer,
+        clock: CurrentTime,
+        config: AvatarUploadConfig,
+    ) -> Self {
+        Self {
+            database,
+            store,
+            resizer,
+            clock,
+            config,
+        }
+    }
+
+    /// Uploads image content as an avatar of the user by its identifier.
+    ///
+    /// Image is validated, normalized to the square images of configured resolutions
+    /// and stored in the object store. Avatar of the user is set to the served URL.
+    ///
+    /// Each upload is stored under its own version, so clients never get stale images
+    /// from their caches. Images of the previous uploads are deleted after the avatar is updated;
+    /// if that fails, they are deleted by the next upload or on erasure of the user.
+    /// Images of this upload are deleted if the avatar of the user was not updated.
+    #[allow(clippy::type_complexity)]
+    pub async fn upload_avatar(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+        content: Vec<u8>,
+    ) -> Result<User, UploadAvatarError<Database::Error, Store::Error, Resizer::Error>> {
+        let Self {
+            database,
+            store,
+            clock,
+            config,
+            ..
+        } = self;
+
+        if !actor.is_allowed(UserAction::UpdateAvatar, &current_id) {
+            return Err(UploadAvatarError::Forbidden(actor));
+        }
+        let is_actor_active = is_actor_active(database, clock, &actor)
+            .await
+            .map_err(UploadAvatarError::Database)?;
+        if !is_actor_active {
+            return Err(UploadAvatarError::Inactive(actor));
+        }
+
+        let AvatarUploadConfig { max_size, .. } = config;
+        if content.len() > *max_size {
+            let error = UploadAvatarError::TooLarge {
+                max: *max_size,
+                actual: content.len(),
+            };
+            return Err(error);
+        }
+        let format = ImageFormat::detect(&content).ok_or(UploadAvatarError::UnsupportedFormat)?;
+        let user_by_id = find_one_by_id(database, &current_id)
+            .await
+            .map_err(UploadAvatarError::Database)?;
+        if user_by_id.is_none() {
+            return Err(UploadAvatarError::NoUser(current_id));
+        }
+
+        let prefix = avatar_key_prefix(&current_id);
+        let version = clock.now().timestamp_millis();
+        let mut stored_keys = Vec::new();
+        let user = self
+            .store_avatar(
+                &current_id,
+                content,
+                format,
+                &prefix,
+                version,
+                &mut stored_keys,
+            )
+            .await;
+        let user = match user {
+            Ok(user) => user,
+            Err(error) => {
+                for key in &stored_keys {
+                    if store.delete(key).await.is_err() {
+                        tracing::warn!(user_id = %current_id, %key, "stored avatar image cannot be deleted");
+                    }
+                }
+                return Err(error);
+            }
+        };
+
+        let keys = match store.list(&prefix).await {
+            Ok(keys) => keys,
+            Err(_) => {
+                tracing::warn!(user_id = %user.id, "superseded avatar images cannot be listed");
+                return Ok(user);
+            }
+        };
+        let superseded = keys
+            .iter()
+            .filter(|key| is_superseded(key, &prefix, version));
+        for key in superseded {
+            if store.delete(key).await.is_err() {
+                tracing::warn!(user_id = %user.id, %key, "superseded avatar image cannot be deleted");
+            }
+        }
+        Ok(user)
+    }
+
+    /// Stores avatar images of the user and updates its avatar,
+    /// collecting keys of the stored images.
+    #[allow(clippy::type_complexity)]
+    async fn store_avatar(
+        &self,
+        current_id: &UserId,
+        content: Vec<u8>,
+        format: ImageFormat,
+        prefix: &str,
+        version: i64,
+        stored_keys: &mut Vec<String>,
+    ) -> Result<User, UploadAvatarError<Database::Error, Store::Error, Resizer::Error>> {
+        let Self {
+            database,
+            store,
+            resizer,
+            config,
+            ..
+        } = self;
+        let AvatarUploadConfig {
+            resolutions,
+            avatar_policy,
+            ..
+        } = config;
+
+        let content: Arc<[u8]> = content.into();
+        let mut avatar_url = None;
+        for size in resolutions.as_slice() {
+            let resized = resizer
+                .resize_square(content.clone(), format, size.get())
+                .await
+                .map_err(UploadAvatarError::Image)?;
+            let key = format!("{prefix}{version}/{size}.{}", ImageFormat::Png.extension());
+            let url = store
+                .put(&key, ImageFormat::Png.content_type(), resized)
+                .await
+                .map_err(UploadAvatarError::ObjectStore)?;
+            stored_keys.push(key);
+            avatar_url = Some(url);
+        }
+        let avatar = match avatar_url {
+            Some(url) => {
+                let avatar = Avatar::new(url).map_err(UploadAvatarError::Avatar)?;
+                avatar_policy
+                    .check(&avatar)
+                    .map_err(UploadAvatarError::Avatar)?;
+                Some(avatar)
+            }
+            None => None,
+        };
+
+        let patch = UserPatch::builder().avatar(avatar).build();
+        let user = database
+            .patch(current_id.clone(), patch)
+            .await
+            .map_err(UploadAvatarError::Database)?
+            .ok_or_else(|| UploadAvatarError::NoUser(current_id.clone()))?;
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, collections::BTreeMap, convert::Infallible, sync::Arc};
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use futures::executor::block_on;
+    use url::Url;
+
+    use super::{AvatarResolutions, AvatarUploadConfig, UploadAvatar, UploadAvatarError};
+    use crate::{
+        model::{Actor, AvatarPolicy, ImageFormat, Role, UserId},
+        repository::{ObjectStore, ResizeImage},
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[derive(Default)]
+    struct MemoryStore(RefCell<BTreeMap<String, Vec<u8>>>);
+
+    #[async_trait(?Send)]
+    impl ObjectStore for MemoryStore {
+        type Error = Infallible;
+
+        async fn put(&self, key: &str, _: &str, content: Vec<u8>) -> Result<Url, Self::Error> {
+            self.0.borrow_mut().insert(key.to_string(), content);
+            Ok(Url::parse("https://cdn.example.com/")
+                .unwrap()
+                .join(key)
+                .unwrap())
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<String>, Self::Error> {
+            let objects = self.0.borrow();
+            let keys = objects.keys().filter(|key| key.starts_with(prefix));
+            Ok(keys.cloned().collect())
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+            self.0.borrow_mut().remove(key);
+            Ok(())
+        }
+    }
+
+    struct SizeResizer;
+
+    #[async_trait(?Send)]
+    impl ResizeImage for SizeResizer {
+        type Error = Infallible;
+
+        async fn resize_square(
+            &self,
+            _: Arc<[u8]>,
+            _: ImageFormat,
+            size: u32,
+        ) -> Result<Vec<u8>, Self::Error> {
+            Ok(size.to_be_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn upload() {
+        assert!(AvatarResolutions::new([]).is_err());
+
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let store = MemoryStore::default();
+        let png = b"\x89PNG\r\n\x1a\nimage".to_vec();
+        let upload = |clock, actor, id, content| {
+            let config = AvatarUploadConfig::default();
+            let interactor = UploadAvatar::new(&database, &store, SizeResizer, clock, config);
+            block_on(interactor.upload_avatar(actor, UserId::new(id), content))
+        };
+        let keys = || store.0.borrow().keys().cloned().collect::<Vec<_>>();
+        let tanabe = actor("tanabe", Role::User);
+        let clock = FixedClock::default();
+        let version = clock.0.timestamp_millis();
+
+        block_on(store.put("avatars/tanabe/256.png", "image/png", Vec::new())).unwrap();
+        let user = upload(clock, tanabe.clone(), "tanabe", png.clone()).unwrap();
+        let avatar = user.data.avatar.unwrap();
+        assert_eq!(
+            avatar.as_str(),
+            format!("https://cdn.example.com/avatars/tanabe/{version}/256.png"),
+        );
+        assert_eq!(
+            keys(),
+            [128, 256, 64].map(|size| format!("avatars/tanabe/{version}/{size}.png")),
+        );
+
+        let later = FixedClock(clock.0 + Duration::minutes(1));
+        let later_version = later.0.timestamp_millis();
+        assert!(upload(later, tanabe.clone(), "tanabe", png.clone()).is_ok());
+        assert_eq!(
+            keys(),
+            [128, 256, 64].map(|size| format!("avatars/tanabe/{later_version}/{size}.png")),
+        );
+
+        assert!(matches!(
+            upload(clock, tanabe.clone(), "tanabe", b"GIF89a".to_vec()),
+            Err(UploadAvatarError::UnsupportedFormat),
+        ));
+        assert!(matches!(
+            upload(clock, tanabe, "kotlinist", png.clone()),
+            Err(UploadAvatarError::Forbidden(_)),
+        ));
+        assert!(matches!(
+            upload(clock, Actor::System, "unknown", png),
+            Err(UploadAvatarError::NoUser(_)),
+        ));
+        assert!(keys()
+            .iter()
+            .all(|key| !key.starts_with("avatars/unknown/")));
+    }
+
+    #[test]
+    fn invalid_avatar() {
+        let database = database([("tanabe", Role::User)]);
+        let store = MemoryStore::default();
+        let png = b"\x89PNG\r\n\x1a\nimage".to_vec();
+        let avatar_policy = AvatarPolicy::builder()
+            .allowed_hosts(vec!["avatars.example.com".to_string()])
+            .build();
+        let config = AvatarUploadConfig::builder()
+            .avatar_policy(avatar_policy)
+            .build();
+        let clock = FixedClock::default();
+        let interactor = UploadAvatar::new(&database, &store, SizeResizer, clock, config);
+
+        let tanabe = actor("tanabe", Role::User);
+        let user = block_on(interactor.upload_avatar(tanabe, UserId::new("tanabe"), png));
+        assert!(matches!(user, Err(UploadAvatarError::Avatar(_))));
+        assert!(store.0.borrow().is_empty());
+    }
+}
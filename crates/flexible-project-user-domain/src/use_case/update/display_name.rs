@@ -1,57 +1,202 @@
+use std::{borrow::Cow, pin::pin};
+
 use derive_more::{Display, Error, From};
+use futures::TryStreamExt;
+use typed_builder::TypedBuilder;
 
 use crate::{
-    model::{DisplayName, User, UserData, UserId},
-    repository::UserDatabase,
-    use_case::find_one::find_one_by_id,
+    model::{
+        Actor, DisplayName, Role, RoleFilters, User, UserAction, UserDataFilters, UserFilters,
+        UserId, UserIdFilters, UserPatch,
+    },
+    repository::{Clock, UserDatabase},
+    use_case::actor::is_actor_active,
 };
 
+/// Configuration of user display name checks.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct DisplayNameConfig {
+    /// Whether display names which could be confused with display names
+    /// of administrators or moderators should be rejected.
+    #[builder(default = true)]
+    pub reject_staff_confusables: bool,
+}
+
+impl Default for DisplayNameConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Checks if provided display name could be confused with display name
+/// of any administrator or moderator other than provided user (if any).
+pub(crate) async fn is_confusable_with_staff<Database>(
+    database: Database,
+    config: &DisplayNameConfig,
+    display_name: &DisplayName,
+    user_id: Option<&UserId>,
+) -> Result<bool, Database::Error>
+where
+    Database: UserDatabase,
+{
+    if !config.reject_staff_confusables {
+        return Ok(false);
+    }
+    let filter = {
+        let id = user_id.map(|id| UserIdFilters::builder().ne(Cow::Borrowed(id)).build());
+        const STAFF_ROLES: &[Role] = &[Role::Moderator, Role::Administrator];
+        let role = RoleFilters::builder()
+            .r#in(Cow::Borrowed(STAFF_ROLES))
+            .build();
+        let data = UserDataFilters::builder().role(role).build();
+        UserFilters {
+            id,
+            data: Some(data),
+        }
+    };
+    let staff = database.read(filter).await?;
+    let mut staff = pin!(staff);
+    while let Some(User { data, .. }) = staff.try_next().await? {
+        if display_name.is_confusable_with(&data.display_name) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// Error type of update user display name use case.
 #[derive(Debug, Display, From, Error)]
 pub enum UpdateDisplayNameError<Error> {
+    /// Actor is not allowed to update user display name.
+    #[display(fmt = "{} is not allowed to update user display name", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
     NoUser(#[error(not(source))] UserId),
+    /// Display name could be confused with display name of administrator or moderator.
+    #[display(
+        fmt = r#"user display name "{}" could be confused with display name of staff member"#,
+        _0
+    )]
+    #[from(ignore)]
+    Confusable(#[error(not(source))] DisplayName),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(Error),
 }
 
 /// Update display name interactor.
-pub struct UpdateDisplayName<Database>
+pub struct UpdateDisplayName<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    clock: CurrentTime,
+    config: DisplayNameConfig,
 }
 
-impl<Database> UpdateDisplayName<Database>
+impl<Database, CurrentTime> UpdateDisplayName<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new update display name interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(database: Database, clock: CurrentTime, config: DisplayNameConfig) -> Self {
+        Self {
+            database,
+            clock,
+            config,
+        }
     }
 
     /// Updates display name of the user by its identifier with provided display name.
     pub async fn update_display_name(
         &self,
+        actor: Actor,
         current_id: UserId,
         display_name: DisplayName,
     ) -> Result<User, UpdateDisplayNameError<Database::Error>> {
-        let Self { database } = self;
+        let Self {
+            database,
+            clock,
+            config,
+        } = self;
 
-        let User { id, data } = {
-            let user_by_id = find_one_by_id(database, &current_id).await?;
-            user_by_id.ok_or_else(|| UpdateDisplayNameError::NoUser(current_id))?
-        };
-        let data = UserData {
-            display_name,
-            ..data
+        if !actor.is_allowed(UserAction::UpdateDisplayName, &current_id) {
+            return Err(UpdateDisplayNameError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UpdateDisplayNameError::Inactive(actor));
+        }
+
+        if is_confusable_with_staff(database, config, &display_name, Some(&current_id)).await? {
+            return Err(UpdateDisplayNameError::Confusable(display_name));
+        }
+        let patch = UserPatch::builder().display_name(display_name).build();
+        let user = database.patch(current_id.clone(), patch).await?;
+        user.ok_or(UpdateDisplayNameError::NoUser(current_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{DisplayNameConfig, UpdateDisplayName, UpdateDisplayNameError};
+    use crate::{
+        model::{DisplayName, Role, UserId},
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn update() {
+        let database = database([("admin", Role::Administrator), ("tanabe", Role::User)]);
+        let update = |config, actor, id, display_name| {
+            let interactor = UpdateDisplayName::new(&database, FixedClock::default(), config);
+            let display_name = DisplayName::new(display_name).unwrap();
+            block_on(interactor.update_display_name(actor, UserId::new(id), display_name))
         };
-        let user = database.update(id, data).await?;
-        Ok(user)
+        let tanabe = actor("tanabe", Role::User);
+
+        let user = update(
+            DisplayNameConfig::default(),
+            tanabe.clone(),
+            "tanabe",
+            "Timur \u{200F}Tugushev",
+        )
+        .unwrap();
+        assert_eq!(user.data.display_name.as_str(), "Timur \u{200F}Tugushev");
+        assert!(matches!(
+            update(
+                DisplayNameConfig::default(),
+                tanabe.clone(),
+                "tanabe",
+                "\u{0410}dm\u{0456}n"
+            ),
+            Err(UpdateDisplayNameError::Confusable(_)),
+        ));
+        assert!(matches!(
+            update(
+                DisplayNameConfig::default(),
+                tanabe.clone(),
+                "admin",
+                "Admin"
+            ),
+            Err(UpdateDisplayNameError::Forbidden(_)),
+        ));
+
+        let admin = actor("admin", Role::Administrator);
+        assert!(update(DisplayNameConfig::default(), admin, "admin", "Admin").is_ok());
+        let config = DisplayNameConfig::builder()
+            .reject_staff_confusables(false)
+            .build();
+        assert!(update(config, tanabe, "tanabe", "ADMIN").is_ok());
     }
 }
@@ -1,14 +1,22 @@
 use derive_more::{Display, Error, From};
 
 use crate::{
-    model::{Email, User, UserData, UserId},
-    repository::UserDatabase,
-    use_case::find_one::{find_one_by_email, find_one_by_id},
+    model::{Actor, Email, EmailDomainError, EmailPolicy, User, UserAction, UserId, UserPatch},
+    repository::{Clock, UserConflict, UserDatabase, UserDatabaseError},
+    use_case::actor::is_actor_active,
 };
 
 /// Error type of update user email use case.
 #[derive(Debug, Display, From, Error)]
 pub enum UpdateEmailError<Error> {
+    /// Actor is not allowed to update user email.
+    #[display(fmt = "{} is not allowed to update user email", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
@@ -17,52 +25,128 @@ pub enum UpdateEmailError<Error> {
     #[display(fmt = r#"user email "{}" is already taken"#, _0)]
     #[from(ignore)]
     AlreadyTaken(#[error(not(source))] Email),
+    /// Domain of provided email does not conform to the email policy.
+    #[display(fmt = r#"user email "{}" is rejected: {}"#, _0, _1)]
+    #[from(ignore)]
+    NotAllowed(
+        #[error(not(source))] Email,
+        #[error(not(source))] EmailDomainError,
+    ),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(Error),
 }
 
 /// Update email interactor.
-pub struct UpdateEmail<Database>
+pub struct UpdateEmail<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    clock: CurrentTime,
+    policy: EmailPolicy,
 }
 
-impl<Database> UpdateEmail<Database>
+impl<Database, CurrentTime> UpdateEmail<Database, CurrentTime>
 where
     Database: UserDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new update email interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(database: Database, clock: CurrentTime, policy: EmailPolicy) -> Self {
+        Self {
+            database,
+            clock,
+            policy,
+        }
     }
 
     /// Updates email of the user by its identifier with provided email.
+    ///
+    /// Provided email must conform to the email policy of the interactor.
     pub async fn update_email(
         &self,
+        actor: Actor,
         current_id: UserId,
         email: Option<Email>,
     ) -> Result<User, UpdateEmailError<Database::Error>> {
-        let Self { database } = self;
+        let Self {
+            database,
+            clock,
+            policy,
+        } = self;
 
-        if email.is_some() {
-            let user_by_email = find_one_by_email(database, &email).await?;
-            if let Some(user_by_email) = user_by_email {
-                let User { data, .. } = user_by_email;
-                let UserData { email, .. } = data;
-                let email = email.expect("user was found by email which is `Some`");
-                return Err(UpdateEmailError::AlreadyTaken(email));
+        if !actor.is_allowed(UserAction::UpdateEmail, &current_id) {
+            return Err(UpdateEmailError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UpdateEmailError::Inactive(actor));
+        }
+        if let Some(email) = &email {
+            if let Err(error) = policy.check(email) {
+                return Err(UpdateEmailError::NotAllowed(email.clone(), error));
             }
         }
 
-        let User { id, data } = {
-            let user_by_id = find_one_by_id(database, &current_id).await?;
-            user_by_id.ok_or_else(|| UpdateEmailError::NoUser(current_id))?
+        let patch = UserPatch::builder().email(email.clone()).build();
+        let user = database
+            .patch(current_id.clone(), patch)
+            .await
+            .map_err(|error| match (error.conflict(), email) {
+                (Some(UserConflict::Email), Some(email)) => UpdateEmailError::AlreadyTaken(email),
+                _ => UpdateEmailError::Database(error),
+            })?;
+        user.ok_or(UpdateEmailError::NoUser(current_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{UpdateEmail, UpdateEmailError};
+    use crate::{
+        model::{Email, EmailPolicy, Role, UserId},
+        use_case::fixture::{actor, database, FixedClock},
+    };
+
+    #[test]
+    fn update_email() {
+        let database = database([
+            ("tanabe", Role::User),
+            ("kotlinist", Role::User),
+            ("administrator", Role::Administrator),
+        ]);
+        let interactor = UpdateEmail::new(
+            database.clone(),
+            FixedClock::default(),
+            EmailPolicy::default(),
+        );
+        let update = |actor, id, email: Option<&str>| {
+            let email = email.map(|email| Email::new(email).unwrap());
+            block_on(interactor.update_email(actor, UserId::new(id), email))
         };
-        let data = UserData { email, ..data };
-        let user = database.update(id, data).await?;
-        Ok(user)
+        let tanabe = actor("tanabe", Role::User);
+
+        let user = update(tanabe.clone(), "tanabe", Some("tanabe@example.com")).unwrap();
+        assert_eq!(user.data.email.unwrap().as_str(), "tanabe@example.com");
+        assert_eq!(user.data.name.as_str(), "tanabe");
+        assert!(matches!(
+            update(
+                actor("kotlinist", Role::User),
+                "kotlinist",
+                Some("TANABE@example.com"),
+            ),
+            Err(UpdateEmailError::AlreadyTaken(_)),
+        ));
+
+        let user = update(tanabe, "tanabe", None).unwrap();
+        assert_eq!(user.data.email, None);
+        assert_eq!(database.users()[0].data.email, None);
+        assert!(matches!(
+            update(actor("administrator", Role::Administrator), "unknown", None),
+            Err(UpdateEmailError::NoUser(_)),
+        ));
     }
 }
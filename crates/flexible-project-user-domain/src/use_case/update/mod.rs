@@ -1,13 +1,22 @@
 pub use self::{
     avatar::{UpdateAvatar, UpdateAvatarError},
-    display_name::{UpdateDisplayName, UpdateDisplayNameError},
+    avatar_upload::{
+        AvatarResolutions, AvatarUploadConfig, EmptyAvatarResolutions, UploadAvatar,
+        UploadAvatarError,
+    },
+    display_name::{DisplayNameConfig, UpdateDisplayName, UpdateDisplayNameError},
     email::{UpdateEmail, UpdateEmailError},
     name::{UpdateName, UpdateNameError},
+    role::{ChangeRole, ChangeRoleError},
     user::{UpdateUser, UpdateUserError, UpdateUserInput},
 };
 
+pub(crate) use self::{avatar_upload::avatar_key_prefix, display_name::is_confusable_with_staff};
+
 mod avatar;
+mod avatar_upload;
 mod display_name;
 mod email;
 mod name;
+mod role;
 mod user;
@@ -1,65 +1,308 @@
 use derive_more::{Display, Error, From};
 
 use crate::{
-    model::{Name, User, UserData, UserId},
-    repository::UserDatabase,
-    use_case::find_one::{find_one_by_id, find_one_by_name},
+    model::{Actor, Name, NameChange, User, UserAction, UserData, UserId, UserPatch},
+    repository::{Clock, NameHistoryDatabase, UserConflict, UserDatabase, UserDatabaseError},
+    use_case::{
+        actor::is_actor_active,
+        find_one::find_one_by_id,
+        name::{is_reserved_for_other, NameHistoryConfig},
+    },
 };
 
 /// Error type of update user name use case.
 #[derive(Debug, Display, From, Error)]
-pub enum UpdateNameError<Error> {
+pub enum UpdateNameError<Error, HistoryError> {
+    /// Actor is not allowed to update user name.
+    #[display(fmt = "{} is not allowed to update user name", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
     NoUser(#[error(not(source))] UserId),
-    /// User with provided name already exists.
+    /// User with provided name already exists or the name was released recently.
     #[display(fmt = r#"user name "{}" is already taken"#, _0)]
     #[from(ignore)]
     AlreadyTaken(#[error(not(source))] Name),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(Error),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    #[from(ignore)]
+    NameHistory(HistoryError),
 }
 
 /// Update name interactor.
-pub struct UpdateName<Database>
+pub struct UpdateName<Database, History, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    history: History,
+    clock: CurrentTime,
+    config: NameHistoryConfig,
 }
 
-impl<Database> UpdateName<Database>
+impl<Database, History, CurrentTime> UpdateName<Database, History, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new update name interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(
+        database: Database,
+        history: History,
+        clock: CurrentTime,
+        config: NameHistoryConfig,
+    ) -> Self {
+        Self {
+            database,
+            history,
+            clock,
+            config,
+        }
     }
 
     /// Updates name of the user by its identifier with provided name.
+    ///
+    /// Former name of the user is stored in the name history
+    /// and cannot be claimed by other users until reuse cooldown ends.
+    ///
+    /// The name change is stored before the user is renamed, so the former name
+    /// is never released without being reserved, and is deleted if the user was not renamed.
+    /// Reservation of provided name is checked again after the user is renamed:
+    /// if the name was released by other user concurrently, the rename is reverted.
     pub async fn update_name(
         &self,
+        actor: Actor,
         current_id: UserId,
         name: Name,
-    ) -> Result<User, UpdateNameError<Database::Error>> {
-        let Self { database } = self;
+    ) -> Result<User, UpdateNameError<Database::Error, History::Error>> {
+        let Self {
+            database,
+            history,
+            clock,
+            config,
+        } = self;
 
-        let user_by_name = find_one_by_name(database, &name).await?;
-        if let Some(user_by_name) = user_by_name {
-            let User { data, .. } = user_by_name;
-            let UserData { name, .. } = data;
-            return Err(UpdateNameError::AlreadyTaken(name));
+        if !actor.is_allowed(UserAction::UpdateName, &current_id) {
+            return Err(UpdateNameError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UpdateNameError::Inactive(actor));
         }
 
         let User { id, data } = {
             let user_by_id = find_one_by_id(database, &current_id).await?;
             user_by_id.ok_or_else(|| UpdateNameError::NoUser(current_id))?
         };
-        let data = UserData { name, ..data };
-        let user = database.update(id, data).await?;
+        if data.name == name {
+            let user = User { id, data };
+            return Ok(user);
+        }
+        let is_reserved = is_reserved_for_other(history, clock, config, &name, Some(&id))
+            .await
+            .map_err(UpdateNameError::NameHistory)?;
+        if is_reserved {
+            return Err(UpdateNameError::AlreadyTaken(name));
+        }
+
+        let UserData {
+            name: former_name, ..
+        } = &data;
+        let change = NameChange {
+            user_id: id.clone(),
+            name: former_name.clone(),
+            changed_at: clock.now(),
+        };
+        history
+            .create(change.clone())
+            .await
+            .map_err(UpdateNameError::NameHistory)?;
+
+        let patch = UserPatch::builder().name(name.clone()).build();
+        let user = match database.patch(id.clone(), patch).await {
+            Ok(Some(user)) => user,
+            result => {
+                history
+                    .delete(change)
+                    .await
+                    .map_err(UpdateNameError::NameHistory)?;
+                let error = match result {
+                    Err(error) if error.conflict() == Some(UserConflict::Name) => {
+                        UpdateNameError::AlreadyTaken(name)
+                    }
+                    Err(error) => UpdateNameError::Database(error),
+                    Ok(_) => UpdateNameError::NoUser(id),
+                };
+                return Err(error);
+            }
+        };
+
+        let is_reserved = is_reserved_for_other(history, clock, config, &name, Some(&id)).await;
+        if !matches!(is_reserved, Ok(false)) {
+            let patch = UserPatch::builder().name(former_name.clone()).build();
+            database.patch(id, patch).await?;
+            history
+                .delete(change)
+                .await
+                .map_err(UpdateNameError::NameHistory)?;
+            let error = match is_reserved {
+                Ok(_) => UpdateNameError::AlreadyTaken(name),
+                Err(error) => UpdateNameError::NameHistory(error),
+            };
+            return Err(error);
+        }
         Ok(user)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+    use futures::{executor::block_on, join};
+
+    use super::{UpdateName, UpdateNameError};
+    use crate::{
+        model::{Actor, Name, NameChange, Role, UserId},
+        repository::{memory::MemoryNameHistoryDatabase, NameHistoryDatabase, UserDatabase},
+        use_case::{
+            find_one::find_one_by_id,
+            fixture::{actor, database, yield_now, FixedClock},
+            NameHistoryConfig,
+        },
+    };
+
+    /// Name history database which yields to other tasks before each name change is stored.
+    struct YieldingHistory(MemoryNameHistoryDatabase);
+
+    #[async_trait(?Send)]
+    impl NameHistoryDatabase for YieldingHistory {
+        type Error = <MemoryNameHistoryDatabase as NameHistoryDatabase>::Error;
+
+        async fn create(&self, change: NameChange) -> Result<NameChange, Self::Error> {
+            yield_now().await;
+            self.0.create(change).await
+        }
+
+        async fn find_latest_by_name(
+            &self,
+            name: &Name,
+        ) -> Result<Option<NameChange>, Self::Error> {
+            self.0.find_latest_by_name(name).await
+        }
+
+        async fn read_by_user(&self, user_id: UserId) -> Result<Vec<NameChange>, Self::Error> {
+            self.0.read_by_user(user_id).await
+        }
+
+        async fn delete(&self, change: NameChange) -> Result<bool, Self::Error> {
+            self.0.delete(change).await
+        }
+
+        async fn delete_by_user(&self, user_id: UserId) -> Result<u64, Self::Error> {
+            self.0.delete_by_user(user_id).await
+        }
+    }
+
+    #[test]
+    fn update() {
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let history = MemoryNameHistoryDatabase::new();
+        let interactor = UpdateName::new(
+            &database,
+            &history,
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+        );
+        let update = |actor, id, name| {
+            let name = Name::new(name).unwrap();
+            block_on(interactor.update_name(actor, UserId::new(id), name))
+        };
+        let tanabe = actor("tanabe", Role::User);
+
+        let user = update(tanabe.clone(), "tanabe", "flexible").unwrap();
+        assert_eq!(user.data.name.as_str(), "flexible");
+        let changes = block_on(history.read_by_user(UserId::new("tanabe"))).unwrap();
+        assert_eq!(changes[0].name.as_str(), "tanabe");
+
+        let kotlinist = actor("kotlinist", Role::User);
+        assert!(matches!(
+            update(kotlinist.clone(), "kotlinist", "tanabe"),
+            Err(UpdateNameError::AlreadyTaken(_)),
+        ));
+        assert!(matches!(
+            update(kotlinist.clone(), "kotlinist", "flexible"),
+            Err(UpdateNameError::AlreadyTaken(_)),
+        ));
+        assert!(matches!(
+            update(kotlinist, "tanabe", "renamed"),
+            Err(UpdateNameError::Forbidden(_)),
+        ));
+        assert!(update(tanabe, "tanabe", "tanabe").is_ok());
+    }
+
+    #[test]
+    fn concurrent_release() {
+        let database = database([("tanabe", Role::User), ("kotlinist", Role::User)]);
+        let history = YieldingHistory(MemoryNameHistoryDatabase::new());
+        let interactor = UpdateName::new(
+            &database,
+            &history,
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+        );
+
+        let (released, claimed) = block_on(async {
+            join!(
+                interactor.update_name(
+                    actor("tanabe", Role::User),
+                    UserId::new("tanabe"),
+                    Name::new("flexible").unwrap(),
+                ),
+                interactor.update_name(
+                    actor("kotlinist", Role::User),
+                    UserId::new("kotlinist"),
+                    Name::new("tanabe").unwrap(),
+                ),
+            )
+        });
+        assert!(released.is_ok());
+        assert!(matches!(claimed, Err(UpdateNameError::AlreadyTaken(_))));
+        let user = block_on(find_one_by_id(&database, UserId::new("kotlinist"))).unwrap();
+        assert_eq!(user.unwrap().data.name.as_str(), "kotlinist");
+    }
+
+    #[test]
+    fn concurrent_deletion() {
+        let database = database([("tanabe", Role::User)]);
+        let history = YieldingHistory(MemoryNameHistoryDatabase::new());
+        let interactor = UpdateName::new(
+            &database,
+            &history,
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+        );
+
+        let id = UserId::new("tanabe");
+        let (renamed, deleted) = block_on(async {
+            join!(
+                interactor.update_name(Actor::System, id.clone(), Name::new("flexible").unwrap(),),
+                database.delete(id.clone()),
+            )
+        });
+        assert!(deleted.is_ok());
+        assert!(matches!(renamed, Err(UpdateNameError::NoUser(_))));
+        let changes = block_on(history.read_by_user(id)).unwrap();
+        assert!(changes.is_empty());
+    }
+}
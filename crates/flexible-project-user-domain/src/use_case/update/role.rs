@@ -0,0 +1,227 @@
+use std::{borrow::Cow, pin::pin};
+
+use derive_more::{Display, Error, From};
+use futures::TryStreamExt;
+
+use crate::{
+    model::{
+        Actor, Role, RoleFilters, User, UserAction, UserDataFilters, UserFilters, UserId,
+        UserIdFilters, UserPatch,
+    },
+    repository::{Clock, UserDatabase},
+    use_case::{actor::is_actor_active, find_one::find_one_by_id},
+};
+
+/// Error type of change role use case.
+#[derive(Debug, Display, From, Error)]
+pub enum ChangeRoleError<Error> {
+    /// No user was found by provided identifier.
+    #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
+    #[from(ignore)]
+    NoUser(#[error(not(source))] UserId),
+    /// Actor is not allowed to change roles of the users.
+    #[display(fmt = "{} is not allowed to change user role", _0)]
+    #[from(ignore)]
+    Forbidden(#[error(not(source))] Actor),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
+    /// The last administrator of the system cannot be demoted.
+    #[display(fmt = r#"user "{}" is the last administrator"#, _0)]
+    #[from(ignore)]
+    LastAdministrator(#[error(not(source))] UserId),
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(Error),
+}
+
+/// Change role interactor.
+pub struct ChangeRole<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    database: Database,
+    clock: CurrentTime,
+}
+
+impl<Database, CurrentTime> ChangeRole<Database, CurrentTime>
+where
+    Database: UserDatabase,
+    CurrentTime: Clock,
+{
+    /// Creates new change role interactor.
+    pub fn new(database: Database, clock: CurrentTime) -> Self {
+        Self { database, clock }
+    }
+
+    /// Changes role of the user by its identifier on behalf of the actor.
+    ///
+    /// Only administrators (and the system) are allowed to promote or demote users.
+    /// The last administrator of the system cannot be demoted.
+    ///
+    /// Administrator is demoted first and then the remaining administrators are counted:
+    /// if none of them are left (e.g. the other one was demoted concurrently),
+    /// the demotion is reverted, so concurrent demotions cannot leave the system
+    /// without administrators.
+    pub async fn change_role(
+        &self,
+        actor: Actor,
+        current_id: UserId,
+        role: Role,
+    ) -> Result<User, ChangeRoleError<Database::Error>> {
+        let Self { database, clock } = self;
+
+        if !actor.is_allowed(UserAction::ChangeRole, &current_id) {
+            return Err(ChangeRoleError::Forbidden(actor));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(ChangeRoleError::Inactive(actor));
+        }
+
+        let User { id, data } = {
+            let user_by_id = find_one_by_id(database, &current_id).await?;
+            user_by_id.ok_or_else(|| ChangeRoleError::NoUser(current_id))?
+        };
+        if data.role == role {
+            let user = User { id, data };
+            return Ok(user);
+        }
+        if data.role == Role::Administrator && !has_other_administrator(database, &id).await? {
+            return Err(ChangeRoleError::LastAdministrator(id));
+        }
+
+        let patch = UserPatch::builder().role(role).build();
+        let user = database.patch(id.clone(), patch).await?;
+        let user = user.ok_or_else(|| ChangeRoleError::NoUser(id.clone()))?;
+        if data.role == Role::Administrator && count_administrators(database).await? == 0 {
+            let patch = UserPatch::builder().role(Role::Administrator).build();
+            database.patch(id.clone(), patch).await?;
+            return Err(ChangeRoleError::LastAdministrator(id));
+        }
+        Ok(user)
+    }
+}
+
+async fn count_administrators<Database>(database: Database) -> Result<u64, Database::Error>
+where
+    Database: UserDatabase,
+{
+    let filter = {
+        let role = RoleFilters::builder()
+            .eq(Cow::Owned(Role::Administrator))
+            .build();
+        let data = UserDataFilters::builder().role(role).build();
+        UserFilters::builder().data(data).build()
+    };
+    database.count(filter).await
+}
+
+async fn has_other_administrator<Database>(
+    database: Database,
+    id: &UserId,
+) -> Result<bool, Database::Error>
+where
+    Database: UserDatabase,
+{
+    let filter = {
+        let id = UserIdFilters::builder().ne(Cow::Borrowed(id)).build();
+        let role = RoleFilters::builder()
+            .eq(Cow::Owned(Role::Administrator))
+            .build();
+        let data = UserDataFilters::builder().role(role).build();
+        UserFilters::builder().id(id).data(data).build()
+    };
+    let administrators = database.read(filter).await?;
+    let mut administrators = pin!(administrators);
+    let other = administrators.try_next().await?;
+    Ok(other.is_some())
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{executor::block_on, join};
+
+    use super::{ChangeRole, ChangeRoleError};
+    use crate::{
+        model::{Actor, Role, UserId},
+        use_case::fixture::{actor, database, FixedClock, YieldingDatabase},
+    };
+
+    #[test]
+    fn permissions() {
+        let database = database([
+            ("admin", Role::Administrator),
+            ("moderator", Role::Moderator),
+            ("tanabe", Role::User),
+        ]);
+        let interactor = ChangeRole::new(&database, FixedClock::default());
+        let change =
+            |actor, id, role| block_on(interactor.change_role(actor, UserId::new(id), role));
+
+        for role in [Role::User, Role::Moderator] {
+            assert!(matches!(
+                change(actor("tanabe", role), "tanabe", Role::Administrator),
+                Err(ChangeRoleError::Forbidden(_)),
+            ));
+            assert!(matches!(
+                change(actor("moderator", role), "tanabe", Role::Moderator),
+                Err(ChangeRoleError::Forbidden(_)),
+            ));
+        }
+        let admin = actor("admin", Role::Administrator);
+        let user = change(admin.clone(), "tanabe", Role::Moderator).unwrap();
+        assert_eq!(user.data.role, Role::Moderator);
+        let user = change(Actor::System, "moderator", Role::User).unwrap();
+        assert_eq!(user.data.role, Role::User);
+        assert!(matches!(
+            change(admin, "unknown", Role::User),
+            Err(ChangeRoleError::NoUser(_)),
+        ));
+    }
+
+    #[test]
+    fn last_administrator() {
+        let database = database([
+            ("admin", Role::Administrator),
+            ("flexible", Role::Administrator),
+        ]);
+        let interactor = ChangeRole::new(&database, FixedClock::default());
+        let change =
+            |actor, id, role| block_on(interactor.change_role(actor, UserId::new(id), role));
+
+        let admin = actor("admin", Role::Administrator);
+        assert!(change(admin, "flexible", Role::User).is_ok());
+        assert!(matches!(
+            change(Actor::System, "admin", Role::Moderator),
+            Err(ChangeRoleError::LastAdministrator(_)),
+        ));
+        let user = change(Actor::System, "admin", Role::Administrator).unwrap();
+        assert_eq!(user.data.role, Role::Administrator);
+    }
+
+    #[test]
+    fn concurrent_demotions() {
+        let inner = database([
+            ("admin", Role::Administrator),
+            ("flexible", Role::Administrator),
+        ]);
+        let database = YieldingDatabase(inner.clone());
+        let interactor = ChangeRole::new(&database, FixedClock::default());
+
+        let (first, second) = block_on(async {
+            join!(
+                interactor.change_role(Actor::System, UserId::new("admin"), Role::User),
+                interactor.change_role(Actor::System, UserId::new("flexible"), Role::User),
+            )
+        });
+        assert!(first.is_ok());
+        assert!(matches!(second, Err(ChangeRoleError::LastAdministrator(_)),));
+        let users = inner.users();
+        let administrators = users
+            .iter()
+            .filter(|user| user.data.role == Role::Administrator);
+        assert_eq!(administrators.count(), 1);
+    }
+}
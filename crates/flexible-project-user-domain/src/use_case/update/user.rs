@@ -2,29 +2,75 @@ use derive_more::{Display, Error, From};
 use typed_builder::TypedBuilder;
 
 use crate::{
-    model::{Avatar, DisplayName, Email, Name, User, UserData, UserId},
-    repository::UserDatabase,
-    use_case::find_one::{find_one_by_email, find_one_by_id, find_one_by_name},
+    model::{
+        Actor, Avatar, AvatarError, AvatarPolicy, Bio, DisplayName, Email, EmailDomainError,
+        EmailPolicy, Locale, Name, NameChange, Pronouns, TimeZone, User, UserAction, UserId,
+        UserPatch,
+    },
+    repository::{Clock, NameHistoryDatabase, UserConflict, UserDatabase, UserDatabaseError},
+    use_case::{
+        actor::is_actor_active,
+        find_one::find_one_by_id,
+        name::{is_reserved_for_other, NameHistoryConfig},
+    },
 };
 
+use super::display_name::{is_confusable_with_staff, DisplayNameConfig};
+
 /// Error type of update user use case.
 #[derive(Debug, Display, From, Error)]
-pub enum UpdateUserError<Error> {
+pub enum UpdateUserError<Error, HistoryError> {
+    /// Actor is not allowed to perform an action on the user.
+    #[display(fmt = "{} is not allowed to {}", _0, _1)]
+    #[from(ignore)]
+    Forbidden(
+        #[error(not(source))] Actor,
+        #[error(not(source))] UserAction,
+    ),
+    /// Actor is suspended or banned.
+    #[display(fmt = "{} is suspended or banned", _0)]
+    #[from(ignore)]
+    Inactive(#[error(not(source))] Actor),
     /// No user was found by provided identifier.
     #[display(fmt = r#"no user exists by identifier "{}""#, _0)]
     #[from(ignore)]
     NoUser(#[error(not(source))] UserId),
-    /// User with provided name already exists.
+    /// User with provided name already exists or the name was released recently.
     #[display(fmt = r#"user name "{}" is already taken"#, _0)]
     #[from(ignore)]
     NameAlreadyTaken(#[error(not(source))] Name),
+    /// Display name could be confused with display name of administrator or moderator.
+    #[display(
+        fmt = r#"user display name "{}" could be confused with display name of staff member"#,
+        _0
+    )]
+    #[from(ignore)]
+    ConfusableDisplayName(#[error(not(source))] DisplayName),
     /// User with provided email already exists.
     #[display(fmt = r#"user email "{}" is already taken"#, _0)]
     #[from(ignore)]
     EmailAlreadyTaken(#[error(not(source))] Email),
+    /// Domain of provided email does not conform to the email policy.
+    #[display(fmt = r#"user email "{}" is rejected: {}"#, _0, _1)]
+    #[from(ignore)]
+    EmailNotAllowed(
+        #[error(not(source))] Email,
+        #[error(not(source))] EmailDomainError,
+    ),
+    /// Provided avatar does not conform to the avatar policy.
+    #[display(fmt = r#"user avatar "{}" is rejected: {}"#, _0, _1)]
+    #[from(ignore)]
+    AvatarNotAllowed(
+        #[error(not(source))] Avatar,
+        #[error(not(source))] AvatarError,
+    ),
     /// Database error.
     #[display(fmt = "database error: {}", _0)]
     Database(Error),
+    /// Name history database error.
+    #[display(fmt = "name history database error: {}", _0)]
+    #[from(ignore)]
+    NameHistory(HistoryError),
 }
 
 /// Input of the update user interactor.
@@ -39,72 +85,272 @@ pub struct UpdateUserInput {
     pub email: Option<Option<Email>>,
     /// Avatar of the user to update, if present.
     pub avatar: Option<Option<Avatar>>,
+    /// Bio of the user to update, if present.
+    pub bio: Option<Option<Bio>>,
+    /// Preferred locale of the user to update, if present.
+    pub locale: Option<Option<Locale>>,
+    /// Time zone of the user to update, if present.
+    pub time_zone: Option<Option<TimeZone>>,
+    /// Pronouns of the user to update, if present.
+    pub pronouns: Option<Option<Pronouns>>,
 }
 
 /// Update user interactor.
-pub struct UpdateUser<Database>
+pub struct UpdateUser<Database, History, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
 {
     database: Database,
+    history: History,
+    clock: CurrentTime,
+    config: NameHistoryConfig,
+    display_name_config: DisplayNameConfig,
+    email_policy: EmailPolicy,
+    avatar_policy: AvatarPolicy,
 }
 
-impl<Database> UpdateUser<Database>
+impl<Database, History, CurrentTime> UpdateUser<Database, History, CurrentTime>
 where
     Database: UserDatabase,
+    History: NameHistoryDatabase,
+    CurrentTime: Clock,
 {
     /// Creates new update user interactor.
-    pub fn new(database: Database) -> Self {
-        Self { database }
+    pub fn new(
+        database: Database,
+        history: History,
+        clock: CurrentTime,
+        config: NameHistoryConfig,
+        display_name_config: DisplayNameConfig,
+        email_policy: EmailPolicy,
+        avatar_policy: AvatarPolicy,
+    ) -> Self {
+        Self {
+            database,
+            history,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
+            avatar_policy,
+        }
     }
 
-    /// Updates user by its identifier with provided name, display name and email.
+    /// Updates user by its identifier with provided name, display name, email, avatar and profile.
     pub async fn update_user(
         &self,
+        actor: Actor,
         current_id: UserId,
         update: UpdateUserInput,
-    ) -> Result<User, UpdateUserError<Database::Error>> {
-        let Self { database } = self;
+    ) -> Result<User, UpdateUserError<Database::Error, History::Error>> {
+        let Self {
+            database,
+            history,
+            clock,
+            config,
+            display_name_config,
+            email_policy,
+            avatar_policy,
+        } = self;
         let UpdateUserInput {
             name,
             display_name,
             email,
             avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = update;
+        let is_profile_updated =
+            bio.is_some() || locale.is_some() || time_zone.is_some() || pronouns.is_some();
 
-        let User { id, mut data } = {
+        let actions = [
+            name.as_ref().map(|_| UserAction::UpdateName),
+            display_name.as_ref().map(|_| UserAction::UpdateDisplayName),
+            email.as_ref().map(|_| UserAction::UpdateEmail),
+            avatar.as_ref().map(|_| UserAction::UpdateAvatar),
+            is_profile_updated.then_some(UserAction::UpdateProfile),
+        ];
+        let forbidden = actions
+            .into_iter()
+            .flatten()
+            .find(|&action| !actor.is_allowed(action, &current_id));
+        if let Some(action) = forbidden {
+            return Err(UpdateUserError::Forbidden(actor, action));
+        }
+        if !is_actor_active(database, clock, &actor).await? {
+            return Err(UpdateUserError::Inactive(actor));
+        }
+
+        let User { id, data } = {
             let user_by_id = find_one_by_id(database, &current_id).await?;
             user_by_id.ok_or_else(|| UpdateUserError::NoUser(current_id))?
         };
-        if let Some(name) = name {
-            let user_by_name = find_one_by_name(database, &name).await?;
-            if let Some(user_by_name) = user_by_name {
-                let User { data, .. } = user_by_name;
-                let UserData { name, .. } = data;
-                return Err(UpdateUserError::NameAlreadyTaken(name));
+        let name = name.filter(|name| name != &data.name);
+        let mut change = None;
+        if let Some(name) = &name {
+            let is_reserved = is_reserved_for_other(history, clock, config, name, Some(&id))
+                .await
+                .map_err(UpdateUserError::NameHistory)?;
+            if is_reserved {
+                return Err(UpdateUserError::NameAlreadyTaken(name.clone()));
             }
-            data.name = name;
+            change = Some(NameChange {
+                user_id: id.clone(),
+                name: data.name.clone(),
+                changed_at: clock.now(),
+            });
         }
-        if let Some(display_name) = display_name {
-            data.display_name = display_name;
+        if let Some(display_name) = &display_name {
+            let is_confusable =
+                is_confusable_with_staff(database, display_name_config, display_name, Some(&id))
+                    .await?;
+            if is_confusable {
+                return Err(UpdateUserError::ConfusableDisplayName(display_name.clone()));
+            }
         }
-        if let Some(email) = email {
-            if email.is_some() {
-                let user_by_email = find_one_by_email(database, &email).await?;
-                if let Some(user_by_email) = user_by_email {
-                    let User { data, .. } = user_by_email;
-                    let UserData { email, .. } = data;
-                    let email = email.expect("user was found by email which is `Some`");
-                    return Err(UpdateUserError::EmailAlreadyTaken(email));
-                }
+        if let Some(Some(email)) = &email {
+            if let Err(error) = email_policy.check(email) {
+                return Err(UpdateUserError::EmailNotAllowed(email.clone(), error));
             }
-            data.email = email;
         }
-        if let Some(avatar) = avatar {
-            data.avatar = avatar;
+        if let Some(Some(avatar)) = &avatar {
+            if let Err(error) = avatar_policy.check(avatar) {
+                return Err(UpdateUserError::AvatarNotAllowed(avatar.clone(), error));
+            }
         }
 
-        let user = database.update(id, data).await?;
+        let patch = UserPatch {
+            name: name.clone(),
+            display_name,
+            email: email.clone(),
+            avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
+            ..Default::default()
+        };
+        let user = database
+            .patch(id.clone(), patch)
+            .await
+            .map_err(|error| match (error.conflict(), name, email.flatten()) {
+                (Some(UserConflict::Name), Some(name), _) => {
+                    UpdateUserError::NameAlreadyTaken(name)
+                }
+                (Some(UserConflict::Email), _, Some(email)) => {
+                    UpdateUserError::EmailAlreadyTaken(email)
+                }
+                _ => UpdateUserError::Database(error),
+            })?
+            .ok_or(UpdateUserError::NoUser(id))?;
+        if let Some(change) = change {
+            history
+                .create(change)
+                .await
+                .map_err(UpdateUserError::NameHistory)?;
+        }
         Ok(user)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use futures::executor::block_on;
+
+    use super::{UpdateUser, UpdateUserError, UpdateUserInput};
+    use crate::{
+        model::{Avatar, AvatarPolicy, DisplayName, Email, EmailPolicy, Name, Role, UserId},
+        repository::{memory::MemoryNameHistoryDatabase, NameHistoryDatabase},
+        use_case::{
+            fixture::{actor, database, FixedClock},
+            DisplayNameConfig, NameHistoryConfig,
+        },
+    };
+
+    #[test]
+    fn update() {
+        let database = database([
+            ("tanabe", Role::User),
+            ("kotlinist", Role::User),
+            ("moderator", Role::Moderator),
+        ]);
+        let history = MemoryNameHistoryDatabase::new();
+        let interactor = UpdateUser::new(
+            database,
+            history.clone(),
+            FixedClock::default(),
+            NameHistoryConfig::default(),
+            DisplayNameConfig::default(),
+            EmailPolicy::default(),
+            AvatarPolicy::default(),
+        );
+        let update =
+            |actor, id, update| block_on(interactor.update_user(actor, UserId::new(id), update));
+        let tanabe = actor("tanabe", Role::User);
+
+        let input = UpdateUserInput::builder()
+            .name(Name::new("flexible").unwrap())
+            .email(Some(Email::new("tanabe@example.com").unwrap()))
+            .build();
+        let user = update(tanabe.clone(), "tanabe", input).unwrap();
+        assert_eq!(user.data.name.as_str(), "flexible");
+        let changes = block_on(history.read_by_user(UserId::new("tanabe"))).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name.as_str(), "tanabe");
+
+        let input = UpdateUserInput::builder()
+            .name(Name::new("tanabe").unwrap())
+            .build();
+        assert!(matches!(
+            update(actor("kotlinist", Role::User), "kotlinist", input),
+            Err(UpdateUserError::NameAlreadyTaken(_)),
+        ));
+        let input = UpdateUserInput::builder()
+            .email(Some(Email::new("Tanabe@Example.com").unwrap()))
+            .build();
+        assert!(matches!(
+            update(actor("kotlinist", Role::User), "kotlinist", input),
+            Err(UpdateUserError::EmailAlreadyTaken(_)),
+        ));
+
+        let input = UpdateUserInput::builder()
+            .display_name(DisplayName::new("Flexible").unwrap())
+            .build();
+        assert!(update(actor("moderator", Role::Moderator), "tanabe", input).is_ok());
+        let input = UpdateUserInput::builder()
+            .name(Name::new("renamed").unwrap())
+            .build();
+        assert!(matches!(
+            update(actor("moderator", Role::Moderator), "tanabe", input),
+            Err(UpdateUserError::Forbidden(_, _)),
+        ));
+
+        let input = UpdateUserInput::builder()
+            .display_name(DisplayName::new("Rnoderator").unwrap())
+            .build();
+        assert!(matches!(
+            update(tanabe, "tanabe", input),
+            Err(UpdateUserError::ConfusableDisplayName(_)),
+        ));
+        let input = UpdateUserInput::builder()
+            .display_name(DisplayName::new("Moderator").unwrap())
+            .build();
+        assert!(update(actor("moderator", Role::Moderator), "moderator", input).is_ok());
+
+        let input = UpdateUserInput::builder()
+            .avatar(Some(Avatar::new("https://example.com/avatar.png").unwrap()))
+            .build();
+        assert!(update(actor("kotlinist", Role::User), "kotlinist", input).is_ok());
+        let input = UpdateUserInput::builder()
+            .avatar(Some(Avatar::new("http://127.0.0.1/avatar.png").unwrap()))
+            .build();
+        assert!(matches!(
+            update(actor("kotlinist", Role::User), "kotlinist", input),
+            Err(UpdateUserError::AvatarNotAllowed(_, _)),
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+//! Authentication of the actor which performs incoming request.
+
+use fp_core::id::ErasedId as CoreErasedId;
+use fp_user_domain::model::{Actor, User, UserData};
+use lapin::message::Delivery;
+
+use crate::{interactor::Interactors, response::ResponseError};
+
+/// Name of the message header with identifier of the user
+/// which was authenticated by the gateway.
+pub const USER_ID_HEADER: &str = "user-id";
+
+/// Configuration of the actor authentication.
+///
+/// Publishers are identified by `user_id` property of the message,
+/// which is validated by the AMQP server against the user of the connection,
+/// so it cannot be forged by the clients.
+#[derive(Debug, Clone, Default)]
+pub struct AuthenticationConfig {
+    /// AMQP user of the gateway which verifies tokens of the clients
+    /// and provides identifier of the authenticated user in the message header.
+    pub gateway_user: Option<String>,
+    /// AMQP user of the internal services which act as the system itself.
+    pub system_user: Option<String>,
+}
+
+/// Authenticates the actor of the request by the properties of the message.
+///
+/// Returns `None` if the request is anonymous, including the requests
+/// of the publishers which are neither the gateway nor the system.
+pub async fn authenticate(
+    delivery: &Delivery,
+    config: &AuthenticationConfig,
+    interactors: &Interactors,
+) -> Result<Option<Actor>, ResponseError> {
+    let AuthenticationConfig {
+        gateway_user,
+        system_user,
+    } = config;
+    let Some(publisher) = delivery.properties.user_id() else {
+        return Ok(None);
+    };
+    let publisher = Some(publisher.as_str());
+    if publisher == system_user.as_deref() {
+        return Ok(Some(Actor::System));
+    }
+    if publisher != gateway_user.as_deref() {
+        return Ok(None);
+    }
+
+    let user_id = delivery
+        .properties
+        .headers()
+        .as_ref()
+        .and_then(|headers| headers.inner().get(USER_ID_HEADER))
+        .and_then(|user_id| user_id.as_long_string());
+    let Some(user_id) = user_id else {
+        return Ok(None);
+    };
+    let user_id = String::from_utf8(user_id.as_bytes().to_vec())
+        .map_err(|_| ResponseError::unauthenticated())?;
+    let user_id = CoreErasedId::new(user_id).with_owner();
+
+    let mut found = interactors
+        .find_users_by_ids
+        .find_users_by_ids(Some(Actor::System), [user_id])
+        .await
+        .map_err(ResponseError::internal)?;
+    let Some((_, user)) = found.users.pop() else {
+        return Err(ResponseError::unauthenticated());
+    };
+    let user = user.into_user();
+    let User {
+        id,
+        data: UserData { role, .. },
+    } = user;
+    Ok(Some(Actor::User { id, role }))
+}
@@ -0,0 +1,265 @@
+//! Configuration of the user service which is loaded from the environment.
+
+use std::{
+    env::{self, VarError},
+    num::NonZeroUsize,
+    time::Duration as StdDuration,
+};
+
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+use fp_user_data::{
+    client::DatabaseConfig,
+    repository::{LocalJsonUserDatabase, LocalObjectStore, LocalOutboxMailer},
+};
+use fp_user_domain::{
+    model::{AvatarPolicy, EmailPolicy},
+    repository::{ResilienceConfig, UserCacheConfig},
+};
+use url::Url;
+
+use crate::{authenticate::AuthenticationConfig, personal_data::PersonalDataSourceConfig};
+
+/// Loads [email policy](EmailPolicy) from the environment variables:
+/// - `EMAIL_ALLOWED_DOMAINS`: comma separated list of allowed domains,
+///   any domain is allowed if not set or empty;
+/// - `EMAIL_DENIED_DOMAINS`: comma separated list of denied domains;
+/// - `EMAIL_MATCH_SUBDOMAINS`: whether subdomains of listed domains are matched too,
+///   `true` if not set.
+pub fn email_policy_from_env() -> Result<EmailPolicy> {
+    let allowed_domains = var("EMAIL_ALLOWED_DOMAINS")?
+        .map(|domains| split_list(&domains))
+        .filter(|domains| !domains.is_empty());
+    let denied_domains = var("EMAIL_DENIED_DOMAINS")?
+        .map(|domains| split_list(&domains))
+        .unwrap_or_default();
+    let match_subdomains = var("EMAIL_MATCH_SUBDOMAINS")?
+        .map(|value| value.parse())
+        .transpose()
+        .with_context(|| "EMAIL_MATCH_SUBDOMAINS must be either `true` or `false`")?
+        .unwrap_or(true);
+
+    let policy = EmailPolicy {
+        allowed_domains,
+        denied_domains,
+        match_subdomains,
+    };
+    Ok(policy)
+}
+
+/// Loads [avatar policy](AvatarPolicy) from the environment variables:
+/// - `AVATAR_ALLOWED_SCHEMES`: comma separated list of allowed URL schemes,
+///   only `https` is allowed if not set or empty;
+/// - `AVATAR_MAX_LENGTH`: maximal length of avatar URL, `2048` if not set;
+/// - `AVATAR_ALLOWED_HOSTS`: comma separated list of allowed hosts,
+///   any host is allowed if not set or empty;
+/// - `AVATAR_ALLOW_PRIVATE_HOSTS`: whether avatar URL could point to loopback,
+///   private or link-local hosts, `false` if not set.
+pub fn avatar_policy_from_env() -> Result<AvatarPolicy> {
+    let default = AvatarPolicy::default();
+    let allowed_schemes = var("AVATAR_ALLOWED_SCHEMES")?
+        .map(|schemes| split_list(&schemes))
+        .filter(|schemes| !schemes.is_empty())
+        .unwrap_or(default.allowed_schemes);
+    let max_length = var("AVATAR_MAX_LENGTH")?
+        .map(|value| value.parse())
+        .transpose()
+        .with_context(|| "AVATAR_MAX_LENGTH must be a non-negative integer")?
+        .unwrap_or(default.max_length);
+    let allowed_hosts = var("AVATAR_ALLOWED_HOSTS")?
+        .map(|hosts| split_list(&hosts))
+        .filter(|hosts| !hosts.is_empty());
+    let allow_private_hosts = var("AVATAR_ALLOW_PRIVATE_HOSTS")?
+        .map(|value| value.parse())
+        .transpose()
+        .with_context(|| "AVATAR_ALLOW_PRIVATE_HOSTS must be either `true` or `false`")?
+        .unwrap_or(default.allow_private_hosts);
+
+    let policy = AvatarPolicy {
+        allowed_schemes,
+        max_length,
+        allowed_hosts,
+        allow_private_hosts,
+    };
+    Ok(policy)
+}
+
+/// Loads [object store](LocalObjectStore) of uploaded avatars from the environment variables:
+/// - `AVATAR_STORE_ROOT`: directory where uploaded images are stored;
+/// - `AVATAR_STORE_URL`: base URL by which stored images are served.
+///
+/// Returns `None` if neither of them is set, so avatars cannot be uploaded.
+pub fn avatar_store_from_env() -> Result<Option<LocalObjectStore>> {
+    let root = var("AVATAR_STORE_ROOT")?;
+    let base_url = var("AVATAR_STORE_URL")?;
+    let (root, base_url) = match (root, base_url) {
+        (Some(root), Some(base_url)) => (root, base_url),
+        (None, None) => return Ok(None),
+        _ => bail!("AVATAR_STORE_ROOT and AVATAR_STORE_URL must be set together"),
+    };
+    let base_url: Url = base_url
+        .parse()
+        .with_context(|| "AVATAR_STORE_URL must be a valid URL")?;
+    let store = LocalObjectStore::new(root, base_url);
+    Ok(Some(store))
+}
+
+/// Loads [outbox mailer](LocalOutboxMailer) from the environment variables:
+/// - `MAILER_OUTBOX_DIR`: directory where mail is written for the mail transfer agent;
+/// - `MAILER_SENDER`: address of the sender of the mail,
+///   `no-reply@flexible-project.local` if not set.
+///
+/// Returns `None` if outbox directory is not set, so no mail can be sent.
+pub fn mailer_from_env() -> Result<Option<LocalOutboxMailer>> {
+    let Some(outbox) = var("MAILER_OUTBOX_DIR")? else {
+        return Ok(None);
+    };
+    let sender = var("MAILER_SENDER")?;
+    let sender = sender.unwrap_or_else(|| "no-reply@flexible-project.local".to_owned());
+    if sender.contains(['\r', '\n']) {
+        bail!("MAILER_SENDER must be a single line");
+    }
+    let mailer = LocalOutboxMailer::new(outbox, sender);
+    Ok(Some(mailer))
+}
+
+/// Opens [JSON file user database](LocalJsonUserDatabase) by path of `USER_DATABASE_JSON_FILE`
+/// environment variable, which is used to run the service without storing user data in MongoDB.
+///
+/// Returns `None` if it is not set, so user data is stored in MongoDB.
+pub async fn user_json_database_from_env() -> Result<Option<LocalJsonUserDatabase>> {
+    let Some(path) = var("USER_DATABASE_JSON_FILE")? else {
+        return Ok(None);
+    };
+    let database = LocalJsonUserDatabase::open(&path)
+        .await
+        .with_context(|| format!("failed to open user database from {path}"))?;
+    Ok(Some(database))
+}
+
+/// Loads [configuration of the user cache](UserCacheConfig) from the environment variables:
+/// - `USER_CACHE_CAPACITY`: maximal count of cached users, `1024` if not set;
+/// - `USER_CACHE_TTL_SECONDS`: duration in seconds after which cached user is read again,
+///   `300` if not set.
+pub fn user_cache_config_from_env() -> Result<UserCacheConfig> {
+    let default = UserCacheConfig::default();
+    let capacity = var("USER_CACHE_CAPACITY")?
+        .map(|value| value.parse::<NonZeroUsize>())
+        .transpose()
+        .with_context(|| "USER_CACHE_CAPACITY must be a positive integer")?
+        .unwrap_or(default.capacity);
+    let ttl = var("USER_CACHE_TTL_SECONDS")?
+        .map(|value| value.parse().map(Duration::seconds))
+        .transpose()
+        .with_context(|| "USER_CACHE_TTL_SECONDS must be an integer")?
+        .unwrap_or(default.ttl);
+
+    let config = UserCacheConfig { capacity, ttl };
+    Ok(config)
+}
+
+/// Loads [configuration of retries and of the circuit](ResilienceConfig) of the user database
+/// from the environment variables:
+/// - `USER_DATABASE_MAX_ATTEMPTS`: maximal count of attempts of the operation
+///   which failed transiently, `3` if not set;
+/// - `USER_DATABASE_FAILURE_THRESHOLD`: count of consecutive transient failures
+///   after which the circuit is opened, `5` if not set;
+/// - `USER_DATABASE_OPEN_SECONDS`: duration in seconds during which requests are requeued
+///   after the circuit was opened, `30` if not set.
+pub fn resilience_config_from_env() -> Result<ResilienceConfig> {
+    let default = ResilienceConfig::default();
+    let max_attempts = var("USER_DATABASE_MAX_ATTEMPTS")?
+        .map(|value| value.parse())
+        .transpose()
+        .with_context(|| "USER_DATABASE_MAX_ATTEMPTS must be a non-negative integer")?
+        .unwrap_or(default.max_attempts);
+    let failure_threshold = var("USER_DATABASE_FAILURE_THRESHOLD")?
+        .map(|value| value.parse())
+        .transpose()
+        .with_context(|| "USER_DATABASE_FAILURE_THRESHOLD must be a non-negative integer")?
+        .unwrap_or(default.failure_threshold);
+    let open_duration = var("USER_DATABASE_OPEN_SECONDS")?
+        .map(|value| value.parse().map(Duration::seconds))
+        .transpose()
+        .with_context(|| "USER_DATABASE_OPEN_SECONDS must be an integer")?
+        .unwrap_or(default.open_duration);
+
+    let config = ResilienceConfig {
+        max_attempts,
+        failure_threshold,
+        open_duration,
+        ..default
+    };
+    Ok(config)
+}
+
+/// Loads [configuration of the personal data source](PersonalDataSourceConfig)
+/// from the environment variables:
+/// - `PERSONAL_DATA_QUEUES`: comma separated list of queues of other services
+///   which hold personal data of the users, no data is collected if not set or empty;
+/// - `PERSONAL_DATA_TIMEOUT_SECONDS`: duration in seconds during which
+///   all of the services must reply, `10` if not set.
+pub fn personal_data_source_config_from_env() -> Result<PersonalDataSourceConfig> {
+    let default = PersonalDataSourceConfig::default();
+    let queues = var("PERSONAL_DATA_QUEUES")?
+        .map(|queues| split_list(&queues))
+        .unwrap_or(default.queues);
+    let timeout = var("PERSONAL_DATA_TIMEOUT_SECONDS")?
+        .map(|value| value.parse().map(StdDuration::from_secs))
+        .transpose()
+        .with_context(|| "PERSONAL_DATA_TIMEOUT_SECONDS must be a non-negative integer")?
+        .unwrap_or(default.timeout);
+
+    let config = PersonalDataSourceConfig { queues, timeout };
+    Ok(config)
+}
+
+/// Loads [authentication configuration](AuthenticationConfig) from the environment variables:
+/// - `AMQP_GATEWAY_USER`: AMQP user of the gateway which provides authenticated users,
+///   all requests are anonymous if not set;
+/// - `AMQP_SYSTEM_USER`: AMQP user of the internal services which act as the system,
+///   no requests are done by the system if not set.
+pub fn authentication_config_from_env() -> Result<AuthenticationConfig> {
+    let gateway_user = var("AMQP_GATEWAY_USER")?;
+    let system_user = var("AMQP_SYSTEM_USER")?;
+    if gateway_user.is_some() && gateway_user == system_user {
+        bail!("AMQP_GATEWAY_USER and AMQP_SYSTEM_USER must be different");
+    }
+    let config = AuthenticationConfig {
+        gateway_user,
+        system_user,
+    };
+    Ok(config)
+}
+
+/// Loads [database configuration](DatabaseConfig) from the JSON file
+/// by path of `DATABASE_CONFIG_FILE` environment variable (if set),
+/// then overrides it with `DATABASE_*` environment variables.
+pub async fn database_config_from_env() -> Result<DatabaseConfig> {
+    let config = match var("DATABASE_CONFIG_FILE")? {
+        Some(path) => DatabaseConfig::from_file(&path)
+            .await
+            .with_context(|| format!("failed to load database configuration from {path}"))?,
+        None => DatabaseConfig::default(),
+    };
+    let config = config
+        .with_env()
+        .with_context(|| "failed to load database configuration from the environment")?;
+    Ok(config)
+}
+
+fn var(key: &str) -> Result<Option<String>> {
+    match env::var(key) {
+        Ok(value) => Ok(Some(value)),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(error) => Err(error).with_context(|| format!("{key} must be valid unicode")),
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
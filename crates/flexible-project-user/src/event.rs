@@ -0,0 +1,90 @@
+//! Publishing of the user events for other services of the system.
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{model::UserEvent as DomainUserEvent, repository::PublishUserEvent};
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions, ExchangeDeclareOptions},
+    types::FieldTable,
+    BasicProperties, Channel, Connection, ExchangeKind,
+};
+
+use crate::model::UserEvent;
+
+/// Name of the fanout exchange where user events are published,
+/// so each consuming service binds its own queue to it.
+pub const USER_EVENTS_EXCHANGE: &str = "user.events";
+
+/// Implementation of user event publisher which publishes events into
+/// [user events exchange](USER_EVENTS_EXCHANGE) of the AMQP server.
+///
+/// Events are persistent and each publish waits for the confirmation of the server,
+/// so the event is not lost once it was published.
+#[derive(Clone)]
+pub struct AmqpPublishUserEvent {
+    channel: Channel,
+}
+
+impl AmqpPublishUserEvent {
+    /// Creates new publisher with its own channel of provided connection
+    /// and declares user events exchange.
+    pub async fn new(connection: &Connection) -> Result<Self, AmqpPublishError> {
+        let channel = connection.create_channel().await?;
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await?;
+        let options = ExchangeDeclareOptions {
+            durable: true,
+            ..Default::default()
+        };
+        channel
+            .exchange_declare(
+                USER_EVENTS_EXCHANGE,
+                ExchangeKind::Fanout,
+                options,
+                FieldTable::default(),
+            )
+            .await?;
+        Ok(Self { channel })
+    }
+}
+
+#[async_trait(?Send)]
+impl PublishUserEvent for AmqpPublishUserEvent {
+    type Error = AmqpPublishError;
+
+    async fn publish(&self, event: DomainUserEvent) -> Result<(), Self::Error> {
+        let Self { channel } = self;
+        let payload = serde_json::to_vec(&UserEvent::from(event))?;
+        // delivery mode 2 makes the message persistent
+        let properties = BasicProperties::default().with_delivery_mode(2);
+        let confirmation = channel
+            .basic_publish(
+                USER_EVENTS_EXCHANGE,
+                "",
+                BasicPublishOptions::default(),
+                &payload,
+                properties,
+            )
+            .await?
+            .await?;
+        if confirmation.is_nack() {
+            return Err(AmqpPublishError::Rejected);
+        }
+        Ok(())
+    }
+}
+
+/// Type of error which is returned when user event cannot be published.
+#[derive(Debug, Display, From, Error)]
+pub enum AmqpPublishError {
+    /// Event cannot be serialized.
+    #[display(fmt = "event cannot be serialized: {}", _0)]
+    Serialize(serde_json::Error),
+    /// AMQP server or connection error.
+    #[display(fmt = "AMQP error: {}", _0)]
+    Amqp(lapin::Error),
+    /// AMQP server rejected the event.
+    #[display(fmt = "event was rejected by AMQP server")]
+    Rejected,
+}
@@ -1,12 +1,25 @@
 //! Utilities to properly handle incoming request.
 
-use std::str;
+use std::time::Duration;
 
+use fp_core::id::ErasedId as CoreErasedId;
+use fp_user_data::repository::LocalUserRecordFormat;
+use fp_user_domain::model::Actor;
+use futures::TryStreamExt;
 use lapin::{
     message::Delivery, options::BasicPublishOptions, types::ShortString, BasicProperties, Channel,
 };
 
-use crate::request::Request;
+use crate::{
+    authenticate::{authenticate, AuthenticationConfig},
+    interactor::Interactors,
+    model::TryFromUserDataError,
+    request::Request,
+    response::{Response, ResponseError, ResponseErrorCode},
+};
+
+/// Delay before the request is requeued while the user database is unavailable.
+const UNAVAILABLE_DELAY: Duration = Duration::from_secs(1);
 
 /// Type of error which is returned if request handling fails.
 pub enum HandleRequestError {
@@ -20,10 +33,12 @@ pub enum HandleRequestError {
 pub async fn handle_request(
     delivery: &Delivery,
     channel: &Channel,
+    interactors: &Interactors,
+    authentication: &AuthenticationConfig,
 ) -> Result<(), HandleRequestError> {
     let routing_key = get_routing_key(delivery)?;
     let correlation_id = get_correlation_id(delivery)?;
-    let payload = create_response(delivery).await?;
+    let payload = create_response(delivery, interactors, authentication).await?;
 
     let publish_response = publish_response(
         channel,
@@ -56,9 +71,6 @@ fn get_correlation_id(delivery: &Delivery) -> Result<&ShortString, HandleRequest
 
 fn get_request(delivery: &Delivery) -> Result<Request, HandleRequestError> {
     let data = delivery.data.as_slice();
-    if let Ok(data) = str::from_utf8(data) {
-        tracing::info!(%data, "received data from the message");
-    }
     let request = serde_json::from_slice(data).map_err(|error| {
         tracing::error!(%error, "message is not a valid request");
         HandleRequestError::Reject
@@ -66,15 +78,241 @@ fn get_request(delivery: &Delivery) -> Result<Request, HandleRequestError> {
     Ok(request)
 }
 
-async fn create_response(delivery: &Delivery) -> Result<Vec<u8>, HandleRequestError> {
+async fn create_response(
+    delivery: &Delivery,
+    interactors: &Interactors,
+    authentication: &AuthenticationConfig,
+) -> Result<Vec<u8>, HandleRequestError> {
     let request = get_request(delivery)?;
     tracing::info!(?request, "received request from the message");
+    if !interactors.is_user_database_available() {
+        return Err(requeue_unavailable().await);
+    }
 
-    // TODO handle request and answer on it with actual data
-    let payload = b"".to_vec();
+    let response = async {
+        let actor = authenticate(delivery, authentication, interactors).await?;
+        respond(request, actor, interactors).await
+    };
+    let response = response.await;
+    let is_internal = matches!(&response, Err(error) if error.code == ResponseErrorCode::Internal);
+    if is_internal && !interactors.is_user_database_available() {
+        return Err(requeue_unavailable().await);
+    }
+    let response: Response = response.into();
+    let payload = serde_json::to_vec(&response).map_err(|error| {
+        tracing::error!(%error, "response cannot be serialized");
+        HandleRequestError::Reject
+    })?;
     Ok(payload)
 }
 
+/// Delays requeueing of the request while the circuit of the user database is open,
+/// so the request is not redelivered to the service immediately.
+async fn requeue_unavailable() -> HandleRequestError {
+    tracing::warn!("user database is unavailable, request is requeued");
+    tokio::time::sleep(UNAVAILABLE_DELAY).await;
+    HandleRequestError::Nack
+}
+
+async fn respond(
+    request: Request,
+    actor: Option<Actor>,
+    interactors: &Interactors,
+) -> Result<Response, ResponseError> {
+    let response = match request {
+        Request::CreateUser { name, email } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let name = name.try_into().map_err(TryFromUserDataError::from)?;
+            let email = email
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(TryFromUserDataError::from)?;
+            let create_user = &interactors.create_user;
+            let user = create_user.create_user(actor, name, email).await?;
+            Response::User(user.into())
+        }
+        Request::FilterUsers { filters } => {
+            let filters = (*filters).try_into()?;
+            let filter_users = &interactors.filter_users;
+            let users = filter_users.filter_users(actor, filters).await?;
+            let users = users
+                .map_ok(Into::into)
+                .try_collect()
+                .await
+                .map_err(ResponseError::internal)?;
+            Response::Users(users)
+        }
+        Request::CountUsers { filters } => {
+            let filters = (*filters).try_into()?;
+            let count_users = &interactors.count_users;
+            let count = count_users.count_users(actor, filters).await?;
+            Response::Count(count)
+        }
+        Request::CountUsersByRole { filters } => {
+            let filters = (*filters).try_into()?;
+            let count_users = &interactors.count_users;
+            let counts = count_users.count_users_by_role(actor, filters).await?;
+            let counts = counts
+                .into_iter()
+                .map(|(role, count)| (role.into(), count))
+                .collect();
+            Response::CountByRole(counts)
+        }
+        Request::FindUsersByIds { ids } => {
+            let ids = ids
+                .into_iter()
+                .map(|id| CoreErasedId::from(id).with_owner());
+            let find_users_by_ids = &interactors.find_users_by_ids;
+            let found = find_users_by_ids
+                .find_users_by_ids(actor, ids)
+                .await
+                .map_err(ResponseError::internal)?;
+            Response::FoundUsers(found.into())
+        }
+        Request::FindUserByName { name } => {
+            let name = name.try_into().map_err(TryFromUserDataError::from)?;
+            let find_user_by_name = &interactors.find_user_by_name;
+            let user = find_user_by_name.find_user_by_name(actor, name).await?;
+            Response::UserByName(user.map(Into::into))
+        }
+        Request::UpdateUser { current_id, update } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let update = update.try_into()?;
+            let update_user = &interactors.update_user;
+            let user = update_user.update_user(actor, current_id, update).await?;
+            Response::User(user.into())
+        }
+        Request::DeleteUser { current_id } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let delete_user = &interactors.delete_user;
+            let user = delete_user.delete_user(actor, current_id).await?;
+            Response::User(user.into())
+        }
+        Request::UploadAvatar {
+            current_id,
+            content,
+        } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let Some(upload_avatar) = &interactors.upload_avatar else {
+                return Err(ResponseError::unavailable("avatar upload"));
+            };
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let user = upload_avatar
+                .upload_avatar(actor, current_id, content.into_inner())
+                .await?;
+            Response::User(user.into())
+        }
+        Request::ChangeRole { current_id, role } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let change_role = &interactors.change_role;
+            let user = change_role
+                .change_role(actor, current_id, role.into())
+                .await?;
+            Response::User(user.into())
+        }
+        Request::RequestPasswordReset { email } => {
+            let Some(request_password_reset) = &interactors.request_password_reset else {
+                return Err(ResponseError::unavailable("password reset request"));
+            };
+            let email = email.try_into().map_err(TryFromUserDataError::from)?;
+            request_password_reset
+                .request_password_reset(email)
+                .await
+                .map_err(ResponseError::internal)?;
+            Response::Done
+        }
+        Request::ResetPassword { token, password } => {
+            let password = password.try_into().map_err(TryFromUserDataError::from)?;
+            let reset_password = &interactors.reset_password;
+            let user = reset_password
+                .reset_password(token.into(), password)
+                .await?;
+            Response::User(user.into())
+        }
+        Request::SuspendUser {
+            current_id,
+            until,
+            reason,
+        } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let suspend_user = &interactors.suspend_user;
+            let user = suspend_user
+                .suspend_user(actor, current_id, until, reason)
+                .await?;
+            Response::User(user.into())
+        }
+        Request::UnsuspendUser { current_id } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let unsuspend_user = &interactors.unsuspend_user;
+            let user = unsuspend_user.unsuspend_user(actor, current_id).await?;
+            Response::User(user.into())
+        }
+        Request::BanUser { current_id, reason } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let ban_user = &interactors.ban_user;
+            let user = ban_user.ban_user(actor, current_id, reason).await?;
+            Response::User(user.into())
+        }
+        Request::ImportUsers {
+            format,
+            content,
+            dry_run,
+        } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let format = LocalUserRecordFormat::from(format);
+            let import_users = &interactors.import_users;
+            let result = import_users
+                .import_users(actor, format, content.into_inner(), dry_run)
+                .await;
+            match result {
+                Ok(report) => Response::ImportReport(report.into()),
+                Err(aborted) => Response::ImportAborted {
+                    report: aborted.report.into(),
+                    error: aborted.error.into(),
+                },
+            }
+        }
+        Request::ExportUsers { format, filters } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let format = LocalUserRecordFormat::from(format);
+            let filters = (*filters).try_into()?;
+            let export_users = &interactors.export_users;
+            let content = export_users.export_users(actor, format, filters).await?;
+            Response::ExportedUsers(content)
+        }
+        Request::ExportPersonalData { current_id } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let export_personal_data = &interactors.export_personal_data;
+            let personal_data = export_personal_data
+                .export_personal_data(actor, current_id)
+                .await?;
+            Response::PersonalData(Box::new(personal_data.into()))
+        }
+        Request::EraseUser { current_id } => {
+            let actor = actor.ok_or_else(ResponseError::unauthenticated)?;
+            let current_id = CoreErasedId::from(current_id).with_owner();
+            let erase_user = &interactors.erase_user;
+            let user = erase_user.erase_user(actor, current_id).await?;
+            Response::User(user.into())
+        }
+        Request::SignIn { name, password } => {
+            let name = name.try_into().map_err(TryFromUserDataError::from)?;
+            let password = password.try_into().map_err(TryFromUserDataError::from)?;
+            let sign_in = &interactors.sign_in;
+            let user = sign_in.sign_in(name, password).await?;
+            Response::User(user.into())
+        }
+    };
+    Ok(response)
+}
+
 async fn publish_response(
     channel: &Channel,
     payload: &[u8],
@@ -0,0 +1,363 @@
+//! Interactors of the user service which are configured with local repositories.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_core::metrics::RepositoryMetrics;
+use fp_user_data::{
+    client::Client,
+    repository::{
+        LocalAnyUserDatabase, LocalClock, LocalCredentialsDatabase, LocalDelay,
+        LocalGeneratePasswordResetToken, LocalGenerateUserId, LocalHashPassword,
+        LocalJsonUserDatabase, LocalNameHistoryDatabase, LocalObjectStore, LocalObjectStoreError,
+        LocalOutboxMailer, LocalPasswordResetDatabase, LocalResizeImage, LocalUserDatabase,
+    },
+};
+use fp_user_domain::{
+    model::{AvatarPolicy, EmailPolicy},
+    repository::{
+        CachedUserDatabase, InstrumentedUserDatabase, ObjectStore, ResilienceConfig,
+        ResilientUserDatabase, UserCacheConfig,
+    },
+    use_case::{
+        AvatarUploadConfig, BanUser, ChangeRole, CountUsers, CreateUser, DeleteUser,
+        DisplayNameConfig, EraseUser, ExportPersonalData, ExportUsers, FilterUsers, FindUserByName,
+        FindUsersByIds, ImportUsers, NameHistoryConfig, PasswordResetConfig, RequestPasswordReset,
+        ResetPassword, SignIn, SuspendUser, UnsuspendUser, UpdateUser, UploadAvatar,
+    },
+};
+use lapin::Connection;
+use url::Url;
+
+use crate::{
+    event::AmqpPublishUserEvent,
+    personal_data::{AmqpPersonalDataSource, PersonalDataSourceConfig},
+};
+
+/// Configuration of the interactors which is loaded by the user service.
+#[derive(Debug, Clone, Default)]
+pub struct InteractorsConfig {
+    /// Policy which emails of new and updated users should conform to.
+    pub email_policy: EmailPolicy,
+    /// Policy which new avatars of the users should conform to.
+    pub avatar_policy: AvatarPolicy,
+    /// Object store of uploaded avatars, or `None` if avatars cannot be uploaded.
+    pub avatar_store: Option<LocalObjectStore>,
+    /// Mailer of password reset tokens, or `None` if password reset cannot be requested.
+    pub mailer: Option<LocalOutboxMailer>,
+    /// Database of user data persisted into a JSON file,
+    /// or `None` if user data is stored in MongoDB.
+    pub user_json_database: Option<LocalJsonUserDatabase>,
+    /// Configuration of the cache of users in front of the user database.
+    pub user_cache: UserCacheConfig,
+    /// Metrics which operations of the user database are recorded into.
+    pub user_metrics: RepositoryMetrics,
+    /// Configuration of retries and of the circuit of the user database.
+    pub resilience: ResilienceConfig,
+    /// Configuration of the collection of personal data from other services.
+    pub personal_data: PersonalDataSourceConfig,
+}
+
+/// Selected database of user data which retries transient failures and opens the circuit
+/// after repeated ones.
+pub type ResilientServiceUserDatabase =
+    Arc<ResilientUserDatabase<LocalAnyUserDatabase, LocalDelay, LocalClock>>;
+
+/// Database of user data which is used by the interactors:
+/// instrumented resilient database behind the cache which is shared by all interactors.
+pub type ServiceUserDatabase =
+    Arc<CachedUserDatabase<InstrumentedUserDatabase<ResilientServiceUserDatabase>, LocalClock>>;
+
+/// Object store of uploaded avatars which holds no objects if avatars cannot be uploaded,
+/// so the users could be erased in either case.
+#[derive(Debug, Clone)]
+pub struct AvatarStore(Option<LocalObjectStore>);
+
+#[async_trait(?Send)]
+impl ObjectStore for AvatarStore {
+    type Error = AvatarStoreError;
+
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<Url, Self::Error> {
+        let Self(store) = self;
+        let store = store.as_ref().ok_or(AvatarStoreError::Unavailable)?;
+        let url = store.put(key, content_type, content).await?;
+        Ok(url)
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, Self::Error> {
+        let Self(store) = self;
+        let Some(store) = store else {
+            return Ok(Vec::new());
+        };
+        let keys = store.list(prefix).await?;
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        let Self(store) = self;
+        let Some(store) = store else {
+            return Ok(());
+        };
+        store.delete(key).await?;
+        Ok(())
+    }
+}
+
+/// Type of error which is returned when [avatar store](AvatarStore) fails.
+#[derive(Debug, Display, From, Error)]
+pub enum AvatarStoreError {
+    /// Avatars cannot be uploaded.
+    #[display(fmt = "avatar store is not configured")]
+    Unavailable,
+    /// Local object store error.
+    Store(LocalObjectStoreError),
+}
+
+/// Interactors which handle requests of the clients of the user service.
+pub struct Interactors {
+    /// Create user interactor.
+    pub create_user:
+        CreateUser<ServiceUserDatabase, LocalNameHistoryDatabase, LocalGenerateUserId, LocalClock>,
+    /// Filter users interactor.
+    pub filter_users: FilterUsers<ServiceUserDatabase, LocalClock>,
+    /// Count users interactor.
+    pub count_users: CountUsers<ServiceUserDatabase, LocalClock>,
+    /// Find users by identifiers interactor.
+    pub find_users_by_ids: FindUsersByIds<ServiceUserDatabase>,
+    /// Find user by name interactor.
+    pub find_user_by_name:
+        FindUserByName<ServiceUserDatabase, LocalNameHistoryDatabase, LocalClock>,
+    /// Update user interactor.
+    pub update_user: UpdateUser<ServiceUserDatabase, LocalNameHistoryDatabase, LocalClock>,
+    /// Upload avatar interactor, or `None` if avatars cannot be uploaded.
+    pub upload_avatar:
+        Option<UploadAvatar<ServiceUserDatabase, LocalObjectStore, LocalResizeImage, LocalClock>>,
+    /// Delete user interactor.
+    pub delete_user: DeleteUser<ServiceUserDatabase, LocalClock>,
+    /// Change role interactor.
+    pub change_role: ChangeRole<ServiceUserDatabase, LocalClock>,
+    /// Request password reset interactor, or `None` if password reset cannot be requested.
+    pub request_password_reset: Option<
+        RequestPasswordReset<
+            ServiceUserDatabase,
+            LocalPasswordResetDatabase,
+            LocalGeneratePasswordResetToken,
+            LocalOutboxMailer,
+            LocalClock,
+        >,
+    >,
+    /// Reset password interactor.
+    pub reset_password: ResetPassword<
+        ServiceUserDatabase,
+        LocalCredentialsDatabase,
+        LocalPasswordResetDatabase,
+        LocalGeneratePasswordResetToken,
+        LocalHashPassword,
+        LocalClock,
+    >,
+    /// Suspend user interactor.
+    pub suspend_user: SuspendUser<ServiceUserDatabase, LocalClock>,
+    /// Unsuspend user interactor.
+    pub unsuspend_user: UnsuspendUser<ServiceUserDatabase, LocalClock>,
+    /// Ban user interactor.
+    pub ban_user: BanUser<ServiceUserDatabase, LocalClock>,
+    /// Import users interactor.
+    pub import_users:
+        ImportUsers<ServiceUserDatabase, LocalNameHistoryDatabase, LocalGenerateUserId, LocalClock>,
+    /// Export users interactor.
+    pub export_users: ExportUsers<ServiceUserDatabase, LocalClock>,
+    /// Export personal data interactor.
+    pub export_personal_data: ExportPersonalData<
+        ServiceUserDatabase,
+        LocalCredentialsDatabase,
+        LocalNameHistoryDatabase,
+        AmqpPersonalDataSource,
+        LocalClock,
+    >,
+    /// Erase user interactor.
+    pub erase_user: EraseUser<
+        ServiceUserDatabase,
+        LocalCredentialsDatabase,
+        LocalNameHistoryDatabase,
+        LocalPasswordResetDatabase,
+        AvatarStore,
+        AmqpPublishUserEvent,
+        LocalClock,
+    >,
+    /// Sign in interactor.
+    pub sign_in:
+        SignIn<ServiceUserDatabase, LocalCredentialsDatabase, LocalHashPassword, LocalClock>,
+    /// Resilient user database which is shared by all interactors.
+    resilient: ResilientServiceUserDatabase,
+}
+
+impl Interactors {
+    /// Creates interactors which use local repositories of provided client,
+    /// AMQP connection to communicate with other services and provided configuration.
+    pub async fn new(
+        client: Client,
+        connection: &Connection,
+        config: InteractorsConfig,
+    ) -> Result<Self> {
+        let InteractorsConfig {
+            email_policy,
+            avatar_policy,
+            avatar_store,
+            mailer,
+            user_json_database,
+            user_cache,
+            user_metrics,
+            resilience,
+            personal_data,
+        } = config;
+        let database: LocalAnyUserDatabase = match user_json_database {
+            Some(database) => database.into(),
+            None => LocalUserDatabase::new(client.clone())
+                .await
+                .with_context(|| "failed to create user database")?
+                .into(),
+        };
+        let resilient = ResilientUserDatabase::new(database, LocalDelay, LocalClock, resilience);
+        let resilient = Arc::new(resilient);
+        let database = InstrumentedUserDatabase::new(resilient.clone(), user_metrics);
+        let database = Arc::new(CachedUserDatabase::new(database, LocalClock, user_cache));
+        let history = LocalNameHistoryDatabase::new(client.clone())
+            .await
+            .with_context(|| "failed to create name history database")?;
+        let credentials = LocalCredentialsDatabase::new(client.clone())
+            .await
+            .with_context(|| "failed to create credentials database")?;
+        let reset_config = PasswordResetConfig::default();
+        let retention = {
+            let PasswordResetConfig {
+                token_lifetime,
+                rate_limit_window,
+                ..
+            } = reset_config;
+            let retention = token_lifetime.max(rate_limit_window).to_std();
+            retention.with_context(|| "password reset retention is negative")?
+        };
+        let resets = LocalPasswordResetDatabase::new(client, retention)
+            .await
+            .with_context(|| "failed to create password reset database")?;
+        let source = AmqpPersonalDataSource::new(connection, personal_data)
+            .await
+            .with_context(|| "failed to create personal data source")?;
+        let publisher = AmqpPublishUserEvent::new(connection)
+            .await
+            .with_context(|| "failed to create user event publisher")?;
+
+        let interactors = Self {
+            create_user: CreateUser::new(
+                database.clone(),
+                history.clone(),
+                LocalGenerateUserId,
+                LocalClock,
+                NameHistoryConfig::default(),
+                DisplayNameConfig::default(),
+                email_policy.clone(),
+            ),
+            filter_users: FilterUsers::new(database.clone(), LocalClock),
+            count_users: CountUsers::new(database.clone(), LocalClock),
+            find_users_by_ids: FindUsersByIds::new(database.clone()),
+            find_user_by_name: FindUserByName::new(
+                database.clone(),
+                history.clone(),
+                LocalClock,
+                NameHistoryConfig::default(),
+            ),
+            update_user: UpdateUser::new(
+                database.clone(),
+                history.clone(),
+                LocalClock,
+                NameHistoryConfig::default(),
+                DisplayNameConfig::default(),
+                email_policy.clone(),
+                avatar_policy.clone(),
+            ),
+            upload_avatar: avatar_store.clone().map(|store| {
+                let config = AvatarUploadConfig::builder()
+                    .avatar_policy(avatar_policy.clone())
+                    .build();
+                UploadAvatar::new(
+                    database.clone(),
+                    store,
+                    LocalResizeImage::default(),
+                    LocalClock,
+                    config,
+                )
+            }),
+            delete_user: DeleteUser::new(database.clone(), LocalClock),
+            change_role: ChangeRole::new(database.clone(), LocalClock),
+            request_password_reset: mailer.map(|mailer| {
+                RequestPasswordReset::new(
+                    database.clone(),
+                    resets.clone(),
+                    LocalGeneratePasswordResetToken,
+                    mailer,
+                    LocalClock,
+                    reset_config,
+                )
+            }),
+            reset_password: ResetPassword::new(
+                database.clone(),
+                credentials.clone(),
+                resets.clone(),
+                LocalGeneratePasswordResetToken,
+                LocalHashPassword::default(),
+                LocalClock,
+            ),
+            suspend_user: SuspendUser::new(database.clone(), LocalClock),
+            unsuspend_user: UnsuspendUser::new(database.clone(), LocalClock),
+            ban_user: BanUser::new(database.clone(), LocalClock),
+            import_users: ImportUsers::new(
+                database.clone(),
+                history.clone(),
+                LocalGenerateUserId,
+                LocalClock,
+                NameHistoryConfig::default(),
+                DisplayNameConfig::default(),
+                email_policy,
+                avatar_policy,
+            ),
+            export_users: ExportUsers::new(database.clone(), LocalClock),
+            export_personal_data: ExportPersonalData::new(
+                database.clone(),
+                credentials.clone(),
+                history.clone(),
+                source,
+                LocalClock,
+            ),
+            erase_user: EraseUser::new(
+                database.clone(),
+                credentials.clone(),
+                history,
+                resets,
+                AvatarStore(avatar_store),
+                publisher,
+                LocalClock,
+            ),
+            sign_in: SignIn::new(
+                database,
+                credentials,
+                LocalHashPassword::default(),
+                LocalClock,
+            ),
+            resilient,
+        };
+        Ok(interactors)
+    }
+
+    /// Checks if the circuit of the user database is closed,
+    /// so the interactors could call the database.
+    pub fn is_user_database_available(&self) -> bool {
+        !self.resilient.is_open()
+    }
+}
@@ -7,24 +7,43 @@
 use std::pin::pin;
 
 use anyhow::{Context, Result};
+use fp_core::metrics::RepositoryMetrics;
+use fp_user_data::client::Client;
 use futures::{FutureExt, StreamExt};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use self::{
+    config::{
+        authentication_config_from_env, avatar_policy_from_env, avatar_store_from_env,
+        database_config_from_env, email_policy_from_env, mailer_from_env,
+        personal_data_source_config_from_env, resilience_config_from_env,
+        user_cache_config_from_env, user_json_database_from_env,
+    },
     handle_request::handle_request,
     handle_result::handle_result,
+    interactor::{Interactors, InteractorsConfig},
+    migrate::{migrate, Command},
     setup::{create_channel, create_connection, create_consumer, declare_queue},
 };
 
+pub mod authenticate;
+pub mod config;
+pub mod event;
 pub mod handle_request;
 pub mod handle_result;
+pub mod interactor;
+pub mod migrate;
 pub mod model;
+pub mod personal_data;
 pub mod request;
+pub mod response;
 pub mod setup;
 
 /// Entry point of the user backend microservice binary.
 #[tokio::main]
 pub async fn main() -> Result<()> {
+    let command = Command::parse(std::env::args().skip(1))?;
+
     if cfg!(debug_assertions) {
         dotenv::dotenv().with_context(|| ".env file not found")?;
     }
@@ -34,9 +53,54 @@ pub async fn main() -> Result<()> {
         .try_init()
         .with_context(|| "failed to init tracing subscriber")?;
 
+    let uri = std::env::var("DATABASE_URL").with_context(|| "DATABASE_URL must be set")?;
+    let database_config = database_config_from_env().await?;
+    tracing::info!(?database_config, "loaded database configuration");
+    let client = Client::new(&uri, database_config)
+        .await
+        .with_context(|| "failed to create database client")?;
+    match command {
+        Command::Migrate { dry_run } => return migrate(client, dry_run).await,
+        Command::Serve => migrate(client.clone(), false).await?,
+    }
+
+    let email_policy = email_policy_from_env()?;
+    tracing::info!(?email_policy, "loaded email policy");
+    let avatar_policy = avatar_policy_from_env()?;
+    tracing::info!(?avatar_policy, "loaded avatar policy");
+    let avatar_store = avatar_store_from_env()?;
+    tracing::info!(?avatar_store, "loaded avatar store");
+    let mailer = mailer_from_env()?;
+    tracing::info!(?mailer, "loaded mailer");
+    let user_json_database = user_json_database_from_env().await?;
+    if user_json_database.is_some() {
+        tracing::info!("user data is stored in the JSON file instead of MongoDB");
+    }
+    let user_cache = user_cache_config_from_env()?;
+    tracing::info!(?user_cache, "loaded user cache configuration");
+    let resilience = resilience_config_from_env()?;
+    tracing::info!(?resilience, "loaded user database resilience configuration");
+    let personal_data = personal_data_source_config_from_env()?;
+    tracing::info!(?personal_data, "loaded personal data source configuration");
+    let user_metrics = RepositoryMetrics::new();
+    let config = InteractorsConfig {
+        email_policy,
+        avatar_policy,
+        avatar_store,
+        mailer,
+        user_json_database,
+        user_cache,
+        user_metrics: user_metrics.clone(),
+        resilience,
+        personal_data,
+    };
+    let authentication = authentication_config_from_env()?;
+    tracing::info!(?authentication, "loaded authentication configuration");
+
     let uri = std::env::var("AMQP_SERVER_URI").with_context(|| "AMQP_SERVER_URI must be set")?;
     let connection = create_connection(&uri).await?;
     tracing::info!("connected to an AMQP server");
+    let interactors = Interactors::new(client, &connection, config).await?;
 
     let channel = create_channel(&connection).await?;
     let _queue = declare_queue(&channel).await?;
@@ -51,7 +115,7 @@ pub async fn main() -> Result<()> {
                 return;
             }
         };
-        let result = handle_request(&delivery, &channel).await;
+        let result = handle_request(&delivery, &channel, &interactors, &authentication).await;
         handle_result(result, &delivery, &channel).await
     });
     let graceful_shutdown = shutdown_signal();
@@ -62,6 +126,7 @@ pub async fn main() -> Result<()> {
         () = request_handler => tracing::error!("should listen for incoming requests endlessly"),
         () = graceful_shutdown => tracing::info!("gracefully shutdown the server"),
     }
+    tracing::info!(metrics = ?user_metrics.snapshot(), "user database metrics");
     Ok(())
 }
 
@@ -0,0 +1,78 @@
+//! Commands of the user service binary and migrations of the local database.
+
+use anyhow::{bail, Context, Result};
+use fp_user_data::{
+    client::Client,
+    migration::{MigrationReport, Migrator},
+};
+
+/// Command of the user service binary which is parsed from the command line arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Applies pending migrations, then serves incoming requests.
+    Serve,
+    /// Applies pending migrations (or only reports them on dry run), then exits.
+    Migrate {
+        /// Whether pending migrations should only be reported without being applied.
+        dry_run: bool,
+    },
+}
+
+impl Command {
+    /// Parses command from provided command line arguments (without the binary name):
+    /// - no arguments: serve incoming requests;
+    /// - `migrate [--dry-run]`: apply pending migrations or report them on dry run.
+    pub fn parse<I>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            None => return Ok(Self::Serve),
+            Some("migrate") => {
+                let dry_run = match args.next().as_deref() {
+                    None => false,
+                    Some("--dry-run") => true,
+                    Some(arg) => bail!("unexpected argument `{arg}` of `migrate` command"),
+                };
+                Self::Migrate { dry_run }
+            }
+            Some(command) => bail!("unknown command `{command}`, expected `migrate`"),
+        };
+        if let Some(arg) = args.next() {
+            bail!("unexpected argument `{arg}`");
+        }
+        Ok(command)
+    }
+}
+
+/// Applies pending migrations of the local database,
+/// or only reports changes they would make on dry run.
+pub async fn migrate(client: Client, dry_run: bool) -> Result<()> {
+    let migrator = Migrator::new(client);
+    let reports = if dry_run {
+        migrator.dry_run().await
+    } else {
+        migrator.migrate().await
+    };
+    let reports = reports.with_context(|| "failed to migrate the database")?;
+
+    if reports.is_empty() {
+        tracing::info!("database is up to date, no migrations are pending");
+    }
+    for report in reports {
+        let MigrationReport {
+            name,
+            changes,
+            conflicts,
+        } = report;
+        match dry_run {
+            true => tracing::info!(name, changes, "migration is pending"),
+            false => tracing::info!(name, changes, "applied migration"),
+        }
+        for conflict in conflicts {
+            tracing::warn!(name, conflict, "migration conflict must be resolved first");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+
+use derive_more::Display;
+use fp_user_domain::model::{
+    Bio as DomainBio, BioError, OptionBioFilters as DomainOptionBioFilters,
+};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::filter::{Equal, In, NotEqual, NotIn, Regex};
+
+/// Serializable [bio](DomainBio) of the user.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Bio(String);
+
+impl From<DomainBio> for Bio {
+    fn from(bio: DomainBio) -> Self {
+        let bio = bio.into_inner();
+        Self(bio)
+    }
+}
+
+impl TryFrom<Bio> for DomainBio {
+    type Error = BioError;
+
+    fn try_from(bio: Bio) -> Result<Self, Self::Error> {
+        let Bio(bio) = bio;
+        DomainBio::new(bio)
+    }
+}
+
+/// Filters for optional user bio of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionBioFilters {
+    /// Equality user bio filter.
+    pub eq: Option<Equal<Option<Bio>>>,
+    /// Inequality user bio filter.
+    pub ne: Option<NotEqual<Option<Bio>>>,
+    /// In user bio filter.
+    pub r#in: Option<In<Vec<Option<Bio>>>>,
+    /// Not in user bio filter.
+    pub nin: Option<NotIn<Vec<Option<Bio>>>>,
+    /// Regex user bio filter.
+    pub regex: Option<Regex<String>>,
+}
+
+impl From<DomainOptionBioFilters<'_>> for OptionBioFilters {
+    fn from(filters: DomainOptionBioFilters<'_>) -> Self {
+        let DomainOptionBioFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = filters;
+        Self {
+            eq: eq.map(|bio| Equal(bio.0.into_owned().map(Into::into))),
+            ne: ne.map(|bio| NotEqual(bio.0.into_owned().map(Into::into))),
+            r#in: r#in.map(|r#in| {
+                let iter = r#in.0.iter();
+                In(iter.cloned().map(|bio| bio.map(Into::into)).collect())
+            }),
+            nin: nin.map(|r#in| {
+                let iter = r#in.0.iter();
+                NotIn(iter.cloned().map(|bio| bio.map(Into::into)).collect())
+            }),
+            regex: regex.map(|regex| Regex(regex.0.into_owned())),
+        }
+    }
+}
+
+impl TryFrom<OptionBioFilters> for DomainOptionBioFilters<'_> {
+    type Error = BioError;
+
+    fn try_from(filters: OptionBioFilters) -> Result<Self, Self::Error> {
+        let OptionBioFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = filters;
+        let eq = eq
+            .map(|Equal(bio)| {
+                let bio = bio.map(TryInto::try_into).transpose()?;
+                let filter = Equal(Cow::Owned(bio)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let ne = ne
+            .map(|NotEqual(bio)| {
+                let bio = bio.map(TryInto::try_into).transpose()?;
+                let filter = NotEqual(Cow::Owned(bio)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let r#in = r#in
+            .map(|In(bios)| {
+                let bios = bios
+                    .into_iter()
+                    .map(|bio| bio.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = In(bios.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let nin = nin
+            .map(|NotIn(bios)| {
+                let bios = bios
+                    .into_iter()
+                    .map(|bio| bio.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = NotIn(bios.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let regex = regex.map(|Regex(regex)| Regex(Cow::Owned(regex)).into());
+        let filters = Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        };
+        Ok(filters)
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+
+use fp_user_data::repository::LocalUserRecordFormat;
+use fp_user_domain::use_case::{
+    ImportFailure as DomainImportFailure, ImportRecordError as DomainImportRecordError,
+    ImportReport as DomainImportReport,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{Avatar, DisplayName, Email, Name, User, ValidationError};
+
+/// Format of the user records which are imported or exported in bulk.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordFormat {
+    /// Each record is a JSON object on its own line.
+    JsonLines,
+    /// Each record is a row of comma separated values with a header.
+    Csv,
+}
+
+impl From<RecordFormat> for LocalUserRecordFormat {
+    fn from(format: RecordFormat) -> Self {
+        match format {
+            RecordFormat::JsonLines => Self::JsonLines,
+            RecordFormat::Csv => Self::Csv,
+        }
+    }
+}
+
+/// Serializable [report](DomainImportReport) of the users import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Users which were imported, or would be imported in dry run mode.
+    pub imported: Vec<User>,
+    /// Records which were not imported.
+    pub failures: Vec<ImportFailure>,
+}
+
+impl<FormatError> From<DomainImportReport<FormatError>> for ImportReport
+where
+    FormatError: Display,
+{
+    fn from(report: DomainImportReport<FormatError>) -> Self {
+        let DomainImportReport { imported, failures } = report;
+        Self {
+            imported: imported.into_iter().map(Into::into).collect(),
+            failures: failures.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Serializable [record](DomainImportFailure) of the user which was not imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportFailure {
+    /// Number of the record in the content, starting from one.
+    pub record: usize,
+    /// Reason why the record was not imported.
+    pub reason: ImportFailureReason,
+}
+
+impl<FormatError> From<DomainImportFailure<FormatError>> for ImportFailure
+where
+    FormatError: Display,
+{
+    fn from(failure: DomainImportFailure<FormatError>) -> Self {
+        let DomainImportFailure { record, error } = failure;
+        Self {
+            record,
+            reason: error.into(),
+        }
+    }
+}
+
+/// Serializable [reason](DomainImportRecordError) why the record of the user was not imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImportFailureReason {
+    /// Record is malformed and cannot be decoded.
+    Malformed {
+        /// Human readable description of the error.
+        message: String,
+    },
+    /// Record does not meet domain requirements.
+    Invalid(ValidationError),
+    /// User with provided name already exists, was imported earlier
+    /// or the name was released recently.
+    NameAlreadyTaken {
+        /// Name of the user which is taken.
+        name: Name,
+    },
+    /// User with provided email already exists or was imported earlier.
+    EmailAlreadyTaken {
+        /// Email of the user which is taken.
+        email: Email,
+    },
+    /// Display name of the user could be confused with display name
+    /// of administrator or moderator.
+    ConfusableDisplayName {
+        /// Display name of the user which is rejected.
+        display_name: DisplayName,
+    },
+    /// Domain of provided email does not conform to the email policy.
+    EmailNotAllowed {
+        /// Email of the user which is rejected.
+        email: Email,
+        /// Human readable description of the error.
+        message: String,
+    },
+    /// Avatar of the user does not conform to the avatar policy.
+    AvatarNotAllowed {
+        /// Avatar of the user which is rejected.
+        avatar: Avatar,
+        /// Human readable description of the error.
+        message: String,
+    },
+}
+
+impl<FormatError> From<DomainImportRecordError<FormatError>> for ImportFailureReason
+where
+    FormatError: Display,
+{
+    fn from(error: DomainImportRecordError<FormatError>) -> Self {
+        match error {
+            DomainImportRecordError::Malformed(error) => Self::Malformed {
+                message: error.to_string(),
+            },
+            DomainImportRecordError::Invalid(error) => Self::Invalid(error.into()),
+            DomainImportRecordError::NameAlreadyTaken(name) => {
+                Self::NameAlreadyTaken { name: name.into() }
+            }
+            DomainImportRecordError::EmailAlreadyTaken(email) => Self::EmailAlreadyTaken {
+                email: email.into(),
+            },
+            DomainImportRecordError::ConfusableDisplayName(display_name) => {
+                Self::ConfusableDisplayName {
+                    display_name: display_name.into(),
+                }
+            }
+            DomainImportRecordError::EmailNotAllowed(email, error) => Self::EmailNotAllowed {
+                email: email.into(),
+                message: error.to_string(),
+            },
+            DomainImportRecordError::AvatarNotAllowed(avatar, error) => Self::AvatarNotAllowed {
+                avatar: avatar.into(),
+                message: error.to_string(),
+            },
+        }
+    }
+}
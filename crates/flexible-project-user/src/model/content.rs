@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+
+/// Serializable binary content of the request, such as uploaded image or imported records.
+///
+/// Content is encoded as Base64 string, and only its length is shown in debug output.
+#[serde_as]
+#[derive(Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Content(#[serde_as(as = "Base64")] Vec<u8>);
+
+impl Content {
+    /// Extracts bytes of the content.
+    pub fn into_inner(self) -> Vec<u8> {
+        let Self(content) = self;
+        content
+    }
+}
+
+impl From<Vec<u8>> for Content {
+    fn from(content: Vec<u8>) -> Self {
+        Self(content)
+    }
+}
+
+impl Debug for Content {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(content) = self;
+        f.debug_struct("Content")
+            .field("len", &content.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt::Display;
+
+use fp_user_domain::model::{
+    AvatarError, BioError, DisplayNameError, EmailError, LocaleError, NameError, PasswordError,
+    PronounsError, TimeZoneError, UserRecordError,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use super::TryFromUserDataError;
+
+/// Serializable error which is returned when user input does not meet domain requirements.
+///
+/// Contains machine readable [reason](ValidationReason) of the error,
+/// so clients could explain to the user why the input was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    /// Field of the input which does not meet requirements.
+    pub field: ValidationField,
+    /// Human readable description of the error.
+    pub message: String,
+    /// Machine readable reason of the error.
+    pub reason: ValidationReason,
+}
+
+impl ValidationError {
+    /// Creates new validation error of provided field from the error of the domain.
+    pub fn new<E>(field: ValidationField, error: E) -> Self
+    where
+        E: Display + Into<ValidationReason>,
+    {
+        Self {
+            field,
+            message: error.to_string(),
+            reason: error.into(),
+        }
+    }
+}
+
+/// Field of the user input which could be rejected by the domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationField {
+    /// Name of the user.
+    Name,
+    /// Display name of the user.
+    DisplayName,
+    /// Email of the user.
+    Email,
+    /// Avatar URL of the user.
+    Avatar,
+    /// Password of the user.
+    Password,
+    /// Bio of the user.
+    Bio,
+    /// Preferred locale of the user.
+    Locale,
+    /// Time zone of the user.
+    TimeZone,
+    /// Pronouns of the user.
+    Pronouns,
+}
+
+/// Reason why user input does not meet domain requirements.
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ValidationReason {
+    /// Input does not meet requirements which have no detailed reason.
+    Invalid,
+    /// Input has fewer characters than required.
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Input has more characters than allowed.
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Input contains character which is not allowed.
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+    /// Input starts with a separator.
+    LeadingSeparator {
+        /// Position of the separator, if it is not the start of the input.
+        position: Option<usize>,
+    },
+    /// Input ends with a separator.
+    TrailingSeparator {
+        /// Position of the separator, if it is not the end of the input.
+        position: Option<usize>,
+    },
+    /// Input contains separators next to each other.
+    ConsecutiveSeparators {
+        /// Position of the second separator in a row.
+        position: usize,
+    },
+    /// Input does not contain any letter.
+    NoLetter,
+    /// Email does not contain `@` character.
+    MissingSeparator,
+    /// Local part of email is empty.
+    EmptyLocalPart,
+    /// Local part of email has more characters than allowed.
+    LocalPartTooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Domain of email is empty.
+    EmptyDomain,
+    /// Domain of email has more characters than allowed.
+    DomainTooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Scheme of URL is not allowed.
+    SchemeNotAllowed,
+    /// URL has no host.
+    NoHost,
+    /// Host of URL is not allowed.
+    HostNotAllowed,
+    /// URL points to loopback, private or link-local host.
+    PrivateHost,
+    /// Language subtag of locale is invalid.
+    InvalidLanguage,
+    /// Subtag of locale after language subtag is invalid.
+    InvalidSubtag {
+        /// Position of the first character of the subtag.
+        position: usize,
+    },
+    /// Time zone is not known.
+    UnknownTimeZone,
+}
+
+impl From<NameError> for ValidationReason {
+    fn from(error: NameError) -> Self {
+        match error {
+            NameError::TooShort { min, actual } => Self::TooShort { min, actual },
+            NameError::TooLong { max, actual } => Self::TooLong { max, actual },
+            NameError::InvalidCharacter { ch, position } => Self::InvalidCharacter { ch, position },
+            NameError::LeadingSeparator => Self::LeadingSeparator { position: None },
+            NameError::TrailingSeparator => Self::TrailingSeparator { position: None },
+            NameError::ConsecutiveSeparators { position } => {
+                Self::ConsecutiveSeparators { position }
+            }
+        }
+    }
+}
+
+impl From<DisplayNameError> for ValidationReason {
+    fn from(error: DisplayNameError) -> Self {
+        match error {
+            DisplayNameError::TooShort { min, actual } => Self::TooShort { min, actual },
+            DisplayNameError::TooLong { max, actual } => Self::TooLong { max, actual },
+            DisplayNameError::InvalidCharacter { ch, position } => {
+                Self::InvalidCharacter { ch, position }
+            }
+            DisplayNameError::NoLetter => Self::NoLetter,
+        }
+    }
+}
+
+impl From<EmailError> for ValidationReason {
+    fn from(error: EmailError) -> Self {
+        match error {
+            EmailError::MissingSeparator => Self::MissingSeparator,
+            EmailError::EmptyLocalPart => Self::EmptyLocalPart,
+            EmailError::LocalPartTooLong { max, actual } => Self::LocalPartTooLong { max, actual },
+            EmailError::EmptyDomain => Self::EmptyDomain,
+            EmailError::DomainTooLong { max, actual } => Self::DomainTooLong { max, actual },
+            EmailError::InvalidCharacter { ch, position } => {
+                Self::InvalidCharacter { ch, position }
+            }
+            EmailError::LeadingSeparator { position } => Self::LeadingSeparator {
+                position: Some(position),
+            },
+            EmailError::TrailingSeparator { position } => Self::TrailingSeparator {
+                position: Some(position),
+            },
+            EmailError::ConsecutiveSeparators { position } => {
+                Self::ConsecutiveSeparators { position }
+            }
+            EmailError::Invalid => Self::Invalid,
+        }
+    }
+}
+
+impl From<AvatarError> for ValidationReason {
+    fn from(error: AvatarError) -> Self {
+        match error {
+            AvatarError::Invalid(_) => Self::Invalid,
+            AvatarError::TooLong { max, actual } => Self::TooLong { max, actual },
+            AvatarError::SchemeNotAllowed => Self::SchemeNotAllowed,
+            AvatarError::NoHost => Self::NoHost,
+            AvatarError::HostNotAllowed => Self::HostNotAllowed,
+            AvatarError::PrivateHost => Self::PrivateHost,
+        }
+    }
+}
+
+impl From<BioError> for ValidationReason {
+    fn from(error: BioError) -> Self {
+        match error {
+            BioError::TooShort { min, actual } => Self::TooShort { min, actual },
+            BioError::TooLong { max, actual } => Self::TooLong { max, actual },
+            BioError::InvalidCharacter { ch, position } => Self::InvalidCharacter { ch, position },
+        }
+    }
+}
+
+impl From<LocaleError> for ValidationReason {
+    fn from(error: LocaleError) -> Self {
+        match error {
+            LocaleError::TooLong { max, actual } => Self::TooLong { max, actual },
+            LocaleError::InvalidLanguage => Self::InvalidLanguage,
+            LocaleError::InvalidSubtag { position } => Self::InvalidSubtag { position },
+        }
+    }
+}
+
+impl From<TimeZoneError> for ValidationReason {
+    fn from(error: TimeZoneError) -> Self {
+        match error {
+            TimeZoneError::Unknown => Self::UnknownTimeZone,
+        }
+    }
+}
+
+impl From<PronounsError> for ValidationReason {
+    fn from(error: PronounsError) -> Self {
+        match error {
+            PronounsError::TooShort { min, actual } => Self::TooShort { min, actual },
+            PronounsError::TooLong { max, actual } => Self::TooLong { max, actual },
+            PronounsError::InvalidCharacter { ch, position } => {
+                Self::InvalidCharacter { ch, position }
+            }
+        }
+    }
+}
+
+impl From<PasswordError> for ValidationReason {
+    fn from(error: PasswordError) -> Self {
+        match error {
+            PasswordError::Invalid => Self::Invalid,
+        }
+    }
+}
+
+impl From<TryFromUserDataError> for ValidationError {
+    fn from(error: TryFromUserDataError) -> Self {
+        let message = error.to_string();
+        let (field, reason) = match error {
+            TryFromUserDataError::Name(error) => (ValidationField::Name, error.into()),
+            TryFromUserDataError::DisplayName(error) => {
+                (ValidationField::DisplayName, error.into())
+            }
+            TryFromUserDataError::Email(error) => (ValidationField::Email, error.into()),
+            TryFromUserDataError::Avatar(error) => (ValidationField::Avatar, error.into()),
+            TryFromUserDataError::Password(error) => (ValidationField::Password, error.into()),
+            TryFromUserDataError::Bio(error) => (ValidationField::Bio, error.into()),
+            TryFromUserDataError::Locale(error) => (ValidationField::Locale, error.into()),
+            TryFromUserDataError::TimeZone(error) => (ValidationField::TimeZone, error.into()),
+            TryFromUserDataError::Pronouns(error) => (ValidationField::Pronouns, error.into()),
+        };
+        Self {
+            field,
+            message,
+            reason,
+        }
+    }
+}
+
+impl From<UserRecordError> for ValidationError {
+    fn from(error: UserRecordError) -> Self {
+        let message = error.to_string();
+        let (field, reason) = match error {
+            UserRecordError::Name(error) => (ValidationField::Name, error.into()),
+            UserRecordError::DisplayName(error) => (ValidationField::DisplayName, error.into()),
+            UserRecordError::Email(error) => (ValidationField::Email, error.into()),
+            UserRecordError::Avatar(error) => (ValidationField::Avatar, error.into()),
+            UserRecordError::Bio(error) => (ValidationField::Bio, error.into()),
+            UserRecordError::Locale(error) => (ValidationField::Locale, error.into()),
+            UserRecordError::TimeZone(error) => (ValidationField::TimeZone, error.into()),
+            UserRecordError::Pronouns(error) => (ValidationField::Pronouns, error.into()),
+        };
+        Self {
+            field,
+            message,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+
+use derive_more::Display;
+use fp_user_domain::model::{
+    Locale as DomainLocale, LocaleError, OptionLocaleFilters as DomainOptionLocaleFilters,
+};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::filter::{Equal, In, NotEqual, NotIn};
+
+/// Serializable [locale](DomainLocale) of the user.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Locale(String);
+
+impl From<DomainLocale> for Locale {
+    fn from(locale: DomainLocale) -> Self {
+        let locale = locale.into_inner();
+        Self(locale)
+    }
+}
+
+impl TryFrom<Locale> for DomainLocale {
+    type Error = LocaleError;
+
+    fn try_from(locale: Locale) -> Result<Self, Self::Error> {
+        let Locale(locale) = locale;
+        DomainLocale::new(locale)
+    }
+}
+
+/// Filters for optional user locale of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionLocaleFilters {
+    /// Equality user locale filter.
+    pub eq: Option<Equal<Option<Locale>>>,
+    /// Inequality user locale filter.
+    pub ne: Option<NotEqual<Option<Locale>>>,
+    /// In user locale filter.
+    pub r#in: Option<In<Vec<Option<Locale>>>>,
+    /// Not in user locale filter.
+    pub nin: Option<NotIn<Vec<Option<Locale>>>>,
+}
+
+impl From<DomainOptionLocaleFilters<'_>> for OptionLocaleFilters {
+    fn from(filters: DomainOptionLocaleFilters<'_>) -> Self {
+        let DomainOptionLocaleFilters { eq, ne, r#in, nin } = filters;
+        Self {
+            eq: eq.map(|locale| Equal(locale.0.into_owned().map(Into::into))),
+            ne: ne.map(|locale| NotEqual(locale.0.into_owned().map(Into::into))),
+            r#in: r#in.map(|r#in| {
+                let iter = r#in.0.iter();
+                In(iter.cloned().map(|locale| locale.map(Into::into)).collect())
+            }),
+            nin: nin.map(|r#in| {
+                let iter = r#in.0.iter();
+                NotIn(iter.cloned().map(|locale| locale.map(Into::into)).collect())
+            }),
+        }
+    }
+}
+
+impl TryFrom<OptionLocaleFilters> for DomainOptionLocaleFilters<'_> {
+    type Error = LocaleError;
+
+    fn try_from(filters: OptionLocaleFilters) -> Result<Self, Self::Error> {
+        let OptionLocaleFilters { eq, ne, r#in, nin } = filters;
+        let eq = eq
+            .map(|Equal(locale)| {
+                let locale = locale.map(TryInto::try_into).transpose()?;
+                let filter = Equal(Cow::Owned(locale)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let ne = ne
+            .map(|NotEqual(locale)| {
+                let locale = locale.map(TryInto::try_into).transpose()?;
+                let filter = NotEqual(Cow::Owned(locale)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let r#in = r#in
+            .map(|In(locales)| {
+                let locales = locales
+                    .into_iter()
+                    .map(|locale| locale.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = In(locales.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let nin = nin
+            .map(|NotIn(locales)| {
+                let locales = locales
+                    .into_iter()
+                    .map(|locale| locale.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = NotIn(locales.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let filters = Self { eq, ne, r#in, nin };
+        Ok(filters)
+    }
+}
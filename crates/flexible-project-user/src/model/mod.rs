@@ -2,20 +2,42 @@
 
 pub use self::{
     avatar::{Avatar, AvatarFilters, OptionAvatarFilters},
+    bio::{Bio, OptionBioFilters},
+    bulk::{ImportFailure, ImportFailureReason, ImportReport, RecordFormat},
+    content::Content,
     display_name::{DisplayName, DisplayNameFilters},
     email::{Email, EmailFilters, OptionEmailFilters},
+    error::{ValidationError, ValidationField, ValidationReason},
     id::{ErasedId, ErasedIdFilters},
+    locale::{Locale, OptionLocaleFilters},
     name::{Name, NameFilters},
+    password::{Password, PasswordResetToken},
+    personal_data::{FormerName, PersonalData, PersonalDataEntry, PersonalDataRequest, UserEvent},
+    pronouns::{OptionPronounsFilters, Pronouns},
     role::{Role, RoleFilters},
-    user::{TryFromUserDataError, User, UserData, UserDataFilters, UserFilters},
+    status::{AccountStatus, AccountStatusFilters, AccountStatusKind},
+    time_zone::{OptionTimeZoneFilters, TimeZone},
+    user::{
+        FoundUsers, TryFromUserDataError, User, UserByName, UserData, UserDataFilters, UserFilters,
+    },
 };
 
 pub mod filter;
 
 mod avatar;
+mod bio;
+mod bulk;
+mod content;
 mod display_name;
 mod email;
+mod error;
 mod id;
+mod locale;
 mod name;
+mod password;
+mod personal_data;
+mod pronouns;
 mod role;
+mod status;
+mod time_zone;
 mod user;
@@ -0,0 +1,60 @@
+use std::fmt::Debug;
+
+use fp_user_domain::model::{
+    Password as DomainPassword, PasswordError, PasswordResetToken as DomainPasswordResetToken,
+};
+use serde::{Deserialize, Serialize};
+
+/// Serializable [password](DomainPassword) of the user.
+#[derive(Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Password(String);
+
+impl From<DomainPassword> for Password {
+    fn from(password: DomainPassword) -> Self {
+        let password = password.into_inner();
+        Self(password)
+    }
+}
+
+impl TryFrom<Password> for DomainPassword {
+    type Error = PasswordError;
+
+    fn try_from(password: Password) -> Result<Self, Self::Error> {
+        let Password(password) = password;
+        DomainPassword::new(password)
+    }
+}
+
+impl Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Password").field(&"<redacted>").finish()
+    }
+}
+
+/// Serializable [password reset token](DomainPasswordResetToken) of the user.
+#[derive(Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PasswordResetToken(String);
+
+impl From<DomainPasswordResetToken> for PasswordResetToken {
+    fn from(token: DomainPasswordResetToken) -> Self {
+        let token = token.into_inner();
+        Self(token)
+    }
+}
+
+impl From<PasswordResetToken> for DomainPasswordResetToken {
+    fn from(token: PasswordResetToken) -> Self {
+        let PasswordResetToken(token) = token;
+        DomainPasswordResetToken::new(token)
+    }
+}
+
+impl Debug for PasswordResetToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PasswordResetToken")
+            .field(&"<redacted>")
+            .finish()
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use fp_user_domain::model::{
+    NameChange as DomainNameChange, PersonalData as DomainPersonalData,
+    PersonalDataEntry as DomainPersonalDataEntry, UserEvent as DomainUserEvent,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+
+use super::{ErasedId, Name, User};
+
+/// Serializable [personal data](DomainPersonalData) of the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalData {
+    /// User which personal data was collected.
+    pub user: User,
+    /// Time when password of the user was updated last time, if the user has a password.
+    pub password_updated_at: Option<DateTime<Utc>>,
+    /// Former names of the user, the latest first.
+    pub name_history: Vec<FormerName>,
+    /// Personal data of the user held by other services of the system.
+    pub external: Vec<PersonalDataEntry>,
+}
+
+impl From<DomainPersonalData> for PersonalData {
+    fn from(personal_data: DomainPersonalData) -> Self {
+        let DomainPersonalData {
+            user,
+            password_updated_at,
+            name_history,
+            external,
+        } = personal_data;
+        Self {
+            user: user.into(),
+            password_updated_at,
+            name_history: name_history.into_iter().map(Into::into).collect(),
+            external: external.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Serializable former name of the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormerName {
+    /// Name of the user before the change.
+    pub name: Name,
+    /// Time when the name was changed.
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<DomainNameChange> for FormerName {
+    fn from(change: DomainNameChange) -> Self {
+        let DomainNameChange {
+            name, changed_at, ..
+        } = change;
+        Self {
+            name: name.into(),
+            changed_at,
+        }
+    }
+}
+
+/// Serializable [personal data entry](DomainPersonalDataEntry) of other service.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalDataEntry {
+    /// Name of the entry in the archive.
+    pub name: String,
+    /// Content of the entry.
+    #[serde_as(as = "Base64")]
+    pub content: Vec<u8>,
+}
+
+impl From<DomainPersonalDataEntry> for PersonalDataEntry {
+    fn from(entry: DomainPersonalDataEntry) -> Self {
+        let DomainPersonalDataEntry { name, content } = entry;
+        Self { name, content }
+    }
+}
+
+impl From<PersonalDataEntry> for DomainPersonalDataEntry {
+    fn from(entry: PersonalDataEntry) -> Self {
+        let PersonalDataEntry { name, content } = entry;
+        Self { name, content }
+    }
+}
+
+/// Serializable request of personal data of the user which is sent to other services.
+///
+/// Other services reply with the list of [personal data entries](PersonalDataEntry).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalDataRequest {
+    /// Identifier of the user which personal data is collected.
+    pub user_id: ErasedId,
+}
+
+/// Serializable [event](DomainUserEvent) which is consumed by other services of the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserEvent {
+    /// User was erased from the system.
+    Erased {
+        /// Identifier of the erased user.
+        id: ErasedId,
+        /// Time when the user was erased.
+        erased_at: DateTime<Utc>,
+    },
+}
+
+impl From<DomainUserEvent> for UserEvent {
+    fn from(event: DomainUserEvent) -> Self {
+        match event {
+            DomainUserEvent::Erased { id, erased_at } => Self::Erased {
+                id: id.erase().into(),
+                erased_at,
+            },
+        }
+    }
+}
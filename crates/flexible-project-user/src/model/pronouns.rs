@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+
+use derive_more::Display;
+use fp_user_domain::model::{
+    OptionPronounsFilters as DomainOptionPronounsFilters, Pronouns as DomainPronouns, PronounsError,
+};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::filter::{Equal, In, NotEqual, NotIn, Regex};
+
+/// Serializable [pronouns](DomainPronouns) of the user.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pronouns(String);
+
+impl From<DomainPronouns> for Pronouns {
+    fn from(pronouns: DomainPronouns) -> Self {
+        let pronouns = pronouns.into_inner();
+        Self(pronouns)
+    }
+}
+
+impl TryFrom<Pronouns> for DomainPronouns {
+    type Error = PronounsError;
+
+    fn try_from(pronouns: Pronouns) -> Result<Self, Self::Error> {
+        let Pronouns(pronouns) = pronouns;
+        DomainPronouns::new(pronouns)
+    }
+}
+
+/// Filters for optional user pronouns of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionPronounsFilters {
+    /// Equality user pronouns filter.
+    pub eq: Option<Equal<Option<Pronouns>>>,
+    /// Inequality user pronouns filter.
+    pub ne: Option<NotEqual<Option<Pronouns>>>,
+    /// In user pronouns filter.
+    pub r#in: Option<In<Vec<Option<Pronouns>>>>,
+    /// Not in user pronouns filter.
+    pub nin: Option<NotIn<Vec<Option<Pronouns>>>>,
+    /// Regex user pronouns filter.
+    pub regex: Option<Regex<String>>,
+}
+
+impl From<DomainOptionPronounsFilters<'_>> for OptionPronounsFilters {
+    fn from(filters: DomainOptionPronounsFilters<'_>) -> Self {
+        let DomainOptionPronounsFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = filters;
+        Self {
+            eq: eq.map(|pronouns| Equal(pronouns.0.into_owned().map(Into::into))),
+            ne: ne.map(|pronouns| NotEqual(pronouns.0.into_owned().map(Into::into))),
+            r#in: r#in.map(|r#in| {
+                let iter = r#in.0.iter();
+                In(iter
+                    .cloned()
+                    .map(|pronouns| pronouns.map(Into::into))
+                    .collect())
+            }),
+            nin: nin.map(|r#in| {
+                let iter = r#in.0.iter();
+                NotIn(
+                    iter.cloned()
+                        .map(|pronouns| pronouns.map(Into::into))
+                        .collect(),
+                )
+            }),
+            regex: regex.map(|regex| Regex(regex.0.into_owned())),
+        }
+    }
+}
+
+impl TryFrom<OptionPronounsFilters> for DomainOptionPronounsFilters<'_> {
+    type Error = PronounsError;
+
+    fn try_from(filters: OptionPronounsFilters) -> Result<Self, Self::Error> {
+        let OptionPronounsFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        } = filters;
+        let eq = eq
+            .map(|Equal(pronouns)| {
+                let pronouns = pronouns.map(TryInto::try_into).transpose()?;
+                let filter = Equal(Cow::Owned(pronouns)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let ne = ne
+            .map(|NotEqual(pronouns)| {
+                let pronouns = pronouns.map(TryInto::try_into).transpose()?;
+                let filter = NotEqual(Cow::Owned(pronouns)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let r#in = r#in
+            .map(|In(pronouns_list)| {
+                let pronouns_list = pronouns_list
+                    .into_iter()
+                    .map(|pronouns| pronouns.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = In(pronouns_list.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let nin = nin
+            .map(|NotIn(pronouns_list)| {
+                let pronouns_list = pronouns_list
+                    .into_iter()
+                    .map(|pronouns| pronouns.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = NotIn(pronouns_list.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let regex = regex.map(|Regex(regex)| Regex(Cow::Owned(regex)).into());
+        let filters = Self {
+            eq,
+            ne,
+            r#in,
+            nin,
+            regex,
+        };
+        Ok(filters)
+    }
+}
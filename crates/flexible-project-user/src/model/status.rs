@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use fp_user_domain::model::{
+    AccountStatus as DomainAccountStatus, AccountStatusFilters as DomainAccountStatusFilters,
+    AccountStatusKind as DomainAccountStatusKind,
+};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::filter::{Equal, In, NotEqual, NotIn};
+
+/// Serializable [account status](DomainAccountStatus) of the user.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AccountStatus {
+    /// Account is active and has no restrictions.
+    #[default]
+    Active,
+    /// Account is suspended until some point in time.
+    Suspended {
+        /// Time when suspension ends.
+        until: DateTime<Utc>,
+        /// Reason of the suspension.
+        reason: String,
+    },
+    /// Account is banned permanently.
+    Banned {
+        /// Reason of the ban.
+        reason: String,
+    },
+}
+
+impl From<DomainAccountStatus> for AccountStatus {
+    fn from(status: DomainAccountStatus) -> Self {
+        match status {
+            DomainAccountStatus::Active => Self::Active,
+            DomainAccountStatus::Suspended { until, reason } => Self::Suspended { until, reason },
+            DomainAccountStatus::Banned { reason } => Self::Banned { reason },
+        }
+    }
+}
+
+impl From<AccountStatus> for DomainAccountStatus {
+    fn from(status: AccountStatus) -> Self {
+        match status {
+            AccountStatus::Active => Self::Active,
+            AccountStatus::Suspended { until, reason } => Self::Suspended { until, reason },
+            AccountStatus::Banned { reason } => Self::Banned { reason },
+        }
+    }
+}
+
+/// Serializable [account status kind](DomainAccountStatusKind) of the user.
+#[derive(
+    Debug, Display, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum AccountStatusKind {
+    /// Account is active.
+    Active,
+    /// Account is suspended.
+    Suspended,
+    /// Account is banned.
+    Banned,
+}
+
+impl From<DomainAccountStatusKind> for AccountStatusKind {
+    fn from(kind: DomainAccountStatusKind) -> Self {
+        match kind {
+            DomainAccountStatusKind::Active => Self::Active,
+            DomainAccountStatusKind::Suspended => Self::Suspended,
+            DomainAccountStatusKind::Banned => Self::Banned,
+        }
+    }
+}
+
+impl From<AccountStatusKind> for DomainAccountStatusKind {
+    fn from(kind: AccountStatusKind) -> Self {
+        match kind {
+            AccountStatusKind::Active => Self::Active,
+            AccountStatusKind::Suspended => Self::Suspended,
+            AccountStatusKind::Banned => Self::Banned,
+        }
+    }
+}
+
+/// Filters for user account status of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct AccountStatusFilters {
+    /// Equality account status kind filter.
+    pub eq: Option<Equal<AccountStatusKind>>,
+    /// Inequality account status kind filter.
+    pub ne: Option<NotEqual<AccountStatusKind>>,
+    /// In account status kind filter.
+    pub r#in: Option<In<Vec<AccountStatusKind>>>,
+    /// Not in account status kind filter.
+    pub nin: Option<NotIn<Vec<AccountStatusKind>>>,
+}
+
+impl From<DomainAccountStatusFilters<'_>> for AccountStatusFilters {
+    fn from(filters: DomainAccountStatusFilters<'_>) -> Self {
+        let DomainAccountStatusFilters {
+            eq,
+            ne,
+            r#in,
+            nin,
+            at: _,
+        } = filters;
+        Self {
+            eq: eq.map(|kind| Equal(kind.0.into_owned().into())),
+            ne: ne.map(|kind| NotEqual(kind.0.into_owned().into())),
+            r#in: r#in.map(|r#in| In(r#in.0.iter().cloned().map(Into::into).collect())),
+            nin: nin.map(|nin| NotIn(nin.0.iter().cloned().map(Into::into).collect())),
+        }
+    }
+}
+
+impl From<AccountStatusFilters> for DomainAccountStatusFilters<'_> {
+    fn from(filters: AccountStatusFilters) -> Self {
+        let AccountStatusFilters { eq, ne, r#in, nin } = filters;
+        Self {
+            eq: eq.map(|Equal(kind)| Equal(Cow::Owned(kind.into())).into()),
+            ne: ne.map(|NotEqual(kind)| NotEqual(Cow::Owned(kind.into())).into()),
+            r#in: r#in.map(|In(kinds)| {
+                let kinds: Vec<_> = kinds.into_iter().map(Into::into).collect();
+                In(kinds.into()).into()
+            }),
+            nin: nin.map(|NotIn(kinds)| {
+                let kinds: Vec<_> = kinds.into_iter().map(Into::into).collect();
+                NotIn(kinds.into()).into()
+            }),
+            at: None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+
+use derive_more::Display;
+use fp_user_domain::model::{
+    OptionTimeZoneFilters as DomainOptionTimeZoneFilters, TimeZone as DomainTimeZone, TimeZoneError,
+};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::filter::{Equal, In, NotEqual, NotIn};
+
+/// Serializable [time zone](DomainTimeZone) of the user.
+#[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TimeZone(String);
+
+impl From<DomainTimeZone> for TimeZone {
+    fn from(time_zone: DomainTimeZone) -> Self {
+        let time_zone = time_zone.into_inner();
+        Self(time_zone)
+    }
+}
+
+impl TryFrom<TimeZone> for DomainTimeZone {
+    type Error = TimeZoneError;
+
+    fn try_from(time_zone: TimeZone) -> Result<Self, Self::Error> {
+        let TimeZone(time_zone) = time_zone;
+        DomainTimeZone::new(time_zone)
+    }
+}
+
+/// Filters for optional user time zone of the backend.
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(default, setter(into, strip_option)))]
+pub struct OptionTimeZoneFilters {
+    /// Equality user time zone filter.
+    pub eq: Option<Equal<Option<TimeZone>>>,
+    /// Inequality user time zone filter.
+    pub ne: Option<NotEqual<Option<TimeZone>>>,
+    /// In user time zone filter.
+    pub r#in: Option<In<Vec<Option<TimeZone>>>>,
+    /// Not in user time zone filter.
+    pub nin: Option<NotIn<Vec<Option<TimeZone>>>>,
+}
+
+impl From<DomainOptionTimeZoneFilters<'_>> for OptionTimeZoneFilters {
+    fn from(filters: DomainOptionTimeZoneFilters<'_>) -> Self {
+        let DomainOptionTimeZoneFilters { eq, ne, r#in, nin } = filters;
+        Self {
+            eq: eq.map(|time_zone| Equal(time_zone.0.into_owned().map(Into::into))),
+            ne: ne.map(|time_zone| NotEqual(time_zone.0.into_owned().map(Into::into))),
+            r#in: r#in.map(|r#in| {
+                let iter = r#in.0.iter();
+                In(iter
+                    .cloned()
+                    .map(|time_zone| time_zone.map(Into::into))
+                    .collect())
+            }),
+            nin: nin.map(|r#in| {
+                let iter = r#in.0.iter();
+                NotIn(
+                    iter.cloned()
+                        .map(|time_zone| time_zone.map(Into::into))
+                        .collect(),
+                )
+            }),
+        }
+    }
+}
+
+impl TryFrom<OptionTimeZoneFilters> for DomainOptionTimeZoneFilters<'_> {
+    type Error = TimeZoneError;
+
+    fn try_from(filters: OptionTimeZoneFilters) -> Result<Self, Self::Error> {
+        let OptionTimeZoneFilters { eq, ne, r#in, nin } = filters;
+        let eq = eq
+            .map(|Equal(time_zone)| {
+                let time_zone = time_zone.map(TryInto::try_into).transpose()?;
+                let filter = Equal(Cow::Owned(time_zone)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let ne = ne
+            .map(|NotEqual(time_zone)| {
+                let time_zone = time_zone.map(TryInto::try_into).transpose()?;
+                let filter = NotEqual(Cow::Owned(time_zone)).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let r#in = r#in
+            .map(|In(time_zones)| {
+                let time_zones = time_zones
+                    .into_iter()
+                    .map(|time_zone| time_zone.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = In(time_zones.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let nin = nin
+            .map(|NotIn(time_zones)| {
+                let time_zones = time_zones
+                    .into_iter()
+                    .map(|time_zone| time_zone.map(TryInto::try_into).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filter = NotIn(time_zones.into()).into();
+                Ok(filter)
+            })
+            .transpose()?;
+        let filters = Self { eq, ne, r#in, nin };
+        Ok(filters)
+    }
+}
@@ -2,17 +2,22 @@ use std::hash::{Hash, Hasher};
 
 use derive_more::{Display, Error, From};
 use fp_core::id::{ErasedId as CoreErasedId, ErasedIdFilters as CoreErasedIdFilters};
-use fp_user_domain::model::{
-    AvatarError, DisplayNameError, EmailError, NameError, User as DomainUser,
-    UserData as DomainUserData, UserDataFilters as DomainUserDataFilters,
-    UserFilters as DomainUserFilters,
+use fp_user_domain::{
+    model::{
+        AvatarError, BioError, DisplayNameError, EmailError, LocaleError, NameError, PasswordError,
+        PronounsError, TimeZoneError, User as DomainUser, UserData as DomainUserData,
+        UserDataFilters as DomainUserDataFilters, UserFilters as DomainUserFilters, VisibleUser,
+    },
+    use_case::{FoundUsers as DomainFoundUsers, UserByName as DomainUserByName},
 };
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use super::{
-    Avatar, DisplayName, DisplayNameFilters, Email, ErasedId, ErasedIdFilters, Name, NameFilters,
-    OptionAvatarFilters, OptionEmailFilters, Role, RoleFilters,
+    AccountStatus, AccountStatusFilters, Avatar, Bio, DisplayName, DisplayNameFilters, Email,
+    ErasedId, ErasedIdFilters, Locale, Name, NameFilters, OptionAvatarFilters, OptionBioFilters,
+    OptionEmailFilters, OptionLocaleFilters, OptionPronounsFilters, OptionTimeZoneFilters,
+    Pronouns, Role, RoleFilters, TimeZone,
 };
 
 /// Serializable [user](DomainUser) of the system.
@@ -49,6 +54,67 @@ impl From<DomainUser> for User {
     }
 }
 
+impl From<VisibleUser> for User {
+    fn from(user: VisibleUser) -> Self {
+        match user {
+            VisibleUser::Private(user) => user.into(),
+            VisibleUser::Public(user) => {
+                let mut user = Self::from(user);
+                user.data.email = None;
+                user.data.role = None;
+                user.data.status = None;
+                user
+            }
+        }
+    }
+}
+
+/// Serializable [users](DomainFoundUsers) which were found by their identifiers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FoundUsers {
+    /// Found users in the order of the request.
+    pub users: Vec<User>,
+    /// Requested identifiers by which no user was found, in the order of the request.
+    pub missing: Vec<ErasedId>,
+}
+
+impl From<DomainFoundUsers> for FoundUsers {
+    fn from(found: DomainFoundUsers) -> Self {
+        let DomainFoundUsers { users, missing } = found;
+        Self {
+            users: users.into_values().map(Into::into).collect(),
+            missing: missing.into_iter().map(|id| id.erase().into()).collect(),
+        }
+    }
+}
+
+/// Serializable [user](DomainUserByName) which was found by its current or former name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum UserByName {
+    /// User was found by its current name.
+    Current(User),
+    /// User was found by its recent former name,
+    /// so the client should be redirected to the current name of the user.
+    Redirect {
+        /// Former name of the user which was used to find it.
+        former_name: Name,
+        /// User which owned the former name.
+        user: User,
+    },
+}
+
+impl From<DomainUserByName> for UserByName {
+    fn from(user: DomainUserByName) -> Self {
+        match user {
+            DomainUserByName::Current(user) => Self::Current(user.into()),
+            DomainUserByName::Redirect { former_name, user } => Self::Redirect {
+                former_name: former_name.into(),
+                user: user.into(),
+            },
+        }
+    }
+}
+
 impl TryFrom<User> for DomainUser {
     type Error = TryFromUserDataError;
 
@@ -102,12 +168,28 @@ pub struct UserData {
     pub name: Name,
     /// Display name of the user.
     pub display_name: DisplayName,
-    /// Role of the user.
-    pub role: Role,
-    /// Unique email of the user, if present.
+    /// Role of the user, if visible to the reader.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    /// Unique email of the user, if present and visible to the reader.
     pub email: Option<Email>,
     /// Avatar URL of the user, if present.
     pub avatar: Option<Avatar>,
+    /// Status of the user account, if visible to the reader.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<AccountStatus>,
+    /// Short Markdown biography of the user, if present.
+    #[serde(default)]
+    pub bio: Option<Bio>,
+    /// Preferred locale of the user, if present.
+    #[serde(default)]
+    pub locale: Option<Locale>,
+    /// Time zone of the user, if present.
+    #[serde(default)]
+    pub time_zone: Option<TimeZone>,
+    /// Pronouns of the user, if present.
+    #[serde(default)]
+    pub pronouns: Option<Pronouns>,
 }
 
 impl From<DomainUserData> for UserData {
@@ -118,13 +200,23 @@ impl From<DomainUserData> for UserData {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = data;
         Self {
             name: name.into(),
             display_name: display_name.into(),
-            role: role.into(),
+            role: Some(role.into()),
             email: email.map(Into::into),
             avatar: avatar.map(Into::into),
+            status: Some(status.into()),
+            bio: bio.map(Into::into),
+            locale: locale.map(Into::into),
+            time_zone: time_zone.map(Into::into),
+            pronouns: pronouns.map(Into::into),
         }
     }
 }
@@ -139,13 +231,23 @@ impl TryFrom<UserData> for DomainUserData {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = data;
         let data = Self {
             name: name.try_into()?,
             display_name: display_name.try_into()?,
-            role: role.into(),
+            role: role.map(Into::into).unwrap_or_default(),
             email: email.map(TryInto::try_into).transpose()?,
             avatar: avatar.map(TryInto::try_into).transpose()?,
+            status: status.map(Into::into).unwrap_or_default(),
+            bio: bio.map(TryInto::try_into).transpose()?,
+            locale: locale.map(TryInto::try_into).transpose()?,
+            time_zone: time_zone.map(TryInto::try_into).transpose()?,
+            pronouns: pronouns.map(TryInto::try_into).transpose()?,
         };
         Ok(data)
     }
@@ -165,6 +267,16 @@ pub struct UserDataFilters {
     pub email: Option<OptionEmailFilters>,
     /// User avatar filters.
     pub avatar: Option<OptionAvatarFilters>,
+    /// User account status filters.
+    pub status: Option<AccountStatusFilters>,
+    /// User bio filters.
+    pub bio: Option<OptionBioFilters>,
+    /// User locale filters.
+    pub locale: Option<OptionLocaleFilters>,
+    /// User time zone filters.
+    pub time_zone: Option<OptionTimeZoneFilters>,
+    /// User pronouns filters.
+    pub pronouns: Option<OptionPronounsFilters>,
 }
 
 impl From<DomainUserDataFilters<'_>> for UserDataFilters {
@@ -175,6 +287,11 @@ impl From<DomainUserDataFilters<'_>> for UserDataFilters {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = filters;
         Self {
             name: name.map(Into::into),
@@ -182,6 +299,11 @@ impl From<DomainUserDataFilters<'_>> for UserDataFilters {
             role: role.map(Into::into),
             email: email.map(Into::into),
             avatar: avatar.map(Into::into),
+            status: status.map(Into::into),
+            bio: bio.map(Into::into),
+            locale: locale.map(Into::into),
+            time_zone: time_zone.map(Into::into),
+            pronouns: pronouns.map(Into::into),
         }
     }
 }
@@ -196,6 +318,11 @@ impl TryFrom<UserDataFilters> for DomainUserDataFilters<'_> {
             role,
             email,
             avatar,
+            status,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = filters;
         let filters = Self {
             name: name.map(TryInto::try_into).transpose()?,
@@ -203,6 +330,11 @@ impl TryFrom<UserDataFilters> for DomainUserDataFilters<'_> {
             role: role.map(Into::into),
             email: email.map(TryInto::try_into).transpose()?,
             avatar: avatar.map(TryInto::try_into).transpose()?,
+            status: status.map(Into::into),
+            bio: bio.map(TryInto::try_into).transpose()?,
+            locale: locale.map(TryInto::try_into).transpose()?,
+            time_zone: time_zone.map(TryInto::try_into).transpose()?,
+            pronouns: pronouns.map(TryInto::try_into).transpose()?,
         };
         Ok(filters)
     }
@@ -220,4 +352,14 @@ pub enum TryFromUserDataError {
     Email(EmailError),
     /// Avatar does not meet domain requirements.
     Avatar(AvatarError),
+    /// Password does not meet domain requirements.
+    Password(PasswordError),
+    /// Bio does not meet domain requirements.
+    Bio(BioError),
+    /// Locale does not meet domain requirements.
+    Locale(LocaleError),
+    /// Time zone does not meet domain requirements.
+    TimeZone(TimeZoneError),
+    /// Pronouns do not meet domain requirements.
+    Pronouns(PronounsError),
 }
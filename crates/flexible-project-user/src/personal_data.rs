@@ -0,0 +1,147 @@
+//! Collection of the personal data of the user held by other services of the system.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use derive_more::{Display, Error, From};
+use fp_user_domain::{
+    model::{PersonalDataEntry as DomainPersonalDataEntry, UserId},
+    repository::PersonalDataSource,
+};
+use futures::StreamExt;
+use lapin::{
+    options::{BasicCancelOptions, BasicConsumeOptions, BasicPublishOptions, QueueDeclareOptions},
+    types::FieldTable,
+    BasicProperties, Channel, Connection,
+};
+
+use crate::model::{PersonalDataEntry, PersonalDataRequest};
+
+/// Configuration of the collection of personal data from other services.
+#[derive(Debug, Clone)]
+pub struct PersonalDataSourceConfig {
+    /// Queues of other services which hold personal data of the users.
+    pub queues: Vec<String>,
+    /// Duration during which all of the services must reply.
+    pub timeout: Duration,
+}
+
+impl Default for PersonalDataSourceConfig {
+    fn default() -> Self {
+        Self {
+            queues: Vec::new(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Implementation of personal data source which requests personal data
+/// from [queues](PersonalDataSourceConfig::queues) of other services of the system.
+///
+/// [Request](PersonalDataRequest) is sent into each queue with `reply_to` property
+/// of the exclusive queue declared for this collection only,
+/// and each service must reply with the list of [entries](PersonalDataEntry) into it.
+#[derive(Clone)]
+pub struct AmqpPersonalDataSource {
+    channel: Channel,
+    config: PersonalDataSourceConfig,
+}
+
+impl AmqpPersonalDataSource {
+    /// Creates new personal data source with its own channel of provided connection.
+    pub async fn new(
+        connection: &Connection,
+        config: PersonalDataSourceConfig,
+    ) -> Result<Self, AmqpPersonalDataError> {
+        let channel = connection.create_channel().await?;
+        Ok(Self { channel, config })
+    }
+
+    async fn request(&self, reply_to: &str, user_id: UserId) -> Result<(), AmqpPersonalDataError> {
+        let Self { channel, config } = self;
+        let request = PersonalDataRequest {
+            user_id: user_id.erase().into(),
+        };
+        let payload = serde_json::to_vec(&request)?;
+        for queue in &config.queues {
+            let properties = BasicProperties::default().with_reply_to(reply_to.into());
+            channel
+                .basic_publish(
+                    "",
+                    queue,
+                    BasicPublishOptions::default(),
+                    &payload,
+                    properties,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl PersonalDataSource for AmqpPersonalDataSource {
+    type Error = AmqpPersonalDataError;
+
+    async fn collect(&self, user_id: UserId) -> Result<Vec<DomainPersonalDataEntry>, Self::Error> {
+        let Self { channel, config } = self;
+        if config.queues.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let options = QueueDeclareOptions {
+            exclusive: true,
+            auto_delete: true,
+            ..Default::default()
+        };
+        let reply_queue = channel
+            .queue_declare("", options, FieldTable::default())
+            .await?;
+        let reply_to = reply_queue.name().as_str();
+        let options = BasicConsumeOptions {
+            no_ack: true,
+            exclusive: true,
+            ..Default::default()
+        };
+        let mut replies = channel
+            .basic_consume(reply_to, "", options, FieldTable::default())
+            .await?;
+
+        let collect = async {
+            self.request(reply_to, user_id).await?;
+            let mut entries = Vec::new();
+            for _ in &config.queues {
+                let Some(reply) = replies.next().await else {
+                    return Err(AmqpPersonalDataError::Closed);
+                };
+                let reply = reply?;
+                let reply: Vec<PersonalDataEntry> = serde_json::from_slice(&reply.data)?;
+                entries.extend(reply.into_iter().map(Into::into));
+            }
+            Ok(entries)
+        };
+        let entries = tokio::time::timeout(config.timeout, collect).await;
+        // exclusive queue is deleted by the server when its only consumer is cancelled
+        channel
+            .basic_cancel(replies.tag().as_str(), BasicCancelOptions::default())
+            .await?;
+        entries.map_err(|_| AmqpPersonalDataError::Timeout)?
+    }
+}
+
+/// Type of error which is returned when personal data cannot be collected from other services.
+#[derive(Debug, Display, From, Error)]
+pub enum AmqpPersonalDataError {
+    /// Request cannot be serialized or reply cannot be deserialized.
+    #[display(fmt = "invalid personal data message: {}", _0)]
+    Json(serde_json::Error),
+    /// AMQP server or connection error.
+    #[display(fmt = "AMQP error: {}", _0)]
+    Amqp(lapin::Error),
+    /// Consumer of the replies was closed before all of the services replied.
+    #[display(fmt = "personal data replies consumer was closed")]
+    Closed,
+    /// Some of the services did not reply in time.
+    #[display(fmt = "personal data was not collected in time")]
+    Timeout,
+}
@@ -1,8 +1,11 @@
 //! Definitions of requests are done by client of the user service.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::model::{ErasedId, Name, UserFilters};
+use crate::model::{
+    Content, Email, ErasedId, Name, Password, PasswordResetToken, RecordFormat, Role, UserFilters,
+};
 
 pub use self::update::UpdateUserInput;
 
@@ -15,12 +18,34 @@ pub enum Request {
     CreateUser {
         /// Name of the new user.
         name: Name,
+        /// Email of the new user, if any.
+        email: Option<Email>,
     },
     /// Filter users of the system.
     FilterUsers {
         /// User filters of the system.
         filters: Box<UserFilters>,
     },
+    /// Count users of the system.
+    CountUsers {
+        /// User filters of the system.
+        filters: Box<UserFilters>,
+    },
+    /// Count users of the system grouped by their role.
+    CountUsersByRole {
+        /// User filters of the system.
+        filters: Box<UserFilters>,
+    },
+    /// Find users of the system by their identifiers with one query.
+    FindUsersByIds {
+        /// Identifiers of the users to find.
+        ids: Vec<ErasedId>,
+    },
+    /// Find user of the system by its current or recent former name.
+    FindUserByName {
+        /// Current or former name of the user.
+        name: Name,
+    },
     /// Update data of existing user of the system.
     UpdateUser {
         /// Identifier of the user to update.
@@ -33,5 +58,86 @@ pub enum Request {
         /// Identifier of the user to delete.
         current_id: ErasedId,
     },
+    /// Upload image as an avatar of existing user of the system.
+    UploadAvatar {
+        /// Identifier of the user to upload avatar of.
+        current_id: ErasedId,
+        /// Content of the uploaded image.
+        content: Content,
+    },
+    /// Change role of existing user of the system.
+    ChangeRole {
+        /// Identifier of the user to change role of.
+        current_id: ErasedId,
+        /// New role of the user.
+        role: Role,
+    },
+    /// Request password reset of the user by its email.
+    RequestPasswordReset {
+        /// Email of the user which forgot its password.
+        email: Email,
+    },
+    /// Reset password of the user with the token from password reset request.
+    ResetPassword {
+        /// Secret token which was sent to the user.
+        token: PasswordResetToken,
+        /// New password of the user.
+        password: Password,
+    },
+    /// Suspend user of the system until some point in time.
+    SuspendUser {
+        /// Identifier of the user to suspend.
+        current_id: ErasedId,
+        /// Time when suspension ends.
+        until: DateTime<Utc>,
+        /// Reason of the suspension.
+        reason: String,
+    },
+    /// Lift suspension of the user of the system.
+    UnsuspendUser {
+        /// Identifier of the user to unsuspend.
+        current_id: ErasedId,
+    },
+    /// Ban user of the system permanently.
+    BanUser {
+        /// Identifier of the user to ban.
+        current_id: ErasedId,
+        /// Reason of the ban.
+        reason: String,
+    },
+    /// Import users into the system in bulk.
+    ImportUsers {
+        /// Format of the imported records.
+        format: RecordFormat,
+        /// Content with the records of the users.
+        content: Content,
+        /// Whether records should be only validated without storing imported users.
+        #[serde(default)]
+        dry_run: bool,
+    },
+    /// Export users of the system in bulk.
+    ExportUsers {
+        /// Format of the exported records.
+        format: RecordFormat,
+        /// User filters of the system.
+        filters: Box<UserFilters>,
+    },
+    /// Export all personal data of the user of the system.
+    ExportPersonalData {
+        /// Identifier of the user which personal data is exported.
+        current_id: ErasedId,
+    },
+    /// Erase all data of the user from the system.
+    EraseUser {
+        /// Identifier of the user to erase.
+        current_id: ErasedId,
+    },
+    /// Sign in user of the system by its name and password.
+    SignIn {
+        /// Name of the user.
+        name: Name,
+        /// Password of the user.
+        password: Password,
+    },
     // TODO other updates
 }
@@ -2,7 +2,9 @@ use fp_user_domain::use_case::UpdateUserInput as DomainUpdateUserInput;
 use serde::{Deserialize, Serialize};
 use serde_with::{rust::double_option, skip_serializing_none};
 
-use crate::model::{Avatar, DisplayName, Email, Name, TryFromUserDataError};
+use crate::model::{
+    Avatar, Bio, DisplayName, Email, Locale, Name, Pronouns, TimeZone, TryFromUserDataError,
+};
 
 /// Serializable [input](DomainUpdateUserInput) of the update user interactor.
 #[skip_serializing_none]
@@ -18,6 +20,18 @@ pub struct UpdateUserInput {
     /// Avatar of the user to update, if present.
     #[serde(with = "double_option")]
     pub avatar: Option<Option<Avatar>>,
+    /// Bio of the user to update, if present.
+    #[serde(with = "double_option")]
+    pub bio: Option<Option<Bio>>,
+    /// Preferred locale of the user to update, if present.
+    #[serde(with = "double_option")]
+    pub locale: Option<Option<Locale>>,
+    /// Time zone of the user to update, if present.
+    #[serde(with = "double_option")]
+    pub time_zone: Option<Option<TimeZone>>,
+    /// Pronouns of the user to update, if present.
+    #[serde(with = "double_option")]
+    pub pronouns: Option<Option<Pronouns>>,
 }
 
 impl From<DomainUpdateUserInput> for UpdateUserInput {
@@ -27,12 +41,20 @@ impl From<DomainUpdateUserInput> for UpdateUserInput {
             display_name,
             email,
             avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = input;
         Self {
             name: name.map(Into::into),
             display_name: display_name.map(Into::into),
             email: email.map(|email| email.map(Into::into)),
             avatar: avatar.map(|avatar| avatar.map(Into::into)),
+            bio: bio.map(|bio| bio.map(Into::into)),
+            locale: locale.map(|locale| locale.map(Into::into)),
+            time_zone: time_zone.map(|time_zone| time_zone.map(Into::into)),
+            pronouns: pronouns.map(|pronouns| pronouns.map(Into::into)),
         }
     }
 }
@@ -46,6 +68,10 @@ impl TryFrom<UpdateUserInput> for DomainUpdateUserInput {
             display_name,
             email,
             avatar,
+            bio,
+            locale,
+            time_zone,
+            pronouns,
         } = input;
         let input = Self {
             name: name.map(TryInto::try_into).transpose()?,
@@ -56,6 +82,18 @@ impl TryFrom<UpdateUserInput> for DomainUpdateUserInput {
             avatar: avatar
                 .map(|avatar| avatar.map(TryInto::try_into).transpose())
                 .transpose()?,
+            bio: bio
+                .map(|bio| bio.map(TryInto::try_into).transpose())
+                .transpose()?,
+            locale: locale
+                .map(|locale| locale.map(TryInto::try_into).transpose())
+                .transpose()?,
+            time_zone: time_zone
+                .map(|time_zone| time_zone.map(TryInto::try_into).transpose())
+                .transpose()?,
+            pronouns: pronouns
+                .map(|pronouns| pronouns.map(TryInto::try_into).transpose())
+                .transpose()?,
         };
         Ok(input)
     }
@@ -0,0 +1,453 @@
+use std::fmt::Display;
+
+use fp_user_domain::use_case::{
+    BanUserError, ChangeRoleError, CreateUserError, DeleteUserError, EraseUserError,
+    ExportPersonalDataError, ExportUsersError, FindUserByNameError, ImportUsersError,
+    ReadUsersError, ResetPasswordError, SignInError, SuspendUserError, UnsuspendUserError,
+    UpdateUserError, UploadAvatarError,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::model::{TryFromUserDataError, ValidationError, ValidationField};
+
+/// Serializable error which is returned when request of the client cannot be fulfilled.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseError {
+    /// Machine readable code of the error.
+    pub code: ResponseErrorCode,
+    /// Human readable description of the error.
+    pub message: String,
+    /// Field of the input and machine readable reason why it was rejected, if any.
+    pub validation: Option<ValidationError>,
+}
+
+/// Machine readable code of the [response error](ResponseError).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ResponseErrorCode {
+    /// Input of the request does not meet domain requirements.
+    InvalidInput,
+    /// Name or password of the user is invalid.
+    InvalidCredentials,
+    /// Password reset token is invalid or has expired.
+    InvalidToken,
+    /// Request requires authenticated actor, but it was not provided or does not exist.
+    Unauthenticated,
+    /// Actor is not allowed to perform the request.
+    Forbidden,
+    /// Actor or the user is suspended or banned.
+    Inactive,
+    /// No user was found by provided identifier.
+    NotFound,
+    /// Name or email of the user is already taken.
+    AlreadyTaken,
+    /// Request is rejected by policies of the system.
+    Rejected,
+    /// Request is not supported by this instance of the service.
+    Unavailable,
+    /// Internal error of the service.
+    Internal,
+}
+
+impl ResponseError {
+    /// Creates new response error with provided code and description of the error.
+    ///
+    /// Description of the [internal](ResponseErrorCode::Internal) error is only logged,
+    /// so details of the service failure are not exposed to the clients.
+    pub fn new(code: ResponseErrorCode, error: impl Display) -> Self {
+        let message = match code {
+            ResponseErrorCode::Internal => {
+                tracing::error!(%error, "failed to handle request");
+                "internal error".to_string()
+            }
+            _ => error.to_string(),
+        };
+        Self {
+            code,
+            message,
+            validation: None,
+        }
+    }
+
+    /// Creates new error of the input which does not meet domain requirements.
+    pub fn invalid(validation: ValidationError) -> Self {
+        let message = validation.message.clone();
+        Self {
+            code: ResponseErrorCode::InvalidInput,
+            message,
+            validation: Some(validation),
+        }
+    }
+
+    /// Creates new internal error of the service.
+    pub fn internal(error: impl Display) -> Self {
+        Self::new(ResponseErrorCode::Internal, error)
+    }
+
+    /// Creates new error of the request which requires authenticated actor.
+    pub fn unauthenticated() -> Self {
+        Self::new(
+            ResponseErrorCode::Unauthenticated,
+            "request is not authenticated",
+        )
+    }
+
+    /// Creates new error of the request which is not supported by this instance of the service.
+    pub fn unavailable(request: &str) -> Self {
+        let message = format!("{request} is not available");
+        Self::new(ResponseErrorCode::Unavailable, message)
+    }
+}
+
+impl From<TryFromUserDataError> for ResponseError {
+    fn from(error: TryFromUserDataError) -> Self {
+        Self::invalid(error.into())
+    }
+}
+
+impl<DatabaseError, HistoryError, GenerateIdError>
+    From<CreateUserError<DatabaseError, HistoryError, GenerateIdError>> for ResponseError
+where
+    DatabaseError: Display,
+    HistoryError: Display,
+    GenerateIdError: Display,
+{
+    fn from(error: CreateUserError<DatabaseError, HistoryError, GenerateIdError>) -> Self {
+        let validation = match &error {
+            &CreateUserError::InvalidDisplayName(_, error) => {
+                Some(ValidationError::new(ValidationField::DisplayName, error))
+            }
+            _ => None,
+        };
+        let code = match &error {
+            CreateUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            CreateUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            CreateUserError::NameAlreadyTaken(_) | CreateUserError::EmailAlreadyTaken(_) => {
+                ResponseErrorCode::AlreadyTaken
+            }
+            CreateUserError::InvalidDisplayName(_, _) => ResponseErrorCode::InvalidInput,
+            CreateUserError::ConfusableDisplayName(_) | CreateUserError::EmailNotAllowed(_, _) => {
+                ResponseErrorCode::Rejected
+            }
+            CreateUserError::Database(_)
+            | CreateUserError::NameHistory(_)
+            | CreateUserError::GenerateId(_) => ResponseErrorCode::Internal,
+        };
+        Self {
+            validation,
+            ..Self::new(code, error)
+        }
+    }
+}
+
+impl<Error> From<ReadUsersError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: ReadUsersError<Error>) -> Self {
+        let code = match &error {
+            ReadUsersError::Forbidden => ResponseErrorCode::Forbidden,
+            ReadUsersError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, HistoryError> From<FindUserByNameError<DatabaseError, HistoryError>>
+    for ResponseError
+where
+    DatabaseError: Display,
+    HistoryError: Display,
+{
+    fn from(error: FindUserByNameError<DatabaseError, HistoryError>) -> Self {
+        Self::internal(error)
+    }
+}
+
+impl<Error, HistoryError> From<UpdateUserError<Error, HistoryError>> for ResponseError
+where
+    Error: Display,
+    HistoryError: Display,
+{
+    fn from(error: UpdateUserError<Error, HistoryError>) -> Self {
+        let validation = match &error {
+            &UpdateUserError::AvatarNotAllowed(_, error) => {
+                Some(ValidationError::new(ValidationField::Avatar, error))
+            }
+            _ => None,
+        };
+        let code = match &error {
+            UpdateUserError::Forbidden(_, _) => ResponseErrorCode::Forbidden,
+            UpdateUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            UpdateUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            UpdateUserError::NameAlreadyTaken(_) | UpdateUserError::EmailAlreadyTaken(_) => {
+                ResponseErrorCode::AlreadyTaken
+            }
+            UpdateUserError::ConfusableDisplayName(_)
+            | UpdateUserError::EmailNotAllowed(_, _)
+            | UpdateUserError::AvatarNotAllowed(_, _) => ResponseErrorCode::Rejected,
+            UpdateUserError::Database(_) | UpdateUserError::NameHistory(_) => {
+                ResponseErrorCode::Internal
+            }
+        };
+        Self {
+            validation,
+            ..Self::new(code, error)
+        }
+    }
+}
+
+impl<DatabaseError, StoreError, ImageError>
+    From<UploadAvatarError<DatabaseError, StoreError, ImageError>> for ResponseError
+where
+    DatabaseError: Display,
+    StoreError: Display,
+    ImageError: Display,
+{
+    fn from(error: UploadAvatarError<DatabaseError, StoreError, ImageError>) -> Self {
+        let code = match &error {
+            UploadAvatarError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            UploadAvatarError::Inactive(_) => ResponseErrorCode::Inactive,
+            UploadAvatarError::NoUser(_) => ResponseErrorCode::NotFound,
+            UploadAvatarError::TooLarge { .. }
+            | UploadAvatarError::UnsupportedFormat
+            | UploadAvatarError::Image(_) => ResponseErrorCode::InvalidInput,
+            UploadAvatarError::Avatar(_)
+            | UploadAvatarError::Database(_)
+            | UploadAvatarError::ObjectStore(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<Error> From<DeleteUserError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: DeleteUserError<Error>) -> Self {
+        let code = match &error {
+            DeleteUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            DeleteUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            DeleteUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            DeleteUserError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<Error> From<ChangeRoleError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: ChangeRoleError<Error>) -> Self {
+        let code = match &error {
+            ChangeRoleError::NoUser(_) => ResponseErrorCode::NotFound,
+            ChangeRoleError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            ChangeRoleError::Inactive(_) => ResponseErrorCode::Inactive,
+            ChangeRoleError::LastAdministrator(_) => ResponseErrorCode::Rejected,
+            ChangeRoleError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, CredentialsError, ResetDatabaseError, HashError>
+    From<ResetPasswordError<DatabaseError, CredentialsError, ResetDatabaseError, HashError>>
+    for ResponseError
+where
+    DatabaseError: Display,
+    CredentialsError: Display,
+    ResetDatabaseError: Display,
+    HashError: Display,
+{
+    fn from(
+        error: ResetPasswordError<DatabaseError, CredentialsError, ResetDatabaseError, HashError>,
+    ) -> Self {
+        let code = match &error {
+            ResetPasswordError::InvalidToken | ResetPasswordError::ExpiredToken => {
+                ResponseErrorCode::InvalidToken
+            }
+            ResetPasswordError::NoUser(_) => ResponseErrorCode::NotFound,
+            ResetPasswordError::Database(_)
+            | ResetPasswordError::Credentials(_)
+            | ResetPasswordError::ResetDatabase(_)
+            | ResetPasswordError::HashPassword(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<Error> From<SuspendUserError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: SuspendUserError<Error>) -> Self {
+        let code = match &error {
+            SuspendUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            SuspendUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            SuspendUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            SuspendUserError::Banned(_) => ResponseErrorCode::Rejected,
+            SuspendUserError::InvalidUntil(_) => ResponseErrorCode::InvalidInput,
+            SuspendUserError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<Error> From<UnsuspendUserError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: UnsuspendUserError<Error>) -> Self {
+        let code = match &error {
+            UnsuspendUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            UnsuspendUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            UnsuspendUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            UnsuspendUserError::NotSuspended(_) => ResponseErrorCode::Rejected,
+            UnsuspendUserError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<Error> From<BanUserError<Error>> for ResponseError
+where
+    Error: Display,
+{
+    fn from(error: BanUserError<Error>) -> Self {
+        let code = match &error {
+            BanUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            BanUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            BanUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            BanUserError::Database(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, HistoryError, GenerateIdError>
+    From<ImportUsersError<DatabaseError, HistoryError, GenerateIdError>> for ResponseError
+where
+    DatabaseError: Display,
+    HistoryError: Display,
+    GenerateIdError: Display,
+{
+    fn from(error: ImportUsersError<DatabaseError, HistoryError, GenerateIdError>) -> Self {
+        let code = match &error {
+            ImportUsersError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            ImportUsersError::Inactive(_) => ResponseErrorCode::Inactive,
+            ImportUsersError::Database(_)
+            | ImportUsersError::NameHistory(_)
+            | ImportUsersError::GenerateId(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, FormatError> From<ExportUsersError<DatabaseError, FormatError>>
+    for ResponseError
+where
+    DatabaseError: Display,
+    FormatError: Display,
+{
+    fn from(error: ExportUsersError<DatabaseError, FormatError>) -> Self {
+        let code = match &error {
+            ExportUsersError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            ExportUsersError::Inactive(_) => ResponseErrorCode::Inactive,
+            ExportUsersError::Database(_) | ExportUsersError::Format(_) => {
+                ResponseErrorCode::Internal
+            }
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, CredentialsError, HistoryError, SourceError>
+    From<ExportPersonalDataError<DatabaseError, CredentialsError, HistoryError, SourceError>>
+    for ResponseError
+where
+    DatabaseError: Display,
+    CredentialsError: Display,
+    HistoryError: Display,
+    SourceError: Display,
+{
+    fn from(
+        error: ExportPersonalDataError<DatabaseError, CredentialsError, HistoryError, SourceError>,
+    ) -> Self {
+        let code = match &error {
+            ExportPersonalDataError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            ExportPersonalDataError::Inactive(_) => ResponseErrorCode::Inactive,
+            ExportPersonalDataError::NoUser(_) => ResponseErrorCode::NotFound,
+            ExportPersonalDataError::Database(_)
+            | ExportPersonalDataError::Credentials(_)
+            | ExportPersonalDataError::NameHistory(_)
+            | ExportPersonalDataError::Source(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, CredentialsError, HistoryError, ResetError, StoreError, PublishError>
+    From<
+        EraseUserError<
+            DatabaseError,
+            CredentialsError,
+            HistoryError,
+            ResetError,
+            StoreError,
+            PublishError,
+        >,
+    > for ResponseError
+where
+    DatabaseError: Display,
+    CredentialsError: Display,
+    HistoryError: Display,
+    ResetError: Display,
+    StoreError: Display,
+    PublishError: Display,
+{
+    fn from(
+        error: EraseUserError<
+            DatabaseError,
+            CredentialsError,
+            HistoryError,
+            ResetError,
+            StoreError,
+            PublishError,
+        >,
+    ) -> Self {
+        let code = match &error {
+            EraseUserError::Forbidden(_) => ResponseErrorCode::Forbidden,
+            EraseUserError::Inactive(_) => ResponseErrorCode::Inactive,
+            EraseUserError::NoUser(_) => ResponseErrorCode::NotFound,
+            EraseUserError::Database(_)
+            | EraseUserError::Credentials(_)
+            | EraseUserError::NameHistory(_)
+            | EraseUserError::PasswordReset(_)
+            | EraseUserError::ObjectStore(_)
+            | EraseUserError::Publish(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl<DatabaseError, CredentialsError, HashError>
+    From<SignInError<DatabaseError, CredentialsError, HashError>> for ResponseError
+where
+    DatabaseError: Display,
+    CredentialsError: Display,
+    HashError: Display,
+{
+    fn from(error: SignInError<DatabaseError, CredentialsError, HashError>) -> Self {
+        let code = match &error {
+            SignInError::InvalidCredentials => ResponseErrorCode::InvalidCredentials,
+            SignInError::Suspended(_) | SignInError::Banned => ResponseErrorCode::Inactive,
+            SignInError::Database(_)
+            | SignInError::Credentials(_)
+            | SignInError::HashPassword(_) => ResponseErrorCode::Internal,
+        };
+        Self::new(code, error)
+    }
+}
@@ -0,0 +1,59 @@
+//! Definitions of responses are sent by the user service to its clients.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+
+use crate::model::{FoundUsers, ImportReport, PersonalData, Role, User, UserByName};
+
+pub use self::error::{ResponseError, ResponseErrorCode};
+
+mod error;
+
+/// Response of the user service on the request of its clients.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Response {
+    /// User which was created, updated, deleted or signed in by the request.
+    User(User),
+    /// Users of the system which satisfy provided filters.
+    Users(Vec<User>),
+    /// Count of users of the system which satisfy provided filters.
+    Count(u64),
+    /// Count of users of the system which satisfy provided filters grouped by their role.
+    CountByRole(BTreeMap<Role, u64>),
+    /// Users of the system which were found by their identifiers.
+    FoundUsers(FoundUsers),
+    /// User of the system which was found by its current or recent former name, if any.
+    UserByName(Option<UserByName>),
+    /// Report of the users import.
+    ImportReport(ImportReport),
+    /// Users import was aborted, but users of the report were already imported.
+    ImportAborted {
+        /// Report of the records processed before the import was aborted.
+        report: ImportReport,
+        /// Error which aborted the import.
+        error: ResponseError,
+    },
+    /// Content with the records of the exported users.
+    ExportedUsers(#[serde_as(as = "Base64")] Vec<u8>),
+    /// All personal data of the user held by the system.
+    PersonalData(Box<PersonalData>),
+    /// Request was done with no data to respond with.
+    Done,
+    /// Request cannot be fulfilled.
+    Error(ResponseError),
+}
+
+impl<Error> From<Result<Self, Error>> for Response
+where
+    Error: Into<ResponseError>,
+{
+    fn from(result: Result<Self, Error>) -> Self {
+        match result {
+            Ok(response) => response,
+            Err(error) => Self::Error(error.into()),
+        }
+    }
+}
@@ -1,9 +1,7 @@
 use std::borrow::Borrow;
 
 use derive_more::{Display, Error};
-use fancy_regex::Regex as FancyRegex;
 use fp_filter::{Equal, Filter, In, NotEqual, NotIn, Regex};
-use once_cell::sync::Lazy;
 use typed_builder::TypedBuilder;
 
 /// Name of the workspace in the system with strong requirements about its content.
@@ -11,6 +9,7 @@ use typed_builder::TypedBuilder;
 /// These requirements are:
 /// - must not be empty;
 /// - must not be larger than 128 characters in length;
+/// - must not contain control characters;
 /// - must contain at least one letter.
 #[derive(Debug, Display, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Name(String);
@@ -23,16 +22,32 @@ impl Name {
     /// This function will return an error
     /// if input string does not match workspace name requirements.
     pub fn new(name: impl Into<String>) -> Result<Self, NameError> {
-        static REGEX: Lazy<FancyRegex> = Lazy::new(|| {
-            FancyRegex::new(r"^(?=.*?\p{L}).{1,128}$").expect("regex pattern should be parsed")
-        });
+        const MIN_LENGTH: usize = 1;
+        const MAX_LENGTH: usize = 128;
 
         let name = name.into();
-        let is_valid = REGEX
-            .is_match(&name)
-            .expect("input name matching should be successful");
-        if !is_valid {
-            return Err(NameError::Invalid);
+        let length = name.chars().count();
+        if length < MIN_LENGTH {
+            let error = NameError::TooShort {
+                min: MIN_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+        if length > MAX_LENGTH {
+            let error = NameError::TooLong {
+                max: MAX_LENGTH,
+                actual: length,
+            };
+            return Err(error);
+        }
+
+        let invalid_character = name.chars().enumerate().find(|(_, ch)| ch.is_control());
+        if let Some((position, ch)) = invalid_character {
+            return Err(NameError::InvalidCharacter { ch, position });
+        }
+        if !name.chars().any(char::is_alphabetic) {
+            return Err(NameError::NoLetter);
         }
         Ok(Self(name))
     }
@@ -51,11 +66,49 @@ impl Name {
 }
 
 /// Type of error which is returned when input does not meet workspace name requirements.
-#[derive(Debug, Display, Clone, Copy, Error)]
+///
+/// Positions of the characters are counted in characters, not bytes, starting from zero.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum NameError {
-    /// Workspace name does not meet requirements.
-    #[display(fmt = "workspace name does not meet requirements")]
-    Invalid,
+    /// Workspace name has fewer characters than required.
+    #[display(
+        fmt = "workspace name must have at least {} characters, got {}",
+        min,
+        actual
+    )]
+    TooShort {
+        /// Minimal count of characters.
+        min: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Workspace name has more characters than allowed.
+    #[display(
+        fmt = "workspace name must have at most {} characters, got {}",
+        max,
+        actual
+    )]
+    TooLong {
+        /// Maximal count of characters.
+        max: usize,
+        /// Actual count of characters.
+        actual: usize,
+    },
+    /// Workspace name contains character which is not allowed.
+    #[display(
+        fmt = "workspace name contains invalid character {:?} at position {}",
+        ch,
+        position
+    )]
+    InvalidCharacter {
+        /// Character which is not allowed.
+        ch: char,
+        /// Position of the character.
+        position: usize,
+    },
+    /// Workspace name does not contain any letter.
+    #[display(fmt = "workspace name must contain at least one letter")]
+    NoLetter,
 }
 
 /// Filters for user name of the backend.
@@ -10,7 +10,7 @@ use typed_builder::TypedBuilder;
 use super::{
     description::{Description, DescriptionFilters},
     id::{WorkspaceId, WorkspaceIdFilters},
-    member::{Member, MemberFilters},
+    member::{Member, MemberFilters, MemberId},
     name::{Name, NameFilters},
     role::{Role, RoleFilters},
     visibility::{Visibility, VisibilityFilters},
@@ -60,6 +60,16 @@ pub struct WorkspaceData {
     pub members: Members,
 }
 
+impl WorkspaceData {
+    /// Removes member by provided identifier from the workspace, if any.
+    ///
+    /// Should be used to scrub references to the user after it was erased by the user service.
+    pub fn remove_member(&mut self, id: &MemberId) -> Option<Member> {
+        let index = self.members.iter().position(|member| &member.id == id)?;
+        self.members.shift_remove_index(index)
+    }
+}
+
 /// Filters for workspaces of the backend.
 #[derive(Debug, Clone, Default, TypedBuilder)]
 #[builder(field_defaults(default, setter(into, strip_option)))]
@@ -1,3 +1,5 @@
 //! Definitions and utilities for objects which have access to the outer environment.
 
-// TODO repository traits
+pub use self::workspace::WorkspaceDatabase;
+
+mod workspace;
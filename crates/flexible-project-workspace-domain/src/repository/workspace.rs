@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use futures::Stream;
+
+use crate::model::{Workspace, WorkspaceFilters};
+
+/// Database of workspaces of the system.
+#[async_trait(?Send)]
+#[auto_impl(&, Box, Rc, Arc)]
+pub trait WorkspaceDatabase {
+    /// The type returned when a repository fails to apply an operation.
+    type Error;
+
+    /// Type of stream which produces filtered repository data.
+    type Workspaces: Stream<Item = Result<Workspace, Self::Error>>;
+    /// Filters workspaces by provided filter object.
+    async fn read(&self, filter: WorkspaceFilters<'_>) -> Result<Self::Workspaces, Self::Error>;
+
+    /// Replaces data of existing workspace with data of provided workspace.
+    ///
+    /// Returns updated workspace or an error if no workspace exists by its identifier.
+    async fn update(&self, workspace: Workspace) -> Result<Workspace, Self::Error>;
+}
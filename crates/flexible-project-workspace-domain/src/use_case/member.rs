@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use derive_more::{Display, Error};
+use fp_filter::Equal;
+use futures::TryStreamExt;
+
+use crate::{
+    model::{
+        MemberFilters, MemberId, MemberIdFilters, MembersFilters, Workspace, WorkspaceDataFilters,
+        WorkspaceFilters,
+    },
+    repository::WorkspaceDatabase,
+};
+
+/// Error type of remove erased member use case.
+#[derive(Debug, Display, Error)]
+pub enum RemoveErasedMemberError<DatabaseError> {
+    /// Database error.
+    #[display(fmt = "database error: {}", _0)]
+    Database(DatabaseError),
+}
+
+/// Remove erased member interactor.
+///
+/// Consumes `erased` events of the user service,
+/// so no workspace keeps references to the user which was erased from the system.
+pub struct RemoveErasedMember<Database>
+where
+    Database: WorkspaceDatabase,
+{
+    database: Database,
+}
+
+impl<Database> RemoveErasedMember<Database>
+where
+    Database: WorkspaceDatabase,
+{
+    /// Creates new remove erased member interactor.
+    pub fn new(database: Database) -> Self {
+        Self { database }
+    }
+
+    /// Removes the erased user by provided identifier from members of all workspaces.
+    ///
+    /// Removal is idempotent, because the same event could be consumed more than once.
+    /// Returns count of workspaces which the user was removed from.
+    pub async fn remove_erased_member(
+        &self,
+        member_id: MemberId,
+    ) -> Result<u64, RemoveErasedMemberError<Database::Error>> {
+        let Self { database } = self;
+
+        let filter = {
+            let id = MemberIdFilters::builder()
+                .eq(Equal(Cow::Borrowed(&member_id)))
+                .build();
+            let member = MemberFilters::builder().id(id).build();
+            let members = MembersFilters::builder().contains(member).build();
+            let data = WorkspaceDataFilters::builder().members(members).build();
+            WorkspaceFilters::builder().data(data).build()
+        };
+        let workspaces: Vec<_> = database
+            .read(filter)
+            .await
+            .map_err(RemoveErasedMemberError::Database)?
+            .try_collect()
+            .await
+            .map_err(RemoveErasedMemberError::Database)?;
+
+        let mut count = 0;
+        for Workspace { id, mut data } in workspaces {
+            if data.remove_member(&member_id).is_none() {
+                continue;
+            }
+            database
+                .update(Workspace { id, data })
+                .await
+                .map_err(RemoveErasedMemberError::Database)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
@@ -1,3 +1,5 @@
 //! Use cases of the workspace microservice domain layer.
 
-// TODO use case structs
+pub use self::member::{RemoveErasedMember, RemoveErasedMemberError};
+
+mod member;